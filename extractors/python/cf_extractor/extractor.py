@@ -5,6 +5,7 @@ Only module-level and class-level symbols; no local variables.
 
 import ast
 import os
+import re
 from pathlib import Path
 from typing import Optional
 
@@ -109,6 +110,34 @@ def _is_trivial_body(node: ast.FunctionDef | ast.AsyncFunctionDef) -> bool:
     return False
 
 
+def _decorator_names(node: ast.FunctionDef | ast.AsyncFunctionDef | ast.ClassDef) -> set[str]:
+    """Bare names of decorators: `@staticmethod`, `@builtins.staticmethod` and `@deco(...)` -> last segment."""
+    names = set()
+    for d in node.decorator_list:
+        if isinstance(d, ast.Call):
+            d = d.func
+        if isinstance(d, ast.Name):
+            names.add(d.id)
+        elif isinstance(d, ast.Attribute):
+            names.add(d.attr)
+    return names
+
+
+def _is_frozen_dataclass(node: ast.ClassDef) -> bool:
+    """`@dataclass(frozen=True)` / `@dataclasses.dataclass(frozen=True)`."""
+    for d in node.decorator_list:
+        if not isinstance(d, ast.Call):
+            continue
+        func = d.func
+        name = func.id if isinstance(func, ast.Name) else getattr(func, "attr", None)
+        if name != "dataclass":
+            continue
+        for kw in d.keywords:
+            if kw.arg == "frozen" and isinstance(kw.value, ast.Constant) and kw.value.value is True:
+                return True
+    return False
+
+
 def _get_docstring(node: ast.AsyncFunctionDef | ast.FunctionDef | ast.ClassDef | ast.Module) -> list[str]:
     docs = []
     doc = ast.get_docstring(node)
@@ -129,6 +158,36 @@ def _get_docstring(node: ast.AsyncFunctionDef | ast.FunctionDef | ast.ClassDef |
     return docs
 
 
+_RAISES_ENTRY = re.compile(r"([A-Za-z_][\w.]*)\s*(?::|$)")
+
+
+def _raised_types(node: ast.FunctionDef | ast.AsyncFunctionDef) -> list[str]:
+    """Exception types as written in a docstring `Raises` section (Google or NumPy style).
+    `raise` statements are not scanned: `throws` carries declarations only."""
+    lines = (ast.get_docstring(node) or "").splitlines()
+    for i, line in enumerate(lines):
+        if line.strip() not in ("Raises:", "Raises"):
+            continue
+        section: list[str] = []
+        for entry in lines[i + 1 :]:
+            if not entry.strip():
+                break
+            if set(entry.strip()) != {"-"}:
+                section.append(entry)
+        if not section:
+            return []
+        # Entries sit at the section's smallest indent; deeper lines continue a description
+        entry_indent = min(len(e) - len(e.lstrip()) for e in section)
+        names = [
+            match.group(1)
+            for entry in section
+            if len(entry) - len(entry.lstrip()) == entry_indent
+            and (match := _RAISES_ENTRY.match(entry.strip()))
+        ]
+        return list(dict.fromkeys(names))
+    return []
+
+
 def _annotation_to_typeref(annotation: Optional[ast.expr]) -> Optional[str]:
     if annotation is None:
         return None
@@ -228,10 +287,18 @@ class DefinitionCollector(ast.NodeVisitor):
         elif "Protocol" in bases or "ABC" in bases or is_abstract:
             type_kind = TypeKind.Interface
 
+        # Pure data types: generated __init__ etc. carry no behavior (CF boundary).
+        is_named_tuple = any(b == "NamedTuple" or b.endswith(".NamedTuple") for b in bases)
+        is_data_class = "dataclass" in _decorator_names(node) or is_named_tuple
+        # Instances cannot change after construction: fields are immutable for Read pruning.
+        is_frozen = is_named_tuple or _is_frozen_dataclass(node)
+
         type_details = TypeDetails(
             kind=type_kind,
             is_abstract=is_abstract,
             is_final=False,
+            is_data_class=is_data_class,
+            is_frozen=is_frozen,
             visibility=_visibility_from_name(node.name),
             type_params=[],
             fields=[],
@@ -280,8 +347,14 @@ class DefinitionCollector(ast.NodeVisitor):
         num_defaults = len(node.args.defaults) if node.args.defaults else 0
         def_start = num_args - num_defaults
         vararg_arg = getattr(node.args.vararg, "arg", None) if node.args.vararg else None
+        decorators = _decorator_names(node)
+        is_static = "staticmethod" in decorators
+        is_classmethod = "classmethod" in decorators
+        # Methods bind their first positional param implicitly (`self`, or `cls` for classmethods);
+        # staticmethods have no implicit receiver.
+        has_implicit_receiver = bool(self._class_stack) and not is_static
         for i, arg in enumerate(node.args.args):
-            if arg.arg in ("self", "cls"):
+            if i == 0 and has_implicit_receiver:
                 continue
             param_type = _annotation_to_typeref(getattr(arg, "annotation", None))
             has_default = i >= def_start
@@ -307,14 +380,6 @@ class DefinitionCollector(ast.NodeVisitor):
             or (isinstance(d, ast.Attribute) and getattr(d, "attr", None) == "abstractmethod")
             for d in node.decorator_list
         )
-        is_static = any(
-            isinstance(d, ast.Name) and d.id == "staticmethod"
-            for d in node.decorator_list
-        )
-        is_classmethod = any(
-            isinstance(d, ast.Name) and d.id == "classmethod"
-            for d in node.decorator_list
-        )
         is_constructor = node.name == "__init__"
         if is_constructor and not return_types:
             return_types = ["None"]
@@ -354,6 +419,7 @@ class DefinitionCollector(ast.NodeVisitor):
                     return_types=return_types,
                     type_params=[],
                     modifiers=modifiers,
+                    throws=_raised_types(node),
                 ),
             )
         )
@@ -803,6 +803,21 @@ class ReferenceCollector(ast.NodeVisitor):
             return candidates[0].symbol_id
         return normalized
 
+    def resolve_declared_throws(self, definitions: list[SymbolDefinition]) -> None:
+        """Rewrite each function's `throws` from names as written to type symbol ids, resolved
+        like annotations at the function's definition; unresolved names are kept as written."""
+        for definition in definitions:
+            details = definition.details
+            if not isinstance(details, FunctionDetails) or not details.throws:
+                continue
+            loc = definition.location
+            details.throws = list(
+                dict.fromkeys(
+                    self._normalize_type_ref(name, line=loc.line, column=loc.column) or name
+                    for name in details.throws
+                )
+            )
+
     def _is_likely_internal_symbol(self, symbol_id: str) -> bool:
         if symbol_id in self.definition_index.by_symbol_id:
             return True
@@ -1892,4 +1907,5 @@ def collect_references(
     add_parents(tree)
     collector.tree = tree
     collector.visit(tree)
+    collector.resolve_declared_throws(doc.definitions)
     return collector.references, list(collector.external_symbols.values())
@@ -106,6 +106,7 @@ class FunctionDetails(BaseModel):
     return_types: list[TypeRef] = PydanticField(default_factory=list)
     type_params: list[TypeParam] = PydanticField(default_factory=list)
     modifiers: FunctionModifiers = PydanticField(default_factory=FunctionModifiers)
+    throws: list[TypeRef] = PydanticField(default_factory=list)
 
 
 # --- Variable details ---
@@ -134,6 +135,8 @@ class TypeDetails(BaseModel):
     kind: TypeKind = TypeKind.Class
     is_abstract: bool = False
     is_final: bool = False
+    is_data_class: bool = False
+    is_frozen: bool = False
     visibility: Visibility = Visibility.Public
     type_params: list[TypeParam] = PydanticField(default_factory=list)
     fields: list[TypeField] = PydanticField(default_factory=list)
@@ -153,6 +156,7 @@ class SymbolDefinition(BaseModel):
     span: SourceSpan
     enclosing_symbol: Optional[SymbolId] = None
     is_external: bool = False
+    is_generated: bool = False
     documentation: list[str] = PydanticField(default_factory=list)
     details: FunctionDetails | VariableDetails | TypeDetails
 
@@ -12,6 +12,7 @@ import pytest
 # Add package to path when running tests without install
 sys.path.insert(0, str(Path(__file__).resolve().parent.parent))
 
+from cf_extractor.extractor import extract_definitions_from_file
 from cf_extractor.main import find_python_files, run_extract, run_extract_with_metrics
 from cf_extractor.resolvers.lsp_common import guess_symbol_name, module_name_from_path
 from cf_extractor.schema import ReferenceRole, SemanticData, SymbolKind
@@ -153,6 +154,147 @@ def test_builtin_decorator_emits_decorate_without_read():
     assert not any(r.role == ReferenceRole.Read and r.target_symbol == "builtins.classmethod" for r in refs)
 
 
+def test_classmethod_and_staticmethod_receiver_params(tmp_path: Path):
+    """classmethod skips `cls` like `self`; staticmethod keeps its first param. Both are is_static."""
+    source = """
+import builtins
+
+
+class Factory:
+    @classmethod
+    def create(cls, name: str) -> "Factory":
+        return cls()
+
+    @builtins.staticmethod
+    def parse(cls: int, raw: str) -> int:
+        return cls
+
+    def run(self, n: int) -> int:
+        return n
+""".strip() + "\n"
+    path = tmp_path / "sample.py"
+    path.write_text(source, encoding="utf-8")
+
+    doc = extract_definitions_from_file(str(path), source, str(tmp_path))
+    funcs = {d.symbol_id: d.details for d in doc.definitions if d.kind == SymbolKind.Function}
+
+    create = funcs["sample.Factory.create"]
+    assert [p.name for p in create.parameters] == ["name"]
+    assert create.parameters[0].param_type == "str"
+    assert create.modifiers.is_static
+
+    parse = funcs["sample.Factory.parse"]
+    assert [p.name for p in parse.parameters] == ["cls", "raw"]
+    assert parse.modifiers.is_static
+
+    run = funcs["sample.Factory.run"]
+    assert [p.name for p in run.parameters] == ["n"]
+    assert not run.modifiers.is_static
+
+
+def test_dataclass_and_namedtuple_marked_as_data_class(tmp_path: Path):
+    source = """
+import dataclasses
+from dataclasses import dataclass
+from typing import NamedTuple
+
+
+@dataclass
+class Point:
+    x: int = 0
+
+
+@dataclasses.dataclass(frozen=True)
+class Frozen:
+    y: int = 0
+
+
+class Pair(NamedTuple):
+    a: int
+    b: int
+
+
+class Plain:
+    pass
+""".strip() + "\n"
+    path = tmp_path / "sample.py"
+    path.write_text(source, encoding="utf-8")
+
+    doc = extract_definitions_from_file(str(path), source, str(tmp_path))
+    types = {d.symbol_id: d.details for d in doc.definitions if d.kind == SymbolKind.Type}
+
+    assert types["sample.Point"].is_data_class
+    assert types["sample.Frozen"].is_data_class
+    assert types["sample.Pair"].is_data_class
+    assert not types["sample.Plain"].is_data_class
+
+    assert not types["sample.Point"].is_frozen
+    assert types["sample.Frozen"].is_frozen
+    assert types["sample.Pair"].is_frozen
+    assert not types["sample.Plain"].is_frozen
+
+
+def test_docstring_raises_resolved_to_type_ids(tmp_path: Path):
+    (tmp_path / "errors.py").write_text(
+        """
+class NotFound(Exception):
+    pass
+
+
+class Conflict(Exception):
+    pass
+""".strip()
+        + "\n",
+        encoding="utf-8",
+    )
+    (tmp_path / "service.py").write_text(
+        """
+from errors import Conflict, NotFound
+
+
+def lookup(key):
+    \"\"\"Find a record.
+
+    Raises:
+        NotFound: If no record has this key.
+            Callers should create one.
+        Conflict: If the key is ambiguous.
+    \"\"\"
+    return {}[key]
+
+
+def store(key, value):
+    \"\"\"Store a record.
+
+    Raises
+    ------
+    Conflict
+        If the key already exists.
+    \"\"\"
+    raise Conflict(key)
+
+
+def undocumented(key):
+    raise NotFound(key)
+""".strip()
+        + "\n",
+        encoding="utf-8",
+    )
+
+    data = run_extract(str(tmp_path))
+    functions = {
+        definition.symbol_id: definition.details
+        for doc in data.documents
+        for definition in doc.definitions
+        if definition.kind == SymbolKind.Function
+    }
+
+    assert functions["service.lookup"].throws == ["errors.NotFound", "errors.Conflict"]
+    assert functions["service.store"].throws == ["errors.Conflict"]
+    # Only declarations count; raise statements are not inferred
+    assert functions["service.undocumented"].throws == []
+
+
 def test_import_alias_calls_use_stable_external_ids(tmp_path: Path):
     (tmp_path / "sample.py").write_text(
         """
@@ -0,0 +1,119 @@
+use super::heuristic::strip_code_fences;
+use crate::domain::policy::{DocumentationScorer, NodeInfo};
+
+/// Weight of a non-trivial summary line (more than three words).
+const SUMMARY_WEIGHT: f32 = 0.3;
+/// Weight of parameter coverage, scaled by the fraction of parameter names mentioned.
+const PARAMS_WEIGHT: f32 = 0.4;
+/// Weight of a described return value.
+const RETURN_WEIGHT: f32 = 0.3;
+
+/// Scores how much of the API surface the documentation covers: the summary line, each
+/// parameter (from [`NodeInfo::param_names`]) and the return value.
+///
+/// A function without parameters or without a declared return type gets that part in full,
+/// so a short but complete doc on `def reset() -> None` still reaches 1.0.
+#[derive(Debug, Default)]
+pub struct CoverageDocScorer;
+
+impl CoverageDocScorer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl DocumentationScorer for CoverageDocScorer {
+    fn score(&self, node_info: &NodeInfo, doc_text: Option<&str>) -> f32 {
+        let prose = doc_text.map(strip_code_fences).unwrap_or_default();
+        let Some(summary) = prose.lines().map(str::trim).find(|line| !line.is_empty()) else {
+            return 0.0;
+        };
+        let doc_lower = prose.to_lowercase();
+        let words: Vec<&str> = doc_lower
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        let summary_score = if summary.split_whitespace().count() > 3 {
+            SUMMARY_WEIGHT
+        } else {
+            0.0
+        };
+
+        let params_score = if node_info.param_names.is_empty() {
+            PARAMS_WEIGHT
+        } else {
+            let covered = node_info
+                .param_names
+                .iter()
+                .filter(|name| words.contains(&name.to_lowercase().as_str()))
+                .count();
+            PARAMS_WEIGHT * covered as f32 / node_info.param_names.len() as f32
+        };
+
+        let return_score = if !node_info.has_return_type
+            || words
+                .iter()
+                .any(|w| matches!(*w, "return" | "returns" | "yields"))
+        {
+            RETURN_WEIGHT
+        } else {
+            0.0
+        };
+
+        (summary_score + params_score + return_score).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::policy::NodeType;
+
+    fn node_info(params: &[&str], has_return_type: bool) -> NodeInfo {
+        NodeInfo {
+            node_type: NodeType::Function,
+            name: "f".into(),
+            signature: None,
+            language: Some("py".into()),
+            param_names: params.iter().map(|p| p.to_string()).collect(),
+            has_return_type,
+            is_abstract: false,
+        }
+    }
+
+    #[test]
+    fn test_full_coverage_scores_one() {
+        let info = node_info(&["user_id", "limit"], true);
+        let doc = "Load the most recent orders.\n\nArgs:\n  user_id: owner\n  limit: max rows\n\nReturns:\n  The orders, newest first.";
+        assert!((CoverageDocScorer.score(&info, Some(doc)) - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_missing_parts_lower_the_score() {
+        let info = node_info(&["user_id", "limit"], true);
+        // Summary and one of two parameters; return value not described
+        let doc = "Load the most recent orders for user_id.";
+        let score = CoverageDocScorer.score(&info, Some(doc));
+        assert!((score - (SUMMARY_WEIGHT + PARAMS_WEIGHT / 2.0)).abs() < 1e-5);
+
+        // Trivial summary only
+        assert_eq!(CoverageDocScorer.score(&info, Some("Loads orders.")), 0.0);
+        assert_eq!(CoverageDocScorer.score(&info, None), 0.0);
+    }
+
+    #[test]
+    fn test_no_params_and_no_return_count_as_covered() {
+        let info = node_info(&[], false);
+        assert!((CoverageDocScorer.score(&info, Some("Reset.")) - 0.7).abs() < 1e-5);
+        let doc = "Reset every cached counter to zero.";
+        assert!((CoverageDocScorer.score(&info, Some(doc)) - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_code_fences_do_not_count_as_coverage() {
+        let info = node_info(&["limit"], true);
+        let doc = "```python\ndef f(limit: int) -> list: ...\n```\nFetch rows.";
+        assert_eq!(CoverageDocScorer.score(&info, Some(doc)), 0.0);
+    }
+}
@@ -112,7 +112,10 @@ impl HeuristicDocScorer {
 
 impl DocumentationScorer for HeuristicDocScorer {
     fn score(&self, node_info: &NodeInfo, doc_text: Option<&str>) -> f32 {
-        let doc = match doc_text {
+        // Indexers (SCIP hover docs) often prepend the symbol's own signature in a code fence;
+        // it names every parameter and the return type without explaining anything.
+        let prose = doc_text.map(strip_code_fences);
+        let doc = match prose.as_deref() {
             Some(d) if !d.trim().is_empty() => d,
             _ => return 0.0,
         };
@@ -135,7 +138,12 @@ impl DocumentationScorer for HeuristicDocScorer {
         let extractor = self.get_extractor(node_info.language.as_deref());
 
         if let Some(signature) = &node_info.signature {
-            let params = extractor.extract_params(signature);
+            // Structured names from the adapter beat re-parsing the signature text
+            let params = if node_info.param_names.is_empty() {
+                extractor.extract_params(signature)
+            } else {
+                node_info.param_names.clone()
+            };
             let has_ret = extractor.has_return_value(signature);
 
             let param_contribution = if !params.is_empty() {
@@ -185,6 +193,30 @@ impl DocumentationScorer for HeuristicDocScorer {
     }
 }
 
+/// Remove markdown fenced code blocks (```` ``` ```` or `~~~`), keeping only the prose.
+/// An unterminated fence swallows the rest of the text.
+pub(crate) fn strip_code_fences(doc: &str) -> String {
+    let mut prose = String::with_capacity(doc.len());
+    let mut fence: Option<&str> = None;
+    for line in doc.lines() {
+        let trimmed = line.trim_start();
+        match fence {
+            Some(marker) => {
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                }
+            }
+            None if trimmed.starts_with("```") => fence = Some("```"),
+            None if trimmed.starts_with("~~~") => fence = Some("~~~"),
+            None => {
+                prose.push_str(line);
+                prose.push('\n');
+            }
+        }
+    }
+    prose
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,6 +228,9 @@ mod tests {
             name: "test".into(),
             signature: signature.map(|s| s.to_string()),
             language: language.map(|s| s.to_string()),
+            param_names: Vec::new(),
+            has_return_type: false,
+            is_abstract: false,
         }
     }
 
@@ -224,6 +259,23 @@ mod tests {
         assert!(score2 >= 0.2);
     }
 
+    #[test]
+    fn test_structured_param_names_drive_coverage() {
+        let s = HeuristicDocScorer::new();
+        // No extractor for the language: the signature text yields no parameters
+        let mut info = node_info(Some("ts"), Some("(alpha, beta) -> []"));
+        let doc = "Combines alpha with beta into one normalized value.";
+        let unstructured = s.score(&info, Some(doc));
+
+        info.param_names = vec!["alpha".into(), "beta".into()];
+        let covered = s.score(&info, Some(doc));
+        // Full parameter coverage (0.4) instead of the no-parameter bonus (0.2)
+        assert!((covered - unstructured - 0.2).abs() < 1e-5);
+
+        let partial = s.score(&info, Some("Combines alpha into one normalized value."));
+        assert!(partial < covered);
+    }
+
     #[test]
     fn test_python_param_coverage() {
         let s = HeuristicDocScorer::new();
@@ -260,6 +312,32 @@ mod tests {
         assert!((score - 0.7).abs() < 0.001);
     }
 
+    #[test]
+    fn test_signature_only_code_fence_scores_low() {
+        let s = HeuristicDocScorer::new();
+        let info = node_info(Some("py"), Some("def f(path: str, retries: int) -> bytes:"));
+
+        let fence_only = "```python\ndef f(path: str, retries: int) -> bytes:\n```";
+        assert_eq!(s.score(&info, Some(fence_only)), 0.0);
+
+        // Prose next to the fence is still scored, without credit from the signature itself
+        let with_prose = format!(
+            "{fence_only}\n\nDownload the file at path, trying up to retries times. Returns the body."
+        );
+        let score = s.score(&info, Some(&with_prose));
+        assert!(score >= 0.5, "got {score}");
+        assert!(s.score(&info, Some("```python\ndef f(path, retries):\n```\nFetch.")) < 0.5);
+    }
+
+    #[test]
+    fn test_strip_code_fences_keeps_prose() {
+        assert_eq!(
+            strip_code_fences("Intro.\n```rust\nfn f() {}\n```\nOutro.\n~~~\nx\n~~~"),
+            "Intro.\nOutro.\n"
+        );
+        assert_eq!(strip_code_fences("```\nunterminated\nrest"), "");
+    }
+
     #[test]
     fn test_empty_doc_is_zero() {
         let s = HeuristicDocScorer::new();
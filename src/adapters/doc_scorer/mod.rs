@@ -1 +1,2 @@
+pub mod coverage;
 pub mod heuristic;
@@ -0,0 +1,196 @@
+use crate::domain::ports::SourceReader;
+use anyhow::{Context, Result, bail};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Serves source files from a `.tar`, `.tar.gz`/`.tgz` or `.zip` archive instead of a checkout.
+///
+/// The archive is read into memory once, keyed by entry path relative to the project. Paths
+/// passed to [`SourceReader`] are resolved against `project_root` (the SemanticData root), so
+/// the builder's `project_root.join(relative_path)` lookups work unchanged. When every entry
+/// shares a single top-level directory (`repo-1.2.0/...`, as in release tarballs), a path
+/// missing from the archive is retried under that directory; an exact entry always wins, so
+/// projects whose paths really start with that directory (`src/...`) are unaffected.
+pub struct ArchiveSourceReader {
+    project_root: PathBuf,
+    files: HashMap<String, String>,
+    shared_prefix: Option<String>,
+}
+
+impl ArchiveSourceReader {
+    pub fn open(archive: &Path, project_root: impl Into<PathBuf>) -> Result<Self> {
+        let name = archive.to_string_lossy().to_lowercase();
+        let file = File::open(archive)
+            .with_context(|| format!("Failed to open source archive: {}", archive.display()))?;
+        let entries = if name.ends_with(".zip") {
+            read_zip(file)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            read_tar(flate2::read::GzDecoder::new(file))
+        } else if name.ends_with(".tar") {
+            read_tar(file)
+        } else {
+            bail!(
+                "Unsupported source archive (expected .tar, .tar.gz, .tgz or .zip): {}",
+                archive.display()
+            );
+        }
+        .with_context(|| format!("Failed to read source archive: {}", archive.display()))?;
+        Ok(Self::from_entries(entries, project_root))
+    }
+
+    /// Build from `(entry path, content)` pairs, e.g. an archive already read elsewhere.
+    pub fn from_entries(
+        entries: impl IntoIterator<Item = (String, String)>,
+        project_root: impl Into<PathBuf>,
+    ) -> Self {
+        let files: HashMap<String, String> = entries
+            .into_iter()
+            .map(|(path, content)| (path.trim_start_matches("./").to_string(), content))
+            .collect();
+        let shared_prefix = shared_top_level_dir(files.keys());
+        Self {
+            project_root: project_root.into(),
+            files,
+            shared_prefix,
+        }
+    }
+
+    fn lookup(&self, path: &Path) -> Result<&str> {
+        let relative = path.strip_prefix(&self.project_root).unwrap_or(path);
+        let key = relative.to_string_lossy().replace('\\', "/");
+        let key = key.trim_start_matches("./");
+        self.files
+            .get(key)
+            .or_else(|| {
+                let prefix = self.shared_prefix.as_deref()?;
+                self.files.get(&format!("{prefix}{key}"))
+            })
+            .map(String::as_str)
+            .with_context(|| format!("Source file not in archive: {}", path.display()))
+    }
+}
+
+impl SourceReader for ArchiveSourceReader {
+    fn read(&self, path: &Path) -> Result<String> {
+        self.lookup(path).map(String::from)
+    }
+
+    fn read_lines(&self, path: &str, start_line: usize, end_line: usize) -> Result<Vec<String>> {
+        let content = self.lookup(Path::new(path))?;
+        // 0-indexed; start_line and end_line are inclusive
+        Ok(content
+            .lines()
+            .skip(start_line)
+            .take((end_line + 1).saturating_sub(start_line))
+            .map(String::from)
+            .collect())
+    }
+}
+
+fn read_tar(reader: impl Read) -> Result<Vec<(String, String)>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut files = Vec::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path()?.to_string_lossy().into_owned();
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+        files.push((path, String::from_utf8_lossy(&bytes).into_owned()));
+    }
+    Ok(files)
+}
+
+fn read_zip(file: File) -> Result<Vec<(String, String)>> {
+    let mut archive = zip::ZipArchive::new(file)?;
+    let mut files = Vec::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let path = entry.name().to_string();
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+        files.push((path, String::from_utf8_lossy(&bytes).into_owned()));
+    }
+    Ok(files)
+}
+
+/// `dir/` when every path lives under the same top-level directory.
+fn shared_top_level_dir<'a>(mut paths: impl Iterator<Item = &'a String>) -> Option<String> {
+    let first = paths.next()?;
+    let (dir, _) = first.split_once('/')?;
+    let prefix = format!("{dir}/");
+    paths.all(|p| p.starts_with(&prefix)).then_some(prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    #[test]
+    fn test_reads_tar_gz_by_project_relative_path() {
+        let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        for (path, content) in [
+            ("repo-1.0/app/main.py", "def main():\n    run()\n"),
+            ("repo-1.0/app/util.py", "def run():\n    pass\n"),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+        let bytes = builder.into_inner().unwrap().finish().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("src.tar.gz");
+        std::fs::write(&archive, bytes).unwrap();
+
+        let reader = ArchiveSourceReader::open(&archive, "/project").unwrap();
+        assert_eq!(
+            reader.read(Path::new("/project/app/util.py")).unwrap(),
+            "def run():\n    pass\n"
+        );
+        assert_eq!(
+            reader.read_lines("/project/app/main.py", 1, 5).unwrap(),
+            vec!["    run()"]
+        );
+        assert!(reader.read(Path::new("/project/app/missing.py")).is_err());
+    }
+
+    #[test]
+    fn test_top_level_dir_kept_when_entries_differ() {
+        let reader = ArchiveSourceReader::from_entries(
+            [
+                ("./src/a.rs".to_string(), "a".to_string()),
+                ("tests/b.rs".to_string(), "b".to_string()),
+            ],
+            "/repo",
+        );
+        assert_eq!(reader.read(Path::new("/repo/src/a.rs")).unwrap(), "a");
+        assert_eq!(reader.read(Path::new("tests/b.rs")).unwrap(), "b");
+    }
+
+    #[test]
+    fn test_shared_top_level_dir_that_is_part_of_project_paths_is_kept() {
+        // Every entry lives under `src/`, but the project's paths include it
+        let reader = ArchiveSourceReader::from_entries(
+            [
+                ("src/a.rs".to_string(), "a".to_string()),
+                ("src/b.rs".to_string(), "b".to_string()),
+            ],
+            "/repo",
+        );
+        assert_eq!(reader.read(Path::new("/repo/src/a.rs")).unwrap(), "a");
+        assert_eq!(reader.read(Path::new("/repo/b.rs")).unwrap(), "b");
+    }
+}
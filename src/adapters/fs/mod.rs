@@ -1 +1,5 @@
+#[cfg(feature = "archive")]
+pub mod archive;
+pub mod null;
 pub mod reader;
+pub mod semantic_cache;
@@ -0,0 +1,20 @@
+use crate::domain::ports::SourceReader;
+use anyhow::Result;
+use std::path::Path;
+
+/// Source reader for index-only analysis, when the source tree is not available.
+///
+/// Every file reads as empty, so size functions measure 0 and context views show no code;
+/// graph structure and node counts are unaffected.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NullSourceReader;
+
+impl SourceReader for NullSourceReader {
+    fn read(&self, _path: &Path) -> Result<String> {
+        Ok(String::new())
+    }
+
+    fn read_lines(&self, _path: &str, _start_line: usize, _end_line: usize) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+}
@@ -0,0 +1,161 @@
+use crate::domain::ports::SemanticDataSource;
+use crate::domain::semantic::SemanticData;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Serves SemanticData from an on-disk cache while the input file is unchanged, so expensive
+/// conversions (e.g. LSIF joins) only run when the input's size or mtime changes.
+///
+/// The cache lives next to the input as `<input>.cfcache` (postcard binary: compact and fast to
+/// decode for large indexes); delete it to force a rebuild.
+pub struct CachedDataSource<S> {
+    inner: S,
+    input: PathBuf,
+    cache_path: PathBuf,
+}
+
+/// Bump when the cache layout changes.
+const CACHE_FORMAT_VERSION: u32 = 1;
+
+/// Identity of the input the cache was written for, and of the writer: a cache from another
+/// format or crate version (whose SemanticData may differ) is a miss.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct InputStamp {
+    format_version: u32,
+    crate_version: String,
+    len: u64,
+    mtime_nanos: u128,
+}
+
+impl<S: SemanticDataSource> CachedDataSource<S> {
+    pub fn new(inner: S, input: impl Into<PathBuf>) -> Self {
+        let input = input.into();
+        let mut cache_path = input.clone().into_os_string();
+        cache_path.push(".cfcache");
+        Self {
+            inner,
+            input,
+            cache_path: cache_path.into(),
+        }
+    }
+
+    pub fn cache_path(&self) -> &Path {
+        &self.cache_path
+    }
+
+    fn stamp(&self) -> Result<InputStamp> {
+        let meta = std::fs::metadata(&self.input)
+            .with_context(|| format!("Failed to stat input: {}", self.input.display()))?;
+        let mtime_nanos = meta
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        Ok(InputStamp {
+            format_version: CACHE_FORMAT_VERSION,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            len: meta.len(),
+            mtime_nanos,
+        })
+    }
+
+    /// Cached data when the cache exists, parses, and was written for `stamp`. The stamp is
+    /// decoded first, so a stale cache is rejected without decoding its data.
+    fn read_cache(&self, stamp: &InputStamp) -> Option<SemanticData> {
+        let bytes = std::fs::read(&self.cache_path).ok()?;
+        let (cached, data) = postcard::take_from_bytes::<InputStamp>(&bytes).ok()?;
+        if cached != *stamp {
+            return None;
+        }
+        postcard::from_bytes(data).ok()
+    }
+}
+
+impl<S: SemanticDataSource> SemanticDataSource for CachedDataSource<S> {
+    fn load(&self) -> Result<SemanticData> {
+        let stamp = self.stamp()?;
+        if let Some(data) = self.read_cache(&stamp) {
+            return Ok(data);
+        }
+        let data = self.inner.load()?;
+        // A cache that cannot be written only costs the next run a rebuild
+        let encoded =
+            postcard::to_stdvec(&stamp).and_then(|header| postcard::to_extend(&data, header));
+        match encoded {
+            Ok(content) => {
+                if let Err(err) = std::fs::write(&self.cache_path, content) {
+                    tracing::warn!(
+                        "Failed to write semantic data cache {}: {err}",
+                        self.cache_path.display()
+                    );
+                }
+            }
+            Err(err) => tracing::warn!("Failed to serialize semantic data cache: {err}"),
+        }
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct CountingSource<'a> {
+        loads: &'a Cell<u32>,
+    }
+
+    impl SemanticDataSource for CountingSource<'_> {
+        fn load(&self) -> Result<SemanticData> {
+            self.loads.set(self.loads.get() + 1);
+            Ok(SemanticData {
+                project_root: "/repo".into(),
+                documents: Vec::new(),
+                external_symbols: Vec::new(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_cache_is_reused_until_input_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("index.lsif");
+        std::fs::write(&input, "{}").unwrap();
+        let loads = Cell::new(0);
+
+        let source = CachedDataSource::new(CountingSource { loads: &loads }, &input);
+        assert_eq!(source.load().unwrap().project_root, "/repo");
+        assert!(source.cache_path().exists());
+        source.load().unwrap();
+        assert_eq!(loads.get(), 1);
+
+        // A different size invalidates the cache
+        std::fs::write(&input, "{}\n{}").unwrap();
+        source.load().unwrap();
+        assert_eq!(loads.get(), 2);
+    }
+
+    #[test]
+    fn test_cache_from_another_version_is_a_miss() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("index.lsif");
+        std::fs::write(&input, "{}").unwrap();
+        let loads = Cell::new(0);
+        let source = CachedDataSource::new(CountingSource { loads: &loads }, &input);
+        source.load().unwrap();
+
+        let mut stamp = source.stamp().unwrap();
+        stamp.crate_version = "0.0.0-old".to_string();
+        let stale = postcard::to_stdvec(&stamp).unwrap();
+        std::fs::write(source.cache_path(), stale).unwrap();
+        source.load().unwrap();
+        assert_eq!(loads.get(), 2);
+
+        // Garbage is a miss too, not an error
+        std::fs::write(source.cache_path(), b"not a cache").unwrap();
+        source.load().unwrap();
+        assert_eq!(loads.get(), 3);
+    }
+}
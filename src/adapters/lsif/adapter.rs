@@ -0,0 +1,880 @@
+//! LSIF (Language Server Index Format) adapter: maps an LSIF dump onto [`SemanticData`].
+//!
+//! LSIF describes ranges, result sets and their definition/reference/hover results, but
+//! carries no type annotations, mutability or read/write distinction. The resulting
+//! SemanticData is therefore coarser than an extractor's:
+//! - Definitions are classified by the LSP `SymbolKind` on their range tag; untagged
+//!   definitions and variables nested in functions (locals) are skipped
+//! - Parameters and return types are left empty; variables are Mutable unless tagged constant
+//! - A reference is a Call when it targets a function and a Read when it targets a variable;
+//!   references outside any function and references to types are dropped
+//! - Hover markdown prose becomes `documentation`, its first code block the `display_name`
+
+use crate::domain::ports::SemanticDataSource;
+use crate::domain::semantic::{
+    DocumentSemantics, Field, FunctionDetails, FunctionModifiers, Mutability, ReferenceRole,
+    SemanticData, SourceLocation, SourceSpan, SymbolDefinition, SymbolDetails, SymbolId,
+    SymbolKind, SymbolReference, TypeDetails, TypeKind, VariableDetails, VariableScope, Visibility,
+};
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Reads SemanticData from an LSIF dump (NDJSON, one vertex or edge per line, or a JSON array).
+pub struct LsifDataSourceAdapter {
+    path: PathBuf,
+}
+
+impl LsifDataSourceAdapter {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Parse an LSIF dump held in memory.
+    pub fn parse(content: &str) -> Result<SemanticData> {
+        let mut index = LsifIndex::default();
+        if content.trim_start().starts_with('[') {
+            let elements: Vec<Value> =
+                serde_json::from_str(content).context("Failed to parse LSIF JSON array")?;
+            elements.iter().for_each(|element| index.add(element));
+        } else {
+            for (line_no, line) in content.lines().enumerate() {
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                let element: Value = serde_json::from_str(line)
+                    .with_context(|| format!("Invalid LSIF element on line {}", line_no + 1))?;
+                index.add(&element);
+            }
+        }
+        Ok(index.into_semantic_data())
+    }
+}
+
+impl SemanticDataSource for LsifDataSourceAdapter {
+    fn load(&self) -> Result<SemanticData> {
+        let content = std::fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read LSIF dump: {}", self.path.display()))?;
+        Self::parse(&content)
+    }
+}
+
+/// LSIF element id (numbers and strings are both allowed by the spec).
+type Id = String;
+
+type Position = (u32, u32);
+
+#[derive(Debug, Clone)]
+struct Range {
+    start: Position,
+    end: Position,
+    /// `tag.fullRange`: the whole definition, not just its name.
+    full: Option<(Position, Position)>,
+    /// `tag.text`
+    name: Option<String>,
+    /// `tag.kind` (LSP SymbolKind)
+    kind: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+struct Item {
+    range: Id,
+    /// "definitions" / "references" on items of a referenceResult.
+    property: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct LsifIndex {
+    project_root: Option<String>,
+    /// Document ids in dump order, with URI and languageId.
+    documents: Vec<(Id, String, String)>,
+    ranges: HashMap<Id, Range>,
+    range_document: HashMap<Id, Id>,
+    /// `next` edges: range or result set → result set.
+    next: HashMap<Id, Id>,
+    definition_result: HashMap<Id, Id>,
+    reference_result: HashMap<Id, Id>,
+    hover_result: HashMap<Id, Id>,
+    moniker_of: HashMap<Id, Id>,
+    /// Moniker vertex → `scheme:identifier`.
+    monikers: HashMap<Id, String>,
+    /// hoverResult vertex → `result.contents`.
+    hovers: HashMap<Id, Value>,
+    items: HashMap<Id, Vec<Item>>,
+}
+
+/// A resolved definition, before enclosing scopes are assigned.
+struct Definition {
+    symbol_id: SymbolId,
+    lsp_kind: u64,
+    kind: SymbolKind,
+    name: String,
+    /// Index into the dump's documents.
+    document: usize,
+    location: Position,
+    span: (Position, Position),
+    hover: Option<Id>,
+    reference_result: Option<Id>,
+}
+
+impl LsifIndex {
+    fn add(&mut self, element: &Value) {
+        let Some(id) = element.get("id").and_then(id_of) else {
+            return;
+        };
+        let label = element.get("label").and_then(Value::as_str).unwrap_or("");
+        match element.get("type").and_then(Value::as_str) {
+            Some("vertex") => self.add_vertex(id, label, element),
+            Some("edge") => self.add_edge(label, element),
+            _ => {}
+        }
+    }
+
+    fn add_vertex(&mut self, id: Id, label: &str, vertex: &Value) {
+        match label {
+            "metaData" => {
+                self.project_root = vertex
+                    .get("projectRoot")
+                    .and_then(Value::as_str)
+                    .map(|uri| strip_file_uri(uri).trim_end_matches('/').to_string());
+            }
+            "document" => {
+                let uri = vertex.get("uri").and_then(Value::as_str).unwrap_or("");
+                let language = vertex
+                    .get("languageId")
+                    .and_then(Value::as_str)
+                    .unwrap_or("")
+                    .to_lowercase();
+                self.documents.push((id, uri.to_string(), language));
+            }
+            "range" => {
+                let (Some(start), Some(end)) = (
+                    vertex.get("start").and_then(position_of),
+                    vertex.get("end").and_then(position_of),
+                ) else {
+                    return;
+                };
+                let tag = vertex.get("tag");
+                let full = tag.and_then(|t| t.get("fullRange")).and_then(|r| {
+                    Some((
+                        r.get("start").and_then(position_of)?,
+                        r.get("end").and_then(position_of)?,
+                    ))
+                });
+                self.ranges.insert(
+                    id,
+                    Range {
+                        start,
+                        end,
+                        full,
+                        name: tag
+                            .and_then(|t| t.get("text"))
+                            .and_then(Value::as_str)
+                            .map(String::from),
+                        kind: tag.and_then(|t| t.get("kind")).and_then(Value::as_u64),
+                    },
+                );
+            }
+            "moniker" => {
+                if let Some(identifier) = vertex.get("identifier").and_then(Value::as_str) {
+                    let scheme = vertex.get("scheme").and_then(Value::as_str).unwrap_or("");
+                    self.monikers.insert(id, format!("{scheme}:{identifier}"));
+                }
+            }
+            "hoverResult" => {
+                if let Some(contents) = vertex.get("result").and_then(|r| r.get("contents")) {
+                    self.hovers.insert(id, contents.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn add_edge(&mut self, label: &str, edge: &Value) {
+        let Some(out_v) = edge.get("outV").and_then(id_of) else {
+            return;
+        };
+        let in_vs: Vec<Id> = match (edge.get("inV"), edge.get("inVs")) {
+            (Some(in_v), _) => id_of(in_v).into_iter().collect(),
+            (None, Some(Value::Array(in_vs))) => in_vs.iter().filter_map(id_of).collect(),
+            _ => Vec::new(),
+        };
+        let Some(first) = in_vs.first().cloned() else {
+            return;
+        };
+        match label {
+            "contains" => {
+                for range in in_vs {
+                    self.range_document.insert(range, out_v.clone());
+                }
+            }
+            "next" => {
+                self.next.insert(out_v, first);
+            }
+            "textDocument/definition" => {
+                self.definition_result.insert(out_v, first);
+            }
+            "textDocument/references" => {
+                self.reference_result.insert(out_v, first);
+            }
+            "textDocument/hover" => {
+                self.hover_result.insert(out_v, first);
+            }
+            "moniker" => {
+                self.moniker_of.insert(out_v, first);
+            }
+            "item" => {
+                let property = edge
+                    .get("property")
+                    .and_then(Value::as_str)
+                    .map(String::from);
+                self.items
+                    .entry(out_v)
+                    .or_default()
+                    .extend(in_vs.into_iter().map(|range| Item {
+                        range,
+                        property: property.clone(),
+                    }));
+            }
+            _ => {}
+        }
+    }
+
+    /// Result set a range (or result set) ultimately points to via `next` edges.
+    fn result_set<'a>(&'a self, mut id: &'a Id) -> Id {
+        let mut hops = 0;
+        while let Some(next) = self.next.get(id) {
+            id = next;
+            hops += 1;
+            if hops > self.next.len() {
+                break;
+            }
+        }
+        id.clone()
+    }
+
+    /// Look up an attached result on the range, or anywhere along its `next` chain.
+    fn attached(&self, map: &HashMap<Id, Id>, range: &Id) -> Option<Id> {
+        let mut id = range;
+        for _ in 0..=self.next.len() {
+            if let Some(result) = map.get(id) {
+                return Some(result.clone());
+            }
+            id = self.next.get(id)?;
+        }
+        None
+    }
+
+    fn relative_path(&self, uri: &str) -> String {
+        let path = strip_file_uri(uri);
+        match &self.project_root {
+            Some(root) => path
+                .strip_prefix(root.as_str())
+                .unwrap_or(path)
+                .trim_start_matches('/')
+                .to_string(),
+            None => path.trim_start_matches('/').to_string(),
+        }
+    }
+
+    fn into_semantic_data(self) -> SemanticData {
+        let paths: HashMap<&Id, String> = self
+            .documents
+            .iter()
+            .map(|(id, uri, _)| (id, self.relative_path(uri)))
+            .collect();
+        let document_index: HashMap<&Id, usize> = self
+            .documents
+            .iter()
+            .enumerate()
+            .map(|(i, (id, _, _))| (id, i))
+            .collect();
+        // Ranges not contained in a known document (partial or malformed dumps) are skipped
+        let mut orphan_ranges = 0;
+        let mut document_of = |range_id: &Id| {
+            let document = self
+                .range_document
+                .get(range_id)
+                .and_then(|document| document_index.get(document))
+                .copied();
+            if document.is_none() {
+                orphan_ranges += 1;
+            }
+            document
+        };
+
+        // Definitions: one per result set with a tagged definition range
+        let mut definitions: Vec<Definition> = Vec::new();
+        let mut symbol_of_set: HashMap<Id, SymbolId> = HashMap::new();
+        let mut range_ids: Vec<&Id> = self.ranges.keys().collect();
+        range_ids.sort_by_key(|id| {
+            let r = &self.ranges[*id];
+            (r.start, (*id).clone())
+        });
+        for range_id in range_ids {
+            let range = &self.ranges[range_id];
+            let (Some(lsp_kind), Some(name)) = (range.kind, range.name.clone()) else {
+                continue;
+            };
+            let Some(kind) = symbol_kind_from_lsp(lsp_kind) else {
+                continue;
+            };
+            let Some(document) = document_of(range_id) else {
+                continue;
+            };
+            let set = self.result_set(range_id);
+            if symbol_of_set.contains_key(&set) {
+                continue;
+            }
+            // Only ranges the index lists as definitions of their result set
+            let is_definition = self
+                .attached(&self.definition_result, range_id)
+                .and_then(|result| self.items.get(&result))
+                .is_some_and(|items| items.iter().any(|item| &item.range == range_id));
+            if !is_definition {
+                continue;
+            }
+
+            let path = &paths[&self.documents[document].0];
+            let symbol_id = self
+                .attached(&self.moniker_of, range_id)
+                .and_then(|moniker| self.monikers.get(&moniker).cloned())
+                .unwrap_or_else(|| {
+                    format!("lsif:{path}:{}:{}#{name}", range.start.0, range.start.1)
+                });
+            symbol_of_set.insert(set, symbol_id.clone());
+            definitions.push(Definition {
+                symbol_id,
+                lsp_kind,
+                kind,
+                name,
+                document,
+                location: range.start,
+                span: range.full.unwrap_or((range.start, range.end)),
+                hover: self.attached(&self.hover_result, range_id),
+                reference_result: self.attached(&self.reference_result, range_id),
+            });
+        }
+
+        // Definitions of each document, outer spans before the spans they contain
+        let mut by_document: Vec<Vec<usize>> = vec![Vec::new(); self.documents.len()];
+        for (i, def) in definitions.iter().enumerate() {
+            by_document[def.document].push(i);
+        }
+        for defs in &mut by_document {
+            defs.sort_by_key(|&i| {
+                (
+                    definitions[i].span.0,
+                    std::cmp::Reverse(definitions[i].span.1),
+                )
+            });
+        }
+
+        // Enclosing scope: innermost Type or Function definition whose span contains this one.
+        // A sweep keeps the open scopes; the innermost container is the last one that contains.
+        let mut enclosing: Vec<Option<usize>> = vec![None; definitions.len()];
+        for defs in &by_document {
+            let mut open: Vec<usize> = Vec::new();
+            for &i in defs {
+                let def = &definitions[i];
+                // A scope ending before this span starts cannot contain anything that follows
+                while open
+                    .last()
+                    .is_some_and(|&outer| definitions[outer].span.1 < def.span.0)
+                {
+                    open.pop();
+                }
+                enclosing[i] = open.iter().rev().copied().find(|&outer| {
+                    definitions[outer].symbol_id != def.symbol_id
+                        && span_contains(definitions[outer].span, def.span)
+                });
+                if def.kind != SymbolKind::Variable {
+                    open.push(i);
+                }
+            }
+        }
+
+        // Functions of each document by start, for finding the function enclosing a reference
+        let functions_by_document: Vec<Vec<usize>> = by_document
+            .iter()
+            .map(|defs| {
+                defs.iter()
+                    .copied()
+                    .filter(|&i| definitions[i].kind == SymbolKind::Function)
+                    .collect()
+            })
+            .collect();
+
+        let mut documents: Vec<DocumentSemantics> = self
+            .documents
+            .iter()
+            .map(|(id, _, language)| DocumentSemantics {
+                relative_path: paths[id].clone(),
+                language: language.clone(),
+                definitions: Vec::new(),
+                references: Vec::new(),
+            })
+            .collect();
+
+        let mut fields_of: HashMap<usize, Vec<Field>> = HashMap::new();
+        let mut semantic_defs: Vec<(usize, SymbolDefinition)> = Vec::new();
+        for (i, def) in definitions.iter().enumerate() {
+            let parent = enclosing[i].map(|p| &definitions[p]);
+            let is_field = parent.is_some_and(|p| p.kind == SymbolKind::Type);
+            if def.kind == SymbolKind::Variable && parent.is_some() && !is_field {
+                // Local variable inside a function
+                continue;
+            }
+            let (documentation, signature) = def
+                .hover
+                .as_ref()
+                .and_then(|hover| self.hovers.get(hover))
+                .map(hover_text)
+                .unwrap_or_default();
+            let details = match def.kind {
+                SymbolKind::Function => SymbolDetails::Function(FunctionDetails {
+                    modifiers: FunctionModifiers {
+                        is_constructor: def.lsp_kind == LSP_CONSTRUCTOR,
+                        ..FunctionModifiers::default()
+                    },
+                    ..FunctionDetails::default()
+                }),
+                SymbolKind::Variable => {
+                    let mutability = if matches!(def.lsp_kind, LSP_CONSTANT | LSP_ENUM_MEMBER) {
+                        Mutability::Const
+                    } else {
+                        Mutability::Mutable
+                    };
+                    if let Some(p) = enclosing[i].filter(|_| is_field) {
+                        fields_of.entry(p).or_default().push(Field {
+                            name: def.name.clone(),
+                            field_type: None,
+                            mutability: mutability.clone(),
+                            visibility: Visibility::Public,
+                            symbol_id: def.symbol_id.clone(),
+                        });
+                    }
+                    SymbolDetails::Variable(VariableDetails {
+                        mutability,
+                        scope: if is_field {
+                            VariableScope::Field
+                        } else {
+                            VariableScope::Global
+                        },
+                        ..VariableDetails::default()
+                    })
+                }
+                SymbolKind::Type => SymbolDetails::Type(TypeDetails {
+                    kind: type_kind_from_lsp(def.lsp_kind),
+                    ..TypeDetails::default()
+                }),
+            };
+            let path = &documents[def.document].relative_path;
+            semantic_defs.push((
+                i,
+                SymbolDefinition {
+                    symbol_id: def.symbol_id.clone(),
+                    kind: def.kind.clone(),
+                    name: def.name.clone(),
+                    display_name: signature.unwrap_or_else(|| def.name.clone()),
+                    location: SourceLocation {
+                        file_path: path.clone(),
+                        line: def.location.0,
+                        column: def.location.1,
+                    },
+                    span: SourceSpan {
+                        start_line: def.span.0.0,
+                        start_column: def.span.0.1,
+                        end_line: def.span.1.0,
+                        end_column: def.span.1.1,
+                    },
+                    enclosing_symbol: parent.map(|p| p.symbol_id.clone()),
+                    is_external: false,
+                    is_generated: false,
+                    documentation,
+                    details,
+                },
+            ));
+        }
+        for (i, mut def) in semantic_defs {
+            if let (Some(fields), SymbolDetails::Type(details)) =
+                (fields_of.remove(&i), &mut def.details)
+            {
+                details.fields = fields;
+            }
+            documents[definitions[i].document].definitions.push(def);
+        }
+
+        // References: every non-definition range of a symbol's referenceResult
+        for def in &definitions {
+            let role = match def.kind {
+                SymbolKind::Function => ReferenceRole::Call,
+                SymbolKind::Variable => ReferenceRole::Read,
+                SymbolKind::Type => continue,
+            };
+            let Some(items) = def
+                .reference_result
+                .as_ref()
+                .and_then(|result| self.items.get(result))
+            else {
+                continue;
+            };
+            for item in items {
+                if item.property.as_deref() == Some("definitions") {
+                    continue;
+                }
+                let Some(range) = self.ranges.get(&item.range) else {
+                    continue;
+                };
+                let Some(document) = document_of(&item.range) else {
+                    continue;
+                };
+                if document == def.document && range.start == def.location {
+                    continue;
+                }
+                // Innermost function: the latest-starting one that contains the reference
+                let functions = &functions_by_document[document];
+                let candidates =
+                    functions.partition_point(|&i| definitions[i].span.0 <= range.start);
+                let Some(caller) = functions[..candidates]
+                    .iter()
+                    .rev()
+                    .map(|&i| &definitions[i])
+                    .find(|outer| span_contains(outer.span, (range.start, range.end)))
+                else {
+                    continue;
+                };
+                let doc = &mut documents[document];
+                doc.references.push(SymbolReference {
+                    target_symbol: Some(def.symbol_id.clone()),
+                    location: SourceLocation {
+                        file_path: doc.relative_path.clone(),
+                        line: range.start.0,
+                        column: range.start.1,
+                    },
+                    enclosing_symbol: caller.symbol_id.clone(),
+                    role: role.clone(),
+                    receiver: None,
+                    method_name: None,
+                    assigned_to: None,
+                });
+            }
+        }
+
+        if orphan_ranges > 0 {
+            tracing::warn!("Skipped {orphan_ranges} LSIF range(s) outside any document");
+        }
+
+        SemanticData {
+            project_root: self.project_root.unwrap_or_default(),
+            documents,
+            external_symbols: Vec::new(),
+        }
+    }
+}
+
+const LSP_CONSTRUCTOR: u64 = 9;
+const LSP_CONSTANT: u64 = 14;
+const LSP_ENUM_MEMBER: u64 = 22;
+
+/// Classify an LSP `SymbolKind`; modules, namespaces and literal kinds are not graph symbols.
+fn symbol_kind_from_lsp(kind: u64) -> Option<SymbolKind> {
+    match kind {
+        6 | 9 | 12 | 24 | 25 => Some(SymbolKind::Function),
+        7 | 8 | 13 | 14 | 22 => Some(SymbolKind::Variable),
+        5 | 10 | 11 | 23 | 26 => Some(SymbolKind::Type),
+        _ => None,
+    }
+}
+
+fn type_kind_from_lsp(kind: u64) -> TypeKind {
+    match kind {
+        10 => TypeKind::Enum,
+        11 => TypeKind::Interface,
+        23 => TypeKind::Struct,
+        26 => TypeKind::TypeVar,
+        _ => TypeKind::Class,
+    }
+}
+
+fn id_of(value: &Value) -> Option<Id> {
+    match value {
+        Value::Number(n) => Some(n.to_string()),
+        Value::String(s) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+fn position_of(value: &Value) -> Option<Position> {
+    Some((
+        value.get("line")?.as_u64()? as u32,
+        value.get("character")?.as_u64()? as u32,
+    ))
+}
+
+fn strip_file_uri(uri: &str) -> &str {
+    uri.strip_prefix("file://").unwrap_or(uri)
+}
+
+fn span_contains(outer: (Position, Position), inner: (Position, Position)) -> bool {
+    outer != inner && outer.0 <= inner.0 && inner.1 <= outer.1
+}
+
+/// Split hover contents into documentation prose and the first code block (the signature).
+fn hover_text(contents: &Value) -> (Vec<String>, Option<String>) {
+    let mut parts: Vec<(bool, String)> = Vec::new();
+    let mut push = |value: &Value| match value {
+        Value::String(markdown) => parts.push((false, markdown.clone())),
+        Value::Object(map) => {
+            let text = map.get("value").and_then(Value::as_str).unwrap_or("");
+            // MarkedString {language, value} is code; MarkupContent {kind, value} is markdown
+            parts.push((map.contains_key("language"), text.to_string()));
+        }
+        _ => {}
+    };
+    match contents {
+        Value::Array(values) => values.iter().for_each(&mut push),
+        value => push(value),
+    }
+
+    let mut documentation = Vec::new();
+    let mut signature = None;
+    for (is_code, text) in parts {
+        if is_code {
+            signature.get_or_insert_with(|| text.trim().to_string());
+            continue;
+        }
+        let mut prose = String::new();
+        let mut code = String::new();
+        let mut in_fence = false;
+        for line in text.lines() {
+            if line.trim_start().starts_with("```") {
+                if in_fence && signature.is_none() {
+                    signature = Some(code.trim().to_string());
+                }
+                code.clear();
+                in_fence = !in_fence;
+            } else if in_fence {
+                code.push_str(line);
+                code.push('\n');
+            } else if line.trim() != "---" {
+                prose.push_str(line);
+                prose.push('\n');
+            }
+        }
+        let prose = prose.trim();
+        if !prose.is_empty() {
+            documentation.push(prose.to_string());
+        }
+    }
+    (documentation, signature.filter(|s| !s.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn vertex(id: u32, label: &str, mut fields: Value) -> Value {
+        fields["id"] = json!(id);
+        fields["type"] = json!("vertex");
+        fields["label"] = json!(label);
+        fields
+    }
+
+    fn edge(id: u32, label: &str, out_v: u32, in_vs: &[u32]) -> Value {
+        json!({"id": id, "type": "edge", "label": label, "outV": out_v, "inVs": in_vs})
+    }
+
+    fn range(id: u32, start: (u32, u32), end: (u32, u32), tag: Option<(&str, u32, u32)>) -> Value {
+        let mut fields = json!({
+            "start": {"line": start.0, "character": start.1},
+            "end": {"line": end.0, "character": end.1},
+        });
+        if let Some((text, kind, end_line)) = tag {
+            fields["tag"] = json!({
+                "type": "definition",
+                "text": text,
+                "kind": kind,
+                "fullRange": {
+                    "start": {"line": start.0, "character": 0},
+                    "end": {"line": end_line, "character": 0},
+                },
+            });
+        }
+        vertex(id, "range", fields)
+    }
+
+    /// class Greeter (lines 0-5) with field `name` and method `greet`; function `main`
+    /// (lines 6-9) with a local `x`, a call to `greet` and a read of `name`.
+    fn sample_dump() -> String {
+        let mut elements = vec![
+            vertex(1, "metaData", json!({"projectRoot": "file:///repo"})),
+            vertex(
+                2,
+                "document",
+                json!({"uri": "file:///repo/app/main.py", "languageId": "python"}),
+            ),
+            range(10, (0, 6), (0, 13), Some(("Greeter", 5, 5))),
+            range(11, (1, 4), (1, 8), Some(("name", 8, 2))),
+            range(12, (2, 8), (2, 13), Some(("greet", 6, 4))),
+            range(13, (6, 4), (6, 8), Some(("main", 12, 9))),
+            range(14, (7, 4), (7, 5), Some(("x", 13, 8))),
+            range(15, (7, 8), (7, 13), None),
+            range(16, (8, 10), (8, 14), None),
+            edge(100, "contains", 2, &[10, 11, 12, 13, 14, 15, 16]),
+        ];
+        let mut next_id = 200;
+        let mut edge_to = |elements: &mut Vec<Value>, label: &str, out_v: u32, in_vs: &[u32]| {
+            next_id += 1;
+            elements.push(edge(next_id, label, out_v, in_vs));
+        };
+        // One result set (2x) and definition result (3x) per definition range
+        for (range_id, set) in [(10, 20), (11, 21), (12, 22), (13, 23), (14, 24)] {
+            let def_result = set + 10;
+            elements.push(vertex(set, "resultSet", json!({})));
+            elements.push(vertex(def_result, "definitionResult", json!({})));
+            edge_to(&mut elements, "next", range_id, &[set]);
+            edge_to(&mut elements, "textDocument/definition", set, &[def_result]);
+            edge_to(&mut elements, "item", def_result, &[range_id]);
+        }
+        edge_to(&mut elements, "next", 15, &[22]);
+        edge_to(&mut elements, "next", 16, &[21]);
+        for (set, ref_result, def_range, ref_range) in [(21, 41, 11, 16), (22, 42, 12, 15)] {
+            elements.push(vertex(ref_result, "referenceResult", json!({})));
+            edge_to(&mut elements, "textDocument/references", set, &[ref_result]);
+            let mut item = edge(0, "item", ref_result, &[def_range]);
+            item["property"] = json!("definitions");
+            elements.push(item);
+            let mut item = edge(0, "item", ref_result, &[ref_range]);
+            item["property"] = json!("references");
+            elements.push(item);
+        }
+        elements.push(vertex(
+            50,
+            "hoverResult",
+            json!({"result": {"contents": {
+                "kind": "markdown",
+                "value": "```python\ndef greet(self) -> str\n```\n---\nSay hello.",
+            }}}),
+        ));
+        edge_to(&mut elements, "textDocument/hover", 22, &[50]);
+        elements.push(vertex(
+            60,
+            "moniker",
+            json!({"scheme": "python", "identifier": "app.main.Greeter.greet", "kind": "export"}),
+        ));
+        edge_to(&mut elements, "moniker", 22, &[60]);
+
+        elements
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_parse_maps_definitions_scopes_and_hover() {
+        let data = LsifDataSourceAdapter::parse(&sample_dump()).unwrap();
+        assert_eq!(data.project_root, "/repo");
+        assert_eq!(data.documents.len(), 1);
+        let doc = &data.documents[0];
+        assert_eq!(doc.relative_path, "app/main.py");
+        assert_eq!(doc.language, "python");
+
+        let names: Vec<_> = doc.definitions.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["Greeter", "name", "greet", "main"],
+            "local x is skipped"
+        );
+
+        let class_id = "lsif:app/main.py:0:6#Greeter";
+        let greet = data
+            .find_definition("python:app.main.Greeter.greet")
+            .unwrap();
+        assert_eq!(greet.kind, SymbolKind::Function);
+        assert_eq!(greet.enclosing_symbol.as_deref(), Some(class_id));
+        assert_eq!(greet.display_name, "def greet(self) -> str");
+        assert_eq!(greet.documentation, ["Say hello."]);
+        assert_eq!(greet.span.start_line, 2);
+        assert_eq!(greet.span.end_line, 4);
+
+        let name = doc.definitions.iter().find(|d| d.name == "name").unwrap();
+        assert!(name.is_field());
+        let fields = &data
+            .find_definition(class_id)
+            .unwrap()
+            .as_type()
+            .unwrap()
+            .fields;
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].symbol_id, name.symbol_id);
+    }
+
+    #[test]
+    fn test_parse_maps_references_to_call_and_read() {
+        let data = LsifDataSourceAdapter::parse(&sample_dump()).unwrap();
+        let mut refs: Vec<_> = data.documents[0]
+            .references
+            .iter()
+            .map(|r| {
+                (
+                    r.target_symbol.clone().unwrap(),
+                    r.role.clone(),
+                    r.enclosing_symbol.clone(),
+                    r.location.line,
+                )
+            })
+            .collect();
+        refs.sort_by_key(|r| r.3);
+        let main = "lsif:app/main.py:6:4#main".to_string();
+        assert_eq!(
+            refs,
+            [
+                (
+                    "python:app.main.Greeter.greet".to_string(),
+                    ReferenceRole::Call,
+                    main.clone(),
+                    7
+                ),
+                (
+                    "lsif:app/main.py:1:4#name".to_string(),
+                    ReferenceRole::Read,
+                    main,
+                    8
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_skips_ranges_outside_known_documents() {
+        // `contains` from a vertex that is not a document
+        let dump = sample_dump().replace(r#""outV":2,"type":"edge""#, r#""outV":99,"type":"edge""#);
+        assert_ne!(dump, sample_dump());
+        let data = LsifDataSourceAdapter::parse(&dump).unwrap();
+        assert_eq!(data.documents.len(), 1);
+        assert!(data.documents[0].definitions.is_empty());
+        assert!(data.documents[0].references.is_empty());
+
+        // A reference range contained in no document
+        let dump = sample_dump().replace(
+            r#""inVs":[10,11,12,13,14,15,16]"#,
+            r#""inVs":[10,11,12,13,14,15]"#,
+        );
+        assert_ne!(dump, sample_dump());
+        let data = LsifDataSourceAdapter::parse(&dump).unwrap();
+        let refs = &data.documents[0].references;
+        assert_eq!(refs.len(), 1, "the read of `name` has no document");
+        assert_eq!(refs[0].role, ReferenceRole::Call);
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_line() {
+        let err = LsifDataSourceAdapter::parse("{\"id\": 1}\nnot json").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+}
@@ -1,4 +1,5 @@
 pub mod doc_scorer;
 pub mod fs;
+pub mod lsif;
 pub mod size_function;
 pub mod test_detector;
@@ -0,0 +1,270 @@
+use crate::domain::policy::{SizeFunction, SourceSpan};
+use anyhow::{Context as _, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Token counts persisted across runs, keyed by `(span text hash, span columns, doc hash)`.
+///
+/// `variant` identifies the size-function configuration the counts were computed with
+/// (e.g. comment stripping); a cache written under another variant is discarded on load.
+/// Only counts used since the cache was loaded are saved, so the file holds one build's worth
+/// of spans instead of accumulating every edited or deleted one.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TokenCountCache {
+    variant: String,
+    entries: HashMap<String, u32>,
+    /// Counts loaded from disk and not used yet in this run; dropped on save.
+    #[serde(skip)]
+    previous: HashMap<String, u32>,
+}
+
+impl TokenCountCache {
+    pub fn new(variant: impl Into<String>) -> Self {
+        Self {
+            variant: variant.into(),
+            entries: HashMap::new(),
+            previous: HashMap::new(),
+        }
+    }
+
+    /// Load the cache at `path`; a missing file, or one written under a different
+    /// `variant`, yields an empty cache.
+    pub fn load(path: &Path, variant: &str) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new(variant));
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read token cache: {}", path.display()))?;
+        let mut cache: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse token cache: {}", path.display()))?;
+        if cache.variant != variant {
+            return Ok(Self::new(variant));
+        }
+        cache.previous = std::mem::take(&mut cache.entries);
+        Ok(cache)
+    }
+
+    /// Write the counts used since [`Self::load`]; unused ones are left out.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string(self)?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write token cache: {}", path.display()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len() + self.previous.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty() && self.previous.is_empty()
+    }
+
+    fn get(&mut self, key: &str) -> Option<u32> {
+        if let Some(&size) = self.entries.get(key) {
+            return Some(size);
+        }
+        let size = self.previous.remove(key)?;
+        self.entries.insert(key.to_string(), size);
+        Some(size)
+    }
+}
+
+/// Size function that serves counts from a [`TokenCountCache`] and only delegates to
+/// `inner` for spans it has not seen, so unchanged files are not re-tokenized on rebuild.
+pub struct CachedSizeFunction {
+    inner: Box<dyn SizeFunction>,
+    cache: Arc<Mutex<TokenCountCache>>,
+}
+
+impl CachedSizeFunction {
+    /// `cache` is shared so the caller can save it once the graph is built.
+    pub fn new(inner: Box<dyn SizeFunction>, cache: Arc<Mutex<TokenCountCache>>) -> Self {
+        Self { inner, cache }
+    }
+
+    fn cached(
+        &self,
+        language: Option<&str>,
+        source: &str,
+        span: &SourceSpan,
+        doc_texts: &[String],
+        compute: impl FnOnce() -> u32,
+    ) -> u32 {
+        let key = cache_key(language, source, span, doc_texts);
+        if let Some(size) = self.cache.lock().unwrap().get(&key) {
+            return size;
+        }
+        let size = compute();
+        self.cache.lock().unwrap().entries.insert(key, size);
+        size
+    }
+}
+
+impl SizeFunction for CachedSizeFunction {
+    fn compute(&self, source: &str, span: &SourceSpan, doc_texts: &[String]) -> u32 {
+        self.cached(None, source, span, doc_texts, || {
+            self.inner.compute(source, span, doc_texts)
+        })
+    }
+
+    fn compute_in_language(
+        &self,
+        language: &str,
+        source: &str,
+        span: &SourceSpan,
+        doc_texts: &[String],
+    ) -> u32 {
+        self.cached(Some(language), source, span, doc_texts, || {
+            self.inner
+                .compute_in_language(language, source, span, doc_texts)
+        })
+    }
+}
+
+/// Only the lines the span covers are hashed, with its columns and line count: the size of a
+/// span does not depend on the rest of the file, so edits elsewhere keep its count cached.
+fn cache_key(
+    language: Option<&str>,
+    source: &str,
+    span: &SourceSpan,
+    doc_texts: &[String],
+) -> String {
+    let line_count = span.end_line.saturating_sub(span.start_line) as usize + 1;
+    let mut span_hash = FNV_OFFSET;
+    for line in source
+        .lines()
+        .skip(span.start_line as usize)
+        .take(line_count)
+    {
+        span_hash = fnv1a(span_hash, line.as_bytes());
+        span_hash = fnv1a(span_hash, b"\n");
+    }
+    let mut doc_hash = FNV_OFFSET;
+    for doc in doc_texts {
+        doc_hash = fnv1a(doc_hash, doc.as_bytes());
+        doc_hash = fnv1a(doc_hash, &[0]);
+    }
+    format!(
+        "{}:{:016x}:{}:{}:{}:{:016x}",
+        language.unwrap_or(""),
+        span_hash,
+        line_count,
+        span.start_column,
+        span.end_column,
+        doc_hash
+    )
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// 64-bit FNV-1a; unlike `DefaultHasher`, stable across Rust releases, so keys stay valid
+/// for a cache written by another build.
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Counts tokenizer invocations; the size is the number of lines in the span.
+    struct CountingSizeFunction(Arc<AtomicUsize>);
+
+    impl SizeFunction for CountingSizeFunction {
+        fn compute(&self, _source: &str, span: &SourceSpan, _doc_texts: &[String]) -> u32 {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            span.end_line - span.start_line + 1
+        }
+    }
+
+    fn span(end_line: u32) -> SourceSpan {
+        SourceSpan {
+            start_line: 0,
+            start_column: 0,
+            end_line,
+            end_column: 0,
+        }
+    }
+
+    /// Size both files the way a build would, returning the counts.
+    fn build(path: &Path, calls: &Arc<AtomicUsize>, a: &str, b: &str) -> (u32, u32) {
+        let cache = Arc::new(Mutex::new(TokenCountCache::load(path, "v1").unwrap()));
+        let size_fn = CachedSizeFunction::new(
+            Box::new(CountingSizeFunction(Arc::clone(calls))),
+            Arc::clone(&cache),
+        );
+        let sizes = (
+            size_fn.compute_in_language("python", a, &span(1), &[]),
+            size_fn.compute_in_language("python", b, &span(2), &["Doc.".into()]),
+        );
+        cache.lock().unwrap().save(path).unwrap();
+        sizes
+    }
+
+    #[test]
+    fn test_rebuild_skips_tokenization_of_unchanged_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("token_cache.json");
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        assert_eq!(build(&path, &calls, "def a(): pass\n", "x = 1\n"), (2, 3));
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+
+        // b.py changed: only it is tokenized again.
+        assert_eq!(build(&path, &calls, "def a(): pass\n", "x = 2\n"), (2, 3));
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+
+        // Nothing changed: no tokenization at all.
+        build(&path, &calls, "def a(): pass\n", "x = 2\n");
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_save_drops_counts_not_used_since_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("token_cache.json");
+        let calls = Arc::new(AtomicUsize::new(0));
+        build(&path, &calls, "a", "b");
+        build(&path, &calls, "a", "c");
+
+        // The count for "b" was not used by the second build and is gone
+        assert_eq!(TokenCountCache::load(&path, "v1").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_key_ignores_lines_outside_the_span() {
+        let span = SourceSpan {
+            start_line: 1,
+            start_column: 4,
+            end_line: 2,
+            end_column: 8,
+        };
+        let key = |source| cache_key(Some("python"), source, &span, &[]);
+        assert_eq!(
+            key("import a\ndef f():\n    pass\n"),
+            key("import b\ndef f():\n    pass\n")
+        );
+        assert_ne!(
+            key("import a\ndef f():\n    pass\n"),
+            key("import a\ndef g():\n    pass\n")
+        );
+    }
+
+    #[test]
+    fn test_cache_from_other_variant_is_discarded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("token_cache.json");
+        let calls = Arc::new(AtomicUsize::new(0));
+        build(&path, &calls, "a", "b");
+
+        assert_eq!(TokenCountCache::load(&path, "v1").unwrap().len(), 2);
+        assert!(TokenCountCache::load(&path, "v2").unwrap().is_empty());
+    }
+}
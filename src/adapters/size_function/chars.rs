@@ -0,0 +1,83 @@
+use super::tiktoken::code_text;
+use crate::domain::policy::{SizeFunction, SourceSpan};
+
+/// Character-count size function: code characters divided by `chars_per_token`.
+///
+/// For models whose tokenizer is not tiktoken's; 4 characters per token is the usual
+/// approximation. Documentation and comment-only lines are excluded exactly as in
+/// [`TiktokenSizeFunction`](super::tiktoken::TiktokenSizeFunction).
+#[derive(Debug, Clone, Copy)]
+pub struct CharSizeFunction {
+    pub chars_per_token: f32,
+}
+
+impl Default for CharSizeFunction {
+    fn default() -> Self {
+        Self::new(4.0)
+    }
+}
+
+impl CharSizeFunction {
+    pub fn new(chars_per_token: f32) -> Self {
+        Self { chars_per_token }
+    }
+}
+
+impl SizeFunction for CharSizeFunction {
+    fn compute(&self, source: &str, span: &SourceSpan, doc_texts: &[String]) -> u32 {
+        let chars = code_text(source, span, doc_texts).chars().count();
+        (chars as f32 / self.chars_per_token.max(f32::EPSILON)).ceil() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::size_function::tiktoken::TiktokenSizeFunction;
+
+    fn span(end_line: u32, end_column: u32) -> SourceSpan {
+        SourceSpan {
+            start_line: 0,
+            start_column: 0,
+            end_line,
+            end_column,
+        }
+    }
+
+    #[test]
+    fn test_divides_characters_by_ratio() {
+        // 16 characters
+        let source = "let total = a+b;";
+        assert_eq!(
+            CharSizeFunction::default().compute(source, &span(0, 16), &[]),
+            4
+        );
+        assert_eq!(
+            CharSizeFunction::new(3.5).compute(source, &span(0, 16), &[]),
+            5
+        );
+    }
+
+    #[test]
+    fn test_doc_text_is_excluded_like_tiktoken() {
+        let source = "def f():\n    \"\"\"Explain the function at length.\"\"\"\n    return 1";
+        let docs = ["\"\"\"Explain the function at length.\"\"\"".to_string()];
+        let chars = CharSizeFunction::new(1.0);
+        let with_docs = chars.compute(source, &span(2, 12), &[]);
+        let without_docs = chars.compute(source, &span(2, 12), &docs);
+        // "def f():", the indentation left by the docstring, "    return 1"
+        assert_eq!(without_docs, 8 + 1 + 4 + 1 + 12);
+        assert!(with_docs > without_docs);
+
+        let tiktoken = TiktokenSizeFunction::new();
+        let source_without_docs = "def f():\n    \n    return 1";
+        assert_eq!(
+            tiktoken.compute(source, &span(2, 12), &docs),
+            tiktoken.compute(source_without_docs, &span(2, 12), &[])
+        );
+        assert_eq!(
+            chars.compute(source_without_docs, &span(2, 12), &[]),
+            without_docs
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use super::tiktoken::code_text;
+use crate::domain::policy::{SizeFunction, SourceSpan};
+
+/// Line-count size function: the number of non-blank code lines in the span.
+///
+/// Much cheaper than tokenizing, for approximate CF on very large codebases. Lines are taken
+/// from the same code text [`TiktokenSizeFunction`] measures, so documentation (`doc_texts`)
+/// and comment-only lines are excluded the same way.
+///
+/// [`TiktokenSizeFunction`]: super::tiktoken::TiktokenSizeFunction
+#[derive(Debug, Default)]
+pub struct LineCountSizeFunction;
+
+impl LineCountSizeFunction {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SizeFunction for LineCountSizeFunction {
+    fn compute(&self, source: &str, span: &SourceSpan, doc_texts: &[String]) -> u32 {
+        code_text(source, span, doc_texts)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .count() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_code_lines_only() {
+        let source = "x = 0\n\
+                      @cached\n\
+                      def foo(a):\n\
+                      \x20   \"\"\"Add one.\"\"\"\n\
+                      \n\
+                      \x20   # increment\n\
+                      \x20   return a + 1\n";
+        let span = SourceSpan {
+            start_line: 1,
+            start_column: 0,
+            end_line: 6,
+            end_column: 16,
+        };
+        let f = LineCountSizeFunction::new();
+        // decorator, def line, return line, plus the docstring quotes when only its text is known
+        assert_eq!(f.compute(source, &span, &["Add one.".to_string()]), 4);
+        assert_eq!(
+            f.compute(source, &span, &["\"\"\"Add one.\"\"\"".to_string()]),
+            3
+        );
+    }
+
+    #[test]
+    fn test_span_past_end_of_source_is_empty() {
+        let span = SourceSpan {
+            start_line: 10,
+            start_column: 0,
+            end_line: 12,
+            end_column: 0,
+        };
+        assert_eq!(LineCountSizeFunction::new().compute("a\nb", &span, &[]), 0);
+    }
+}
@@ -1 +1,4 @@
+pub mod cache;
+pub mod chars;
+pub mod lines;
 pub mod tiktoken;
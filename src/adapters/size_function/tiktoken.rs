@@ -1,8 +1,11 @@
+use crate::domain::comment_syntax::{CommentSyntax, comment_syntax};
 use crate::domain::policy::{SizeFunction, SourceSpan};
 
 /// Tiktoken-based size function
 /// Uses tiktoken to count tokens in the source code span
-pub struct TiktokenSizeFunction;
+pub struct TiktokenSizeFunction {
+    strip_comments: bool,
+}
 
 impl Default for TiktokenSizeFunction {
     fn default() -> Self {
@@ -12,98 +15,195 @@ impl Default for TiktokenSizeFunction {
 
 impl TiktokenSizeFunction {
     pub fn new() -> Self {
-        Self
+        Self {
+            strip_comments: false,
+        }
+    }
+
+    /// Also strip trailing inline comments (`x = 1  # note`) in languages with known comment
+    /// syntax (see [`comment_syntax`]). Whole-line comments are always excluded.
+    pub fn with_comment_stripping(mut self, enabled: bool) -> Self {
+        self.strip_comments = enabled;
+        self
     }
 }
 
 impl SizeFunction for TiktokenSizeFunction {
     fn compute(&self, source: &str, span: &SourceSpan, doc_texts: &[String]) -> u32 {
-        // Extract the code snippet from the span
-        let lines: Vec<&str> = source.lines().collect();
+        self.compute_snippet(None, source, span, doc_texts)
+    }
 
-        if span.start_line as usize >= lines.len() {
-            return 0;
-        }
+    fn compute_in_language(
+        &self,
+        language: &str,
+        source: &str,
+        span: &SourceSpan,
+        doc_texts: &[String],
+    ) -> u32 {
+        let syntax = if self.strip_comments {
+            comment_syntax(language)
+        } else {
+            None
+        };
+        self.compute_snippet(syntax, source, span, doc_texts)
+    }
+}
+
+impl TiktokenSizeFunction {
+    fn compute_snippet(
+        &self,
+        inline_comments: Option<&CommentSyntax>,
+        source: &str,
+        span: &SourceSpan,
+        doc_texts: &[String],
+    ) -> u32 {
+        // Use a simple token counting approach (approximate)
+        count_tokens_approx(&measured_text(inline_comments, source, span, doc_texts))
+    }
+}
 
-        let start_line_idx = span.start_line as usize;
-        let end_line_idx = (span.end_line as usize).min(lines.len() - 1);
+/// Code in `span` with `doc_texts` and comment-only lines removed: the text size functions
+/// measure, so documentation does not "punish" well-documented code.
+pub(crate) fn code_text(source: &str, span: &SourceSpan, doc_texts: &[String]) -> String {
+    measured_text(None, source, span, doc_texts)
+}
+
+/// [`code_text`], also stripping inline comments when `inline_comments` is set.
+fn measured_text(
+    inline_comments: Option<&CommentSyntax>,
+    source: &str,
+    span: &SourceSpan,
+    doc_texts: &[String],
+) -> String {
+    // Extract the code snippet from the span
+    let lines: Vec<&str> = source.lines().collect();
+
+    if span.start_line as usize >= lines.len() {
+        return String::new();
+    }
 
-        let mut code_snippet = String::new();
+    let start_line_idx = span.start_line as usize;
+    let end_line_idx = (span.end_line as usize).min(lines.len() - 1);
+
+    let mut code_snippet = String::new();
+
+    if start_line_idx == end_line_idx {
+        // Single line
+        let line = lines[start_line_idx];
+        let start_col = span.start_column as usize;
+        let end_col = span.end_column as usize;
+        let start = column_to_byte_idx(line, start_col);
+        let end = column_to_byte_idx(line, end_col);
+        if start < end {
+            code_snippet.push_str(&line[start..end]);
+        }
+    } else {
+        // Multiple lines
+        // First line
+        let first_line = lines[start_line_idx];
+        let start_col = span.start_column as usize;
+        let start = column_to_byte_idx(first_line, start_col);
+        if start < first_line.len() {
+            code_snippet.push_str(&first_line[start..]);
+        }
+        code_snippet.push('\n');
 
-        if start_line_idx == end_line_idx {
-            // Single line
-            let line = lines[start_line_idx];
-            let start_col = span.start_column as usize;
+        // Middle lines
+        for line in lines.iter().take(end_line_idx).skip(start_line_idx + 1) {
+            code_snippet.push_str(line);
+            code_snippet.push('\n');
+        }
+
+        // Last line
+        if end_line_idx < lines.len() {
+            let last_line = lines[end_line_idx];
             let end_col = span.end_column as usize;
-            let start = column_to_byte_idx(line, start_col);
-            let end = column_to_byte_idx(line, end_col);
-            if start < end {
-                code_snippet.push_str(&line[start..end]);
+            let end = column_to_byte_idx(last_line, end_col);
+            if end > 0 {
+                code_snippet.push_str(&last_line[..end]);
             }
-        } else {
-            // Multiple lines
-            // First line
-            let first_line = lines[start_line_idx];
-            let start_col = span.start_column as usize;
-            let start = column_to_byte_idx(first_line, start_col);
-            if start < first_line.len() {
-                code_snippet.push_str(&first_line[start..]);
-            }
-            code_snippet.push('\n');
+        }
+    }
+
+    // --- Comment Stripping Logic ---
+
+    // 1. Remove recognized doc_texts contents
+    let mut pure_logic = code_snippet;
+    for doc in doc_texts {
+        pure_logic = pure_logic.replace(doc, "");
+    }
 
-            // Middle lines
-            for line in lines.iter().take(end_line_idx).skip(start_line_idx + 1) {
-                code_snippet.push_str(line);
-                code_snippet.push('\n');
+    // 2. Strip common comment markers and empty comment lines
+    // This covers ///, //, #, and block comment markers like /*, */, *
+    let lines: Vec<String> = pure_logic
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim();
+            // If the line consists only of comment markers or is empty after markers removed
+            if trimmed.starts_with("///")
+                || trimmed.starts_with("//")
+                || trimmed.starts_with('#')
+                || trimmed.starts_with("/*")
+                || trimmed.starts_with("*/")
+                || trimmed == "*"
+            {
+                String::new() // Effectively remove the line
+            } else if let Some(syntax) = inline_comments {
+                strip_inline_comment(line, syntax).trim_end().to_string()
+            } else {
+                line.to_string() // Keep the line as is (minus the doc content removed earlier)
             }
+        })
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    lines.join("\n")
+}
 
-            // Last line
-            if end_line_idx < lines.len() {
-                let last_line = lines[end_line_idx];
-                let end_col = span.end_column as usize;
-                let end = column_to_byte_idx(last_line, end_col);
-                if end > 0 {
-                    code_snippet.push_str(&last_line[..end]);
+/// Remove inline comments from one line, ignoring markers inside string literals.
+///
+/// Line-based heuristic: string state does not carry across lines, so a marker inside a
+/// multi-line string (Python triple quotes, template literals) may still be stripped, and an
+/// unterminated `/*` drops the rest of the line.
+fn strip_inline_comment(line: &str, syntax: &CommentSyntax) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut quote: Option<char> = None;
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if let Some(q) = quote {
+            out.push(c);
+            if c == '\\' {
+                if let Some((_, escaped)) = chars.next() {
+                    out.push(escaped);
                 }
+            } else if c == q {
+                quote = None;
             }
+            continue;
         }
-
-        // --- Comment Stripping Logic ---
-
-        // 1. Remove recognized doc_texts contents
-        let mut pure_logic = code_snippet;
-        for doc in doc_texts {
-            pure_logic = pure_logic.replace(doc, "");
+        let rest = &line[i..];
+        if rest.starts_with(syntax.line_marker) {
+            break;
         }
-
-        // 2. Strip common comment markers and empty comment lines
-        // This covers ///, //, #, and block comment markers like /*, */, *
-        let lines: Vec<String> = pure_logic
-            .lines()
-            .map(|line| {
-                let trimmed = line.trim();
-                // If the line consists only of comment markers or is empty after markers removed
-                if trimmed.starts_with("///")
-                    || trimmed.starts_with("//")
-                    || trimmed.starts_with('#')
-                    || trimmed.starts_with("/*")
-                    || trimmed.starts_with("*/")
-                    || trimmed == "*"
-                {
-                    "" // Effectively remove the line
-                } else {
-                    line // Keep the line as is (minus the doc content removed earlier)
+        if syntax.block_comments && rest.starts_with("/*") {
+            match rest[2..].find("*/") {
+                Some(end) => {
+                    // Skip to the closing marker; the iterator resumes after it.
+                    let close = i + 2 + end + 2;
+                    while chars.peek().is_some_and(|(j, _)| *j < close) {
+                        chars.next();
+                    }
+                    continue;
                 }
-            })
-            .filter(|s| !s.is_empty())
-            .map(|s| s.to_string())
-            .collect();
-
-        let final_text = lines.join("\n");
-
-        // Use a simple token counting approach (approximate)
-        count_tokens_approx(&final_text)
+                None => break,
+            }
+        }
+        if syntax.quotes.contains(&c) {
+            quote = Some(c);
+        }
+        out.push(c);
     }
+    out
 }
 
 fn count_tokens_approx(text: &str) -> u32 {
@@ -141,6 +241,7 @@ fn column_to_byte_idx(line: &str, column: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::comment_syntax::{C_FAMILY_COMMENTS, HASH_COMMENTS, RUST_COMMENTS};
     use crate::domain::policy::SourceSpan;
 
     #[test]
@@ -232,6 +333,57 @@ mod tests {
         assert!(size < 10);
     }
 
+    #[test]
+    fn test_strip_comments_excludes_inline_comment_tokens() {
+        let source = "total = price * qty  # apply the regional discount table here\n";
+        let span = SourceSpan {
+            start_line: 0,
+            start_column: 0,
+            end_line: 0,
+            end_column: source.len() as u32 - 1,
+        };
+        let code_only = "total = price * qty";
+        let code_span = SourceSpan {
+            end_column: code_only.len() as u32,
+            ..span.clone()
+        };
+
+        let plain = TiktokenSizeFunction::new();
+        let stripping = TiktokenSizeFunction::new().with_comment_stripping(true);
+        let expected = plain.compute(code_only, &code_span, &[]);
+        assert!(plain.compute_in_language("python", source, &span, &[]) > expected);
+        assert_eq!(
+            stripping.compute_in_language("python", source, &span, &[]),
+            expected
+        );
+        // Unknown language: nothing stripped
+        assert!(stripping.compute_in_language("cobol", source, &span, &[]) > expected);
+    }
+
+    #[test]
+    fn test_strip_inline_comment_respects_strings() {
+        assert_eq!(
+            strip_inline_comment("s = \"#fff\"  # color", &HASH_COMMENTS),
+            "s = \"#fff\"  "
+        );
+        assert_eq!(
+            strip_inline_comment("let u = \"http://x\"; // url", &RUST_COMMENTS),
+            "let u = \"http://x\"; "
+        );
+        assert_eq!(
+            strip_inline_comment("fn f<'a>(x: &'a str) // lifetime", &RUST_COMMENTS),
+            "fn f<'a>(x: &'a str) "
+        );
+        assert_eq!(
+            strip_inline_comment("call(a /* first */, b)", &C_FAMILY_COMMENTS),
+            "call(a , b)"
+        );
+        assert_eq!(
+            strip_inline_comment("x = 'it\\'s # not'", &HASH_COMMENTS),
+            "x = 'it\\'s # not'"
+        );
+    }
+
     #[test]
     fn test_unicode_columns_do_not_panic() {
         let f = TiktokenSizeFunction::new();
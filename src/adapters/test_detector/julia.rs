@@ -0,0 +1,66 @@
+use super::TestDetector;
+
+/// Julia test code detector
+///
+/// Conventions:
+/// - test/ directories (Pkg's standard layout)
+/// - runtests.jl entry files
+/// - @test / @testset blocks (detected in symbols)
+pub struct JuliaTestDetector;
+
+impl TestDetector for JuliaTestDetector {
+    fn is_test_code(&self, symbol: &str, file_path: &str) -> bool {
+        // Check test directory
+        if file_path.contains("/test/")
+            || file_path.starts_with("test/")
+            || file_path.contains("/tests/")
+            || file_path.starts_with("tests/")
+        {
+            return true;
+        }
+
+        // Check test runner file
+        if file_path
+            .split('/')
+            .next_back()
+            .is_some_and(|filename| filename == "runtests.jl")
+        {
+            return true;
+        }
+
+        // Definitions nested in @test/@testset blocks carry the macro in their symbol
+        symbol.contains("@test")
+    }
+
+    fn language(&self) -> &str {
+        "julia"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_julia_test_directory_and_runner() {
+        let detector = JuliaTestDetector;
+        assert!(detector.is_test_code("", "test/runtests.jl"));
+        assert!(detector.is_test_code("", "MyPkg/test/parser_tests.jl"));
+        assert!(detector.is_test_code("", "scripts/runtests.jl"));
+        assert!(!detector.is_test_code("", "src/MyPkg.jl"));
+        assert!(!detector.is_test_code("", "src/testing_utils.jl"));
+    }
+
+    #[test]
+    fn test_detects_julia_testset_symbols() {
+        let detector = JuliaTestDetector;
+        assert!(detector.is_test_code("MyPkg.@testset.helper#", "src/MyPkg.jl"));
+        assert!(!detector.is_test_code("MyPkg.parse#", "src/MyPkg.jl"));
+    }
+
+    #[test]
+    fn test_language_returns_julia() {
+        let detector = JuliaTestDetector;
+        assert_eq!(detector.language(), "julia");
+    }
+}
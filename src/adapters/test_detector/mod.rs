@@ -6,13 +6,17 @@
 mod go;
 mod java;
 mod javascript;
+mod julia;
 mod python;
+mod ruby;
 mod rust;
 
 pub use go::GoTestDetector;
 pub use java::JavaTestDetector;
 pub use javascript::JavaScriptTestDetector;
+pub use julia::JuliaTestDetector;
 pub use python::PythonTestDetector;
+pub use ruby::RubyTestDetector;
 pub use rust::RustTestDetector;
 
 /// Trait for detecting test code based on language conventions
@@ -24,6 +28,8 @@ pub trait TestDetector: Send + Sync {
     fn language(&self) -> &str;
 }
 
+use crate::domain::ports::TestCodeClassifier;
+
 /// Multi-language test detector that routes to language-specific detectors
 pub struct UniversalTestDetector {
     detectors: Vec<Box<dyn TestDetector>>,
@@ -38,6 +44,8 @@ impl UniversalTestDetector {
                 Box::new(JavaScriptTestDetector),
                 Box::new(JavaTestDetector),
                 Box::new(GoTestDetector),
+                Box::new(JuliaTestDetector),
+                Box::new(RubyTestDetector),
             ],
         }
     }
@@ -70,6 +78,10 @@ impl UniversalTestDetector {
             return Some(&JavaTestDetector as &dyn TestDetector);
         } else if file_path.ends_with(".go") {
             return Some(&GoTestDetector as &dyn TestDetector);
+        } else if file_path.ends_with(".jl") {
+            return Some(&JuliaTestDetector as &dyn TestDetector);
+        } else if file_path.ends_with(".rb") {
+            return Some(&RubyTestDetector as &dyn TestDetector);
         }
         None
     }
@@ -81,6 +93,12 @@ impl Default for UniversalTestDetector {
     }
 }
 
+impl TestCodeClassifier for UniversalTestDetector {
+    fn is_test_code(&self, symbol: &str, file_path: &str) -> bool {
+        UniversalTestDetector::is_test_code(self, symbol, file_path)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,5 +120,11 @@ mod tests {
         // Go
         assert!(d.is_test_code("", "pkg/foo_test.go"));
         assert!(!d.is_test_code("", "src/main.go"));
+        // Julia
+        assert!(d.is_test_code("", "test/runtests.jl"));
+        assert!(!d.is_test_code("", "src/MyPkg.jl"));
+        // Ruby
+        assert!(d.is_test_code("", "spec/models/user_spec.rb"));
+        assert!(!d.is_test_code("", "app/models/user.rb"));
     }
 }
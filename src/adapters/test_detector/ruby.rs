@@ -0,0 +1,66 @@
+use super::TestDetector;
+
+/// Ruby test code detector
+///
+/// Conventions:
+/// - *_spec.rb (RSpec) and *_test.rb (Minitest) files
+/// - spec/ and test/ directories
+/// - RSpec `describe`/`it` blocks (detected in symbols)
+pub struct RubyTestDetector;
+
+impl TestDetector for RubyTestDetector {
+    fn is_test_code(&self, symbol: &str, file_path: &str) -> bool {
+        // Check test directories
+        if file_path.contains("/spec/")
+            || file_path.starts_with("spec/")
+            || file_path.contains("/test/")
+            || file_path.starts_with("test/")
+        {
+            return true;
+        }
+
+        // Check test file patterns
+        if file_path.ends_with("_spec.rb") || file_path.ends_with("_test.rb") {
+            return true;
+        }
+
+        // Definitions nested in RSpec example groups carry the block in their symbol
+        symbol
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .any(|part| matches!(part, "describe" | "it"))
+    }
+
+    fn language(&self) -> &str {
+        "ruby"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_ruby_test_files_and_directories() {
+        let detector = RubyTestDetector;
+        assert!(detector.is_test_code("", "spec/models/user_spec.rb"));
+        assert!(detector.is_test_code("", "test/models/user_test.rb"));
+        assert!(detector.is_test_code("", "engines/billing/spec/support/helpers.rb"));
+        assert!(detector.is_test_code("", "lib/parser_test.rb"));
+        assert!(!detector.is_test_code("", "app/models/user.rb"));
+        assert!(!detector.is_test_code("", "lib/spectrum.rb"));
+    }
+
+    #[test]
+    fn test_detects_rspec_block_symbols() {
+        let detector = RubyTestDetector;
+        assert!(detector.is_test_code("User#describe().helper().", "app/models/user.rb"));
+        assert!(detector.is_test_code("Billing/it(charges).", "app/billing.rb"));
+        assert!(!detector.is_test_code("User#edit().", "app/models/user.rb"));
+    }
+
+    #[test]
+    fn test_language_returns_ruby() {
+        let detector = RubyTestDetector;
+        assert_eq!(detector.language(), "ruby");
+    }
+}
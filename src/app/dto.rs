@@ -1,12 +1,14 @@
 use rmcp::schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[derive(Default)]
 pub enum PolicyKind {
     #[default]
     Academic,
+    Balanced,
     Strict,
 }
 
@@ -18,21 +20,61 @@ pub struct HealthResponse {
     pub edge_count: usize,
 }
 
+/// Health plus a sample of source reads, to catch a stale or misconfigured project root.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DeepHealthResponse {
+    #[serde(flatten)]
+    pub health: HealthResponse,
+    pub sampled_nodes: usize,
+    pub readable_nodes: usize,
+    pub failures: Vec<SourceReadFailure>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SourceReadFailure {
+    pub file_path: String,
+    pub error: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ComputeRequest {
     pub symbols: Vec<String>,
     #[serde(default)]
     pub policy: PolicyKind,
     pub max_tokens: Option<u32>,
+    /// Follow edges at most this many hops from the start symbols; nodes at the cap still count.
+    #[serde(default)]
+    pub max_depth: Option<u32>,
+    /// Overrides the policy's documentation threshold for regular functions.
+    #[serde(default)]
+    pub func_doc_threshold: Option<f32>,
+    /// Overrides the policy's documentation threshold for interface/abstract methods.
+    #[serde(default)]
+    pub interface_doc_threshold: Option<f32>,
+    /// Symbols the reader has already read: they cost nothing, so the result is the additional
+    /// context needed (their dependencies still count). `max_tokens` is ignored when set.
+    #[serde(default)]
+    pub already_read: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ComputeResponse {
     pub starting_symbols: Vec<String>,
     pub total_context_size: u32,
+    /// Like `total_context_size`, but boundaries count only their signature size.
+    #[serde(default)]
+    pub weighted_context_size: u32,
+    /// Distinct source lines covered by the reachable nodes (overlapping spans counted once).
+    pub unique_source_lines: usize,
     pub reachable_node_count: usize,
+    /// Reachable nodes where traversal stopped (valid abstractions, not expanded further).
+    #[serde(default)]
+    pub boundary_count: usize,
     pub reachable_nodes_by_layer: Vec<Vec<ReachableNode>>,
     pub reachable_nodes_ordered: Vec<ReachableNode>,
+    /// Distinct files of the reachable nodes, sorted.
+    #[serde(default)]
+    pub files_touched: Vec<String>,
     /// How each input anchor was resolved (class expansion, variable lookup, etc.).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub anchor_resolutions: Option<Vec<AnchorResolution>>,
@@ -77,6 +119,59 @@ pub struct ReachabilityResponse {
     pub witness_paths: Vec<Vec<String>>,
 }
 
+/// Edge chain through which CF traversal from `from` pulls in `to`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PathResponse {
+    pub from: String,
+    pub to: String,
+    /// False when `to` is not in `from`'s reachable set (`hops` is then empty).
+    pub reachable: bool,
+    pub hops: Vec<PathHop>,
+}
+
+/// One traversal hop, written in edge direction (`source --edge_kind--> target`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PathHop {
+    pub source: String,
+    pub target: String,
+    pub edge_kind: String,
+    /// "Forward", or "CallIn" / "SharedStateWrite" when the edge was followed backwards.
+    pub reached_via: String,
+    /// Pruning decision at forward hops ("Boundary" or "Transparent").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decision: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub boundary_reason: Option<String>,
+}
+
+/// Which edges of a node [`NeighborsResponse`] covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[derive(Default)]
+pub enum NeighborDirection {
+    /// Edges from the node (its dependencies).
+    Out,
+    /// Edges into the node (its dependents).
+    In,
+    #[default]
+    Both,
+}
+
+/// A node and the nodes one edge away from it, without running CF.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NeighborsResponse {
+    pub node: ReachableNode,
+    pub neighbors: Vec<Neighbor>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Neighbor {
+    pub node: ReachableNode,
+    pub edge_kind: String,
+    /// `out` when the edge goes from the queried node to this one, `in` otherwise.
+    pub direction: NeighborDirection,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ReachableNode {
     pub id: u32,
@@ -109,6 +204,176 @@ pub struct SpanDto {
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct StatsResponse {
     pub functions: CfDistribution,
+    /// Variable (global and field) CF distribution; only set when requested.
+    /// Immutable variables are boundaries with nothing to expand, so they cluster near their
+    /// own size; the tail is mutable state pulling in its writers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variables: Option<CfDistribution>,
+    /// The module-level share of [`Self::variables`]; only set when requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub global_variables: Option<CfDistribution>,
+    /// The class/struct field share of [`Self::variables`]; only set when requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field_variables: Option<CfDistribution>,
+    /// Functions that qualify as boundaries under the policy (a call into them stops there).
+    #[serde(default)]
+    pub boundary_count: usize,
+    /// Functions a call would traverse into.
+    #[serde(default)]
+    pub transparent_count: usize,
+}
+
+/// Union CF per source file (all of the file's definitions as starting points).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FileStatsResponse {
+    pub distribution: CfDistribution,
+    /// Files sorted by CF, heaviest first.
+    pub files: Vec<FileCfItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FileCfItem {
+    pub file_path: String,
+    /// Number of definitions used as starting points.
+    pub node_count: usize,
+    pub cf: u32,
+}
+
+/// Weights and scales of the composite fitness score. Deserializable so teams can keep their
+/// weighting in a file; omitted fields fall back to [`Default`].
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct FitnessConfig {
+    pub median_cf_weight: f32,
+    pub boundary_ratio_weight: f32,
+    pub largest_cycle_weight: f32,
+    pub god_object_weight: f32,
+    /// Median function CF (tokens) that scores 0.5: `scale / (scale + median)`.
+    pub median_cf_scale: f32,
+    /// Extra functions in the largest call cycle that score 0.5: `scale / (scale + size - 1)`.
+    pub cycle_size_scale: f32,
+    /// A type with more members (methods and fields) than this counts as a god object.
+    pub god_object_min_members: usize,
+}
+
+impl Default for FitnessConfig {
+    fn default() -> Self {
+        Self {
+            median_cf_weight: 1.0,
+            boundary_ratio_weight: 1.0,
+            largest_cycle_weight: 1.0,
+            god_object_weight: 1.0,
+            median_cf_scale: 1000.0,
+            cycle_size_scale: 5.0,
+            god_object_min_members: 20,
+        }
+    }
+}
+
+/// Composite coupling-health score: weighted mean of per-factor scores, scaled to 0–100.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FitnessResponse {
+    pub score: f32,
+    pub factors: Vec<FitnessFactor>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FitnessFactor {
+    pub name: String,
+    /// Raw measurement (tokens, ratio, node count, type count).
+    pub value: f32,
+    /// Normalized to [0, 1]; higher is healthier.
+    pub score: f32,
+    pub weight: f32,
+}
+
+/// Recorded CF of every symbol, for comparing later runs against a baseline.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct CfSnapshot {
+    pub symbols: BTreeMap<String, u32>,
+}
+
+/// Result of comparing current CF against a [`CfSnapshot`].
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BaselineComparison {
+    /// Symbols whose CF grew beyond the tolerance, largest growth first.
+    pub regressions: Vec<CfChange>,
+    /// Symbols whose CF shrank.
+    pub improved: usize,
+    /// Symbols present now but not in the baseline.
+    pub added: Vec<String>,
+    /// Baseline symbols that no longer exist.
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CfChange {
+    pub symbol: String,
+    pub baseline: u32,
+    pub current: u32,
+}
+
+/// CF differences between two indexes of the same project, matched by symbol string.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CfDiffResponse {
+    /// Symbols present in both whose CF changed by at least the threshold, largest
+    /// absolute delta first.
+    pub changes: Vec<CfDelta>,
+    /// Symbols only in the new index.
+    pub added: usize,
+    /// Symbols only in the old index.
+    pub removed: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CfDelta {
+    pub symbol: String,
+    pub old: u32,
+    pub new: u32,
+    /// `new - old`; negative when the CF shrank.
+    pub delta: i64,
+}
+
+/// Nodes and edges of the whole graph, or of one symbol's reachable set, for external
+/// graph tools (Gephi, Graphviz).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GraphExport {
+    pub nodes: Vec<ExportNode>,
+    /// Edges between exported nodes; `source`/`target` index into `nodes`.
+    pub edges: Vec<ExportEdge>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExportNode {
+    pub symbol: String,
+    pub node_type: String,
+    pub cf: u32,
+    pub doc_score: f32,
+    pub is_external: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExportEdge {
+    pub source: usize,
+    pub target: usize,
+    pub kind: String,
+}
+
+/// CF of the functions touched by a patch.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ChangedCfResponse {
+    /// Changed functions, highest CF first.
+    pub items: Vec<ChangedCfItem>,
+    /// Number of items whose CF exceeds the budget (0 without a budget).
+    pub over_budget: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ChangedCfItem {
+    pub symbol: String,
+    pub file_path: String,
+    pub cf: u32,
+    pub over_budget: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -117,6 +382,9 @@ pub struct CfDistribution {
     pub percentiles: Vec<PercentileValue>,
     pub average: u64,
     pub median: u32,
+    /// Always reported, whichever percentiles were requested: CF outliers live in the tail.
+    #[serde(default)]
+    pub p99: u32,
     pub min: u32,
     pub max: u32,
 }
@@ -139,6 +407,141 @@ pub struct TopItem {
     pub cf: u32,
 }
 
+/// Functions whose CF lies above a percentile cutoff, and their share of the summed CF.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ParetoResponse {
+    pub percentile: u32,
+    /// CF at `percentile`; listed functions lie strictly above it.
+    pub cutoff_cf: u32,
+    pub function_count: usize,
+    /// Sum of all function CFs.
+    pub total_cf: u64,
+    /// Fraction (0.0-1.0) of `total_cf` accounted for by the listed functions.
+    pub share_of_total: f64,
+    /// Highest CF first.
+    pub items: Vec<TopItem>,
+}
+
+/// Nodes that appear in the most other nodes' reachable sets (systemic coupling points).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UbiquityResponse {
+    /// Number of reachable sets computed (one per start node).
+    pub start_count: usize,
+    /// Most ubiquitous first.
+    pub items: Vec<UbiquityItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UbiquityItem {
+    pub symbol: String,
+    pub node_type: String,
+    /// Number of other nodes whose reachable set contains this one.
+    pub reachable_set_count: usize,
+}
+
+/// Share of nodes reachable from a set of entry points, following every edge (no pruning).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CoverageResponse {
+    pub entry_points: Vec<String>,
+    pub total_nodes: usize,
+    pub reachable_nodes: usize,
+    pub coverage_percent: f64,
+    /// Per source file, highest unreachable ratio first.
+    pub modules: Vec<ModuleCoverage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ModuleCoverage {
+    pub module: String,
+    pub total_nodes: usize,
+    pub reachable_nodes: usize,
+    pub unreachable_ratio: f64,
+    /// Unreachable ratio at or above the requested threshold.
+    pub likely_dead: bool,
+}
+
+/// N×N reachability matrix: `rows[i][j]` describes whether `symbols[j]` is in `symbols[i]`'s reachable set.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MatrixResponse {
+    pub symbols: Vec<String>,
+    /// 1/0 per entry, or the target's context_size when weighted (0 if unreachable).
+    pub rows: Vec<Vec<u32>>,
+}
+
+/// Reachable sets of two symbol sets (each the union over its members) partitioned into the
+/// nodes only A reaches, only B reaches, and the shared core.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SetDiffResponse {
+    /// CF of set A computed as one multi-start traversal.
+    pub cf_a: u32,
+    /// CF of set B computed as one multi-start traversal.
+    pub cf_b: u32,
+    pub only_a: SetDiffPart,
+    pub only_b: SetDiffPart,
+    pub shared: SetDiffPart,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SetDiffPart {
+    pub node_count: usize,
+    /// Sum of the context_size of the part's nodes.
+    pub context_size: u32,
+    /// Sorted.
+    pub symbols: Vec<String>,
+}
+
+/// Groups of functions whose normalized bodies are identical, largest saving first.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DuplicatesResponse {
+    pub groups: Vec<DuplicateGroup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DuplicateGroup {
+    /// Members of the group with their CF, sorted by symbol.
+    pub members: Vec<TopItem>,
+    /// context_size of a single copy.
+    pub context_size: u32,
+    /// CF removed by keeping only the copy with the highest CF (sum of the others).
+    pub saved_cf: u32,
+}
+
+/// Mutual recursion (call cycles) and direct recursion in the Call graph.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CyclesResponse {
+    /// Groups of two or more functions that transitively call each other, largest first.
+    pub cycles: Vec<CallCycle>,
+    /// Functions that call themselves directly, sorted.
+    pub self_calls: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CallCycle {
+    /// Sorted.
+    pub symbols: Vec<String>,
+    /// Sum of the members' context_size.
+    pub context_size: u32,
+}
+
+/// Calls the builder left without an edge, for diagnosing CFs that look too small.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UnresolvedResponse {
+    pub total: usize,
+    /// Unresolved calls per file, most first.
+    pub by_file: Vec<UnresolvedCount>,
+    /// Most frequent callees (method name, else target symbol), most first.
+    pub top_callees: Vec<UnresolvedCount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UnresolvedCount {
+    pub name: String,
+    pub count: usize,
+    /// A receiver seen with this callee (top_callees only), e.g. the variable whose type was unknown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example_receiver: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SearchResponse {
     pub items: Vec<SearchItem>,
@@ -158,11 +561,20 @@ pub struct ContextRequest {
     #[serde(default)]
     pub policy: PolicyKind,
     pub max_tokens: Option<u32>,
+    /// Follow edges at most this many hops from the symbol; nodes at the cap still count.
+    #[serde(default)]
+    pub max_depth: Option<u32>,
     #[serde(default)]
     pub include_code: bool,
     /// When true, include traversal_steps (edge kind + decision per node) for debugging.
     #[serde(default)]
     pub show_traversal: bool,
+    /// List callers up to this many Call hops upstream (0 = none). Not part of the CF.
+    #[serde(default)]
+    pub with_callers: u32,
+    /// Stop reaching into new files once this many distinct files are covered.
+    #[serde(default)]
+    pub max_files: Option<usize>,
 }
 
 /// One step in BFS traversal: node plus the edge and decision that led to it.
@@ -173,9 +585,24 @@ pub struct TraversalStepDto {
     pub edge_kind: Option<String>,
     /// Pruning decision at that edge ("Boundary" or "Transparent").
     pub decision: Option<String>,
-    /// For functions only: whether the signature is complete.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Why traversal stopped here (e.g. "External", "AbstractFactory"); set for Boundary only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub boundary_reason: Option<String>,
+    /// For functions only: whether the signature is complete, as seen by pruning.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub is_signature_complete: Option<bool>,
+    /// For functions only: DI-wired (container-injected) function.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_di_wired: Option<bool>,
+    /// For functions only: interface/abstract method.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_interface_method: Option<bool>,
+    /// Symbol of the node whose expansion reached this one; absent for start node(s).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// BFS layer (0 = start node).
+    #[serde(default)]
+    pub depth: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -183,10 +610,36 @@ pub struct ContextResponse {
     pub symbol: String,
     pub total_context_size: u32,
     pub reachable_node_count: usize,
+    /// Reachable nodes where traversal stopped (valid abstractions, not expanded further).
+    #[serde(default)]
+    pub boundary_count: usize,
     pub layers: Vec<ContextLayer>,
     /// Traversal steps in BFS order (only set when request had show_traversal).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub traversal_steps: Option<Vec<TraversalStepDto>>,
+    /// Upstream callers (only set when request had with_callers > 0); does not affect total_context_size.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callers: Option<Vec<CallerNode>>,
+    /// Exception types declared by reachable functions (Raises edges), sorted by type.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub raises: Vec<RaisedType>,
+}
+
+/// An exception type in the context's error surface.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RaisedType {
+    /// Type symbol (the enclosing type of the constructor the Raises edge points at).
+    pub type_symbol: String,
+    /// Reachable functions declaring it, sorted.
+    pub raised_by: Vec<String>,
+}
+
+/// A function that (transitively) calls the context symbol.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CallerNode {
+    /// 1 = direct caller, 2 = caller of a direct caller, ...
+    pub hops: u32,
+    pub node: ReachableNode,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
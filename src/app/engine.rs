@@ -1,26 +1,136 @@
+use crate::adapters::doc_scorer::coverage::CoverageDocScorer;
 use crate::adapters::doc_scorer::heuristic::HeuristicDocScorer;
+#[cfg(feature = "archive")]
+use crate::adapters::fs::archive::ArchiveSourceReader;
+use crate::adapters::fs::null::NullSourceReader;
+use crate::adapters::fs::semantic_cache::CachedDataSource;
+use crate::adapters::lsif::LsifDataSourceAdapter;
+use crate::adapters::size_function::cache::{CachedSizeFunction, TokenCountCache};
+use crate::adapters::size_function::chars::CharSizeFunction;
+use crate::adapters::size_function::lines::LineCountSizeFunction;
 use crate::adapters::size_function::tiktoken::TiktokenSizeFunction;
 use crate::adapters::test_detector::UniversalTestDetector;
 use crate::app::dto::*;
 use crate::domain::builder::GraphBuilder;
 use crate::domain::edge::EdgeKind;
 use crate::domain::graph::ContextGraph;
-use crate::domain::node::{Node, NodeId};
-use crate::domain::policy::{PruningDecision, PruningParams};
-use crate::domain::ports::SourceReader;
+use crate::domain::node::{Node, NodeId, VariableKind};
+use crate::domain::path_glob::PathGlob;
+use crate::domain::policy::{
+    DocumentationScorer, PruningDecision, PruningParams, SizeFunction, evaluate_forward,
+};
+use crate::domain::ports::{SemanticDataSource, SourceReader};
 use crate::domain::semantic::SemanticData;
-use crate::domain::solver::{CfSolver, ReachabilityOptions};
+use crate::domain::solver::{CfScratch, CfSolver, ReachabilityOptions, ReachedVia};
 use anyhow::{Context as _, Result, anyhow};
 use petgraph::graph::NodeIndex;
-use std::collections::{HashMap, HashSet};
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, RwLock};
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex, RwLock};
 
 #[derive(Clone)]
 pub struct ContextEngine {
     inner: Arc<RwLock<EngineData>>,
 }
 
+/// How the graph is built from SemanticData (kept across [`ContextEngine::reload`]).
+#[derive(Debug, Clone, Default)]
+pub struct LoadOptions {
+    /// Exclude trailing inline comments from context sizes (whole-line comments always are).
+    pub strip_comments: bool,
+    /// Treat never-written `ALL_CAPS` / `settings` globals as constants.
+    pub infer_config_constants: bool,
+    /// Persist token counts here so unchanged spans are not re-tokenized on rebuild.
+    pub token_cache: Option<PathBuf>,
+    /// How node context sizes are measured.
+    pub size_function: SizeFunctionKind,
+    /// How documentation completeness is scored.
+    pub doc_scorer: DocScorerKind,
+    /// Documents whose relative path matches any of these never enter the graph.
+    pub exclude: Vec<PathGlob>,
+    /// Read source files from this `.tar`, `.tar.gz`/`.tgz` or `.zip` instead of `project_root`.
+    #[cfg(feature = "archive")]
+    pub source_archive: Option<PathBuf>,
+    /// Size nodes of unreadable source files as 0 (with a warning) instead of failing the load.
+    pub allow_missing_sources: bool,
+    /// Analyze the index alone: every source file reads as empty ([`NullSourceReader`]).
+    pub no_source: bool,
+    /// Cap on external symbol sizes (builder default when `None`).
+    pub external_max_tokens: Option<u32>,
+    /// Characters of external signatures kept before truncation (builder default when `None`).
+    pub external_signature_len: Option<usize>,
+    /// Add Uses edges from functions to the project types of their parameters.
+    pub include_param_types: bool,
+    /// Merge external symbols that differ only in package version.
+    pub normalize_symbol_versions: bool,
+    /// Measure function signature sizes, for policies with `boundary_counts_signature_only`.
+    pub signature_sizes: bool,
+}
+
+/// A policy file: any subset of [`PruningParams`] fields, plus an optional `exclude` list of
+/// path patterns applied when the graph is built (see [`LoadOptions::exclude`]).
+#[derive(Debug, Clone)]
+pub struct PolicyFile {
+    pub params: PruningParams,
+    pub exclude: Vec<PathGlob>,
+}
+
+impl PolicyFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read policy file: {}", path.display()))?;
+        // `.toml` files are TOML; anything else is JSON. Both are read into the same value so
+        // `exclude` and the pruning params are split out the same way.
+        let is_toml = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+        let parsed = if is_toml {
+            toml::from_str::<serde_json::Value>(&content).map_err(anyhow::Error::from)
+        } else {
+            serde_json::from_str::<serde_json::Value>(&content).map_err(anyhow::Error::from)
+        };
+        let mut value =
+            parsed.with_context(|| format!("Failed to parse policy file: {}", path.display()))?;
+        let exclude = match value.as_object_mut().and_then(|obj| obj.remove("exclude")) {
+            Some(exclude) => serde_json::from_value(exclude).with_context(|| {
+                format!(
+                    "Invalid exclude patterns in policy file: {}",
+                    path.display()
+                )
+            })?,
+            None => Vec::new(),
+        };
+        let params = serde_json::from_value(value)
+            .with_context(|| format!("Failed to parse policy file: {}", path.display()))?;
+        Ok(Self { params, exclude })
+    }
+}
+
+/// Documentation scorer used for boundary decisions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DocScorerKind {
+    /// Length and keyword heuristics ([`HeuristicDocScorer`]).
+    #[default]
+    Heuristic,
+    /// Coverage of summary, parameters and return value ([`CoverageDocScorer`]).
+    Coverage,
+}
+
+/// Size function used to measure node context sizes.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SizeFunctionKind {
+    /// Approximate token count (the CF unit everywhere else).
+    #[default]
+    Tiktoken,
+    /// Non-blank code lines: much faster, for approximate CF of very large codebases.
+    Lines,
+    /// Code characters divided by a tokenizer-specific ratio, for non-tiktoken models.
+    Chars { chars_per_token: f32 },
+}
+
 struct EngineData {
     semantic_path: PathBuf,
     project_root: PathBuf,
@@ -29,6 +139,72 @@ struct EngineData {
     node_id_to_index: HashMap<NodeId, NodeIndex>,
     node_id_to_symbol: HashMap<NodeId, String>,
     source_reader: Arc<dyn SourceReader>,
+    /// Custom pruning params (from a policy file) used instead of the `PolicyKind` presets.
+    policy_override: Option<PruningParams>,
+    /// Extra `exclude_edge_targets` patterns added to whichever params are in effect.
+    excluded_edge_targets: Vec<Regex>,
+    load_options: LoadOptions,
+    /// Memoized CF totals per (node, policy). CF total is deterministic for a fixed graph and
+    /// params, so entries stay valid until a reload or a change of the effective params.
+    /// Holds one entry per node and policy once warm, so it costs about as much memory as the
+    /// node maps, but repeated `stats`/`top`/`search` calls become lookups.
+    cf_cache: RwLock<HashMap<(NodeId, PolicyKind), u32>>,
+}
+
+impl EngineData {
+    /// Solver over the current graph, with the test classifier for `exclude_test_callers`.
+    fn solver(&self, params: PruningParams) -> CfSolver {
+        CfSolver::new(self.graph.clone(), params)
+            .with_test_classifier(Arc::new(UniversalTestDetector::new()))
+    }
+
+    fn pruning_params(&self, kind: PolicyKind) -> PruningParams {
+        let mut params = self
+            .policy_override
+            .clone()
+            .unwrap_or_else(|| pruning_params(kind));
+        params
+            .exclude_edge_targets
+            .extend(self.excluded_edge_targets.iter().cloned());
+        params
+    }
+
+    /// CF totals of `nodes` (in order) under `policy`, served from the memo where present.
+    /// Missing totals are computed and stored, unless `solver` was cancelled part-way.
+    fn cached_cf_totals(
+        &self,
+        solver: &CfSolver,
+        policy: PolicyKind,
+        nodes: &[NodeIndex],
+    ) -> Vec<u32> {
+        let key = |node_idx: NodeIndex| (self.graph.node(node_idx).core().id, policy);
+        let mut totals = vec![0; nodes.len()];
+        let mut missing: Vec<(usize, NodeIndex)> = Vec::new();
+        {
+            let cache = self.cf_cache.read().unwrap();
+            for (pos, &node_idx) in nodes.iter().enumerate() {
+                match cache.get(&key(node_idx)) {
+                    Some(&cf) => totals[pos] = cf,
+                    None => missing.push((pos, node_idx)),
+                }
+            }
+        }
+        if missing.is_empty() {
+            return totals;
+        }
+
+        let missing_nodes: Vec<NodeIndex> = missing.iter().map(|&(_, idx)| idx).collect();
+        let computed = compute_cf_totals(solver, &missing_nodes);
+        let cancelled = solver.is_cancelled();
+        let mut cache = self.cf_cache.write().unwrap();
+        for ((pos, node_idx), cf) in missing.into_iter().zip(computed) {
+            totals[pos] = cf;
+            if !cancelled {
+                cache.insert(key(node_idx), cf);
+            }
+        }
+        totals
+    }
 }
 
 impl ContextEngine {
@@ -51,15 +227,38 @@ impl ContextEngine {
                 node_id_to_index,
                 node_id_to_symbol,
                 source_reader,
+                policy_override: None,
+                excluded_edge_targets: Vec::new(),
+                load_options: LoadOptions::default(),
+                cf_cache: RwLock::default(),
             })),
         }
     }
 
     pub fn load_from_json(json_path: &Path) -> Result<Self> {
+        Self::load_from_json_with_options(json_path, LoadOptions::default())
+    }
+
+    pub fn load_from_json_with_options(json_path: &Path, options: LoadOptions) -> Result<Self> {
+        Ok(Self {
+            inner: Arc::new(RwLock::new(Self::build_engine_data(json_path, options)?)),
+        })
+    }
+
+    /// Read SemanticData from `json_path`. A `.lsif` path is read as an LSIF dump instead of
+    /// SemanticData JSON; its conversion is cached next to the dump (see [`CachedDataSource`]).
+    pub fn load_semantic_data(json_path: &Path) -> Result<SemanticData> {
+        if json_path.extension().is_some_and(|ext| ext == "lsif") {
+            return CachedDataSource::new(LsifDataSourceAdapter::new(json_path), json_path).load();
+        }
         let json_content =
             std::fs::read_to_string(json_path).context("Failed to read JSON file")?;
-        let semantic_data: SemanticData =
-            serde_json::from_str(&json_content).context("Failed to parse SemanticData JSON")?;
+        serde_json::from_str(&json_content).context("Failed to parse SemanticData JSON")
+    }
+
+    /// Parse SemanticData from `json_path` and build the graph, without touching any engine.
+    fn build_engine_data(json_path: &Path, options: LoadOptions) -> Result<EngineData> {
+        let semantic_data = Self::load_semantic_data(json_path)?;
 
         let project_root = PathBuf::from(&semantic_data.project_root);
 
@@ -88,48 +287,107 @@ impl ContextEngine {
             }
         }
 
-        let source_reader: Arc<dyn SourceReader> = Arc::new(SimpleSourceReader {
-            project_root: semantic_data.project_root.clone(),
-        });
+        let source_reader: Arc<dyn SourceReader> = if options.no_source {
+            Arc::new(NullSourceReader)
+        } else {
+            Arc::new(SimpleSourceReader {
+                project_root: semantic_data.project_root.clone(),
+            })
+        };
+        #[cfg(feature = "archive")]
+        let source_reader: Arc<dyn SourceReader> = match &options.source_archive {
+            Some(archive) => Arc::new(ArchiveSourceReader::open(archive, &project_root)?),
+            None => source_reader,
+        };
 
-        let size_function = Box::new(TiktokenSizeFunction::new());
-        let doc_scorer = Box::new(HeuristicDocScorer);
-        let builder = GraphBuilder::new(size_function, doc_scorer);
+        let (mut size_function, variant): (Box<dyn SizeFunction>, String) =
+            match options.size_function {
+                SizeFunctionKind::Tiktoken => (
+                    Box::new(
+                        TiktokenSizeFunction::new().with_comment_stripping(options.strip_comments),
+                    ),
+                    format!("tiktoken:strip_comments={}", options.strip_comments),
+                ),
+                SizeFunctionKind::Lines => {
+                    (Box::new(LineCountSizeFunction::new()), "lines".to_string())
+                }
+                SizeFunctionKind::Chars { chars_per_token } => (
+                    Box::new(CharSizeFunction::new(chars_per_token)),
+                    format!("chars:chars_per_token={chars_per_token}"),
+                ),
+            };
+        let token_cache = match &options.token_cache {
+            Some(path) => {
+                let cache = Arc::new(Mutex::new(TokenCountCache::load(path, &variant)?));
+                size_function = Box::new(CachedSizeFunction::new(size_function, cache.clone()));
+                Some((path, cache))
+            }
+            None => None,
+        };
+        let doc_scorer: Box<dyn DocumentationScorer> = match options.doc_scorer {
+            DocScorerKind::Heuristic => Box::new(HeuristicDocScorer),
+            DocScorerKind::Coverage => Box::new(CoverageDocScorer),
+        };
+        let mut builder = GraphBuilder::new(size_function, doc_scorer)
+            .with_config_constant_inference(options.infer_config_constants)
+            .with_exclude(options.exclude.clone())
+            .with_missing_source_fatal(!options.allow_missing_sources)
+            .with_param_type_edges(options.include_param_types)
+            .with_symbol_version_normalization(options.normalize_symbol_versions)
+            .with_signature_sizes(options.signature_sizes);
+        if let Some(max_tokens) = options.external_max_tokens {
+            builder = builder.with_external_max_tokens(max_tokens);
+        }
+        if let Some(len) = options.external_signature_len {
+            builder = builder.with_external_signature_len(len);
+        }
 
         let graph = builder
             .build(semantic_data, source_reader.as_ref())
             .context("Failed to build context graph")?;
+        for warning in &graph.warnings {
+            tracing::warn!("{warning}");
+        }
+        if let Some((path, cache)) = token_cache
+            && let Err(err) = cache.lock().unwrap().save(path)
+        {
+            tracing::warn!("{err:#}");
+        }
 
         let (node_id_to_index, node_id_to_symbol) = build_node_maps(&graph);
 
-        Ok(Self {
-            inner: Arc::new(RwLock::new(EngineData {
-                semantic_path: json_path.to_path_buf(),
-                project_root,
-                project_root_override: None,
-                graph: Arc::new(graph),
-                node_id_to_index,
-                node_id_to_symbol,
-                source_reader,
-            })),
+        Ok(EngineData {
+            semantic_path: json_path.to_path_buf(),
+            project_root,
+            project_root_override: None,
+            graph: Arc::new(graph),
+            node_id_to_index,
+            node_id_to_symbol,
+            source_reader,
+            policy_override: None,
+            excluded_edge_targets: Vec::new(),
+            load_options: options,
+            cf_cache: RwLock::default(),
         })
     }
 
+    /// Rebuild the graph from the SemanticData file. The new data is built fully before the
+    /// write lock is taken, so readers are only blocked for the swap itself.
     pub fn reload(&self) -> Result<HealthResponse> {
-        let path = {
+        let (path, options) = {
             let data = self.inner.read().unwrap();
-            data.semantic_path.clone()
+            (data.semantic_path.clone(), data.load_options.clone())
         };
-        let new_engine = Self::load_from_json(&path)?;
-        let new_data = new_engine.inner.read().unwrap();
+        let new_data = Self::build_engine_data(&path, options)?;
 
         let mut data = self.inner.write().unwrap();
-        data.project_root = new_data.project_root.clone();
-        data.project_root_override = new_data.project_root_override.clone();
-        data.graph = new_data.graph.clone();
-        data.node_id_to_index = new_data.node_id_to_index.clone();
-        data.node_id_to_symbol = new_data.node_id_to_symbol.clone();
-        data.source_reader = new_data.source_reader.clone();
+        data.project_root = new_data.project_root;
+        data.project_root_override = new_data.project_root_override;
+        data.graph = new_data.graph;
+        data.node_id_to_index = new_data.node_id_to_index;
+        data.node_id_to_symbol = new_data.node_id_to_symbol;
+        data.source_reader = new_data.source_reader;
+        data.cf_cache = new_data.cf_cache;
 
         Ok(HealthResponse {
             semantic_path: data.semantic_path.to_string_lossy().to_string(),
@@ -139,6 +397,43 @@ impl ContextEngine {
         })
     }
 
+    /// Pruning params of a built-in policy preset.
+    pub fn policy_preset(kind: PolicyKind) -> PruningParams {
+        pruning_params(kind)
+    }
+
+    /// Load a policy file: a JSON object (or a `.toml` table) with any subset of
+    /// [`PruningParams`] fields.
+    /// Its build-time `exclude` list is ignored here; see [`PolicyFile`].
+    pub fn load_policy_file(path: &Path) -> Result<PruningParams> {
+        Ok(PolicyFile::load(path)?.params)
+    }
+
+    /// Use `params` for every computation regardless of the requested `PolicyKind`;
+    /// `None` restores the presets.
+    pub fn set_policy_override(&self, params: Option<PruningParams>) {
+        let mut data = self.inner.write().unwrap();
+        data.policy_override = params;
+        data.cf_cache.get_mut().unwrap().clear();
+    }
+
+    /// Ignore edges whose target symbol matches any of `patterns` (e.g. `logger\.`), on top of
+    /// the policy's own `exclude_edge_targets`.
+    pub fn set_excluded_edge_targets(&self, patterns: Vec<Regex>) {
+        let mut data = self.inner.write().unwrap();
+        data.excluded_edge_targets = patterns;
+        data.cf_cache.get_mut().unwrap().clear();
+    }
+
+    /// Compute and memoize the CF total of every node under `policy` (in parallel with the
+    /// `parallel` feature), so later `stats`/`top`/`search` calls only look totals up.
+    pub fn warm_cache(&self, policy: PolicyKind) {
+        let data = self.inner.read().unwrap();
+        let solver = data.solver(data.pruning_params(policy));
+        let nodes: Vec<NodeIndex> = data.graph.graph.node_indices().collect();
+        data.cached_cf_totals(&solver, policy, &nodes);
+    }
+
     pub fn health(&self) -> HealthResponse {
         let data = self.inner.read().unwrap();
         HealthResponse {
@@ -149,13 +444,70 @@ impl ContextEngine {
         }
     }
 
+    /// [`health`](Self::health) plus a source read for up to `sample_size` project nodes
+    /// (one per file, spread across the sorted file list).
+    pub fn deep_health(&self, sample_size: usize) -> DeepHealthResponse {
+        let health = self.health();
+        let data = self.inner.read().unwrap();
+        let graph = data.graph.as_ref();
+
+        let mut first_node_per_file: HashMap<&str, NodeIndex> = HashMap::new();
+        for idx in graph.graph.node_indices() {
+            let core = graph.node(idx).core();
+            if !core.is_external && core.context_size > 0 {
+                first_node_per_file
+                    .entry(core.file_path.as_str())
+                    .or_insert(idx);
+            }
+        }
+        let mut files: Vec<&str> = first_node_per_file.keys().copied().collect();
+        files.sort_unstable();
+        let step = files.len().div_ceil(sample_size.max(1)).max(1);
+
+        let mut sampled_nodes = 0;
+        let mut failures = Vec::new();
+        for file in files.into_iter().step_by(step) {
+            let core = graph.node(first_node_per_file[file]).core();
+            let full_path = data.project_root.join(&core.file_path);
+            sampled_nodes += 1;
+            if let Err(e) = data.source_reader.read_lines(
+                &full_path.to_string_lossy(),
+                core.span.start_line as usize,
+                core.span.end_line as usize,
+            ) {
+                failures.push(SourceReadFailure {
+                    file_path: core.file_path.clone(),
+                    error: format!("{e:#}"),
+                });
+            }
+        }
+
+        DeepHealthResponse {
+            health,
+            sampled_nodes,
+            readable_nodes: sampled_nodes - failures.len(),
+            failures,
+        }
+    }
+
     pub fn compute(&self, req: ComputeRequest) -> Result<ComputeResponse> {
+        self.compute_cancellable(req, Arc::new(AtomicBool::new(false)))
+    }
+
+    /// [`compute`](Self::compute) that gives up with an error once `cancel` is set
+    /// (e.g. the requesting client went away).
+    pub fn compute_cancellable(
+        &self,
+        req: ComputeRequest,
+        cancel: Arc<AtomicBool>,
+    ) -> Result<ComputeResponse> {
         let data = self.inner.read().unwrap();
         let graph = data.graph.as_ref();
 
         let mut starts = Vec::with_capacity(req.symbols.len());
         let mut resolutions = Vec::with_capacity(req.symbols.len());
         let mut effective_symbols = Vec::new();
+        let mut missing = Vec::new();
 
         for sym in &req.symbols {
             let resolution = self.resolve_anchor_locked(&data, sym);
@@ -170,7 +522,7 @@ impl ContextEngine {
                 }
                 None => {
                     if resolution.unresolved_reason.is_some() {
-                        return Err(anyhow!("Symbol not found: {}", sym));
+                        missing.push(sym.as_str());
                     }
                     if let Some(idx) = graph.get_node_by_symbol(sym) {
                         starts.push(idx);
@@ -180,9 +532,37 @@ impl ContextEngine {
             }
             resolutions.push(resolution);
         }
+        if !missing.is_empty() {
+            return Err(anyhow!("Symbol not found: {}", missing.join(", ")));
+        }
 
-        let solver = CfSolver::new(data.graph.clone(), pruning_params(req.policy));
-        let result = solver.compute_cf(&starts, req.max_tokens);
+        let mut params = data.pruning_params(req.policy);
+        params.max_depth = req.max_depth.or(params.max_depth);
+        if let Some(threshold) = req.func_doc_threshold {
+            params.func_doc_threshold = threshold;
+        }
+        if let Some(threshold) = req.interface_doc_threshold {
+            params.interface_doc_threshold = threshold;
+        }
+        let solver = data.solver(params).with_cancellation(cancel);
+        let result = if req.already_read.is_empty() {
+            solver.compute_cf(&starts, req.max_tokens)
+        } else {
+            let already_read = req
+                .already_read
+                .iter()
+                .map(|sym| {
+                    graph
+                        .get_node_by_symbol(sym)
+                        .map(|idx| graph.node(idx).core().id)
+                        .ok_or_else(|| anyhow!("Symbol not found: {}", sym))
+                })
+                .collect::<Result<HashSet<NodeId>>>()?;
+            solver.compute_cf_excluding(&starts, &already_read, req.max_tokens)
+        };
+        if result.cancelled {
+            return Err(anyhow!("Computation cancelled"));
+        }
 
         let reachable_nodes_ordered = result
             .reachable_nodes_ordered
@@ -201,12 +581,23 @@ impl ContextEngine {
             })
             .collect::<Vec<_>>();
 
+        let files_touched = reachable_nodes_ordered
+            .iter()
+            .map(|node| node.file_path.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
         Ok(ComputeResponse {
             starting_symbols: effective_symbols,
             total_context_size: result.total_context_size,
+            weighted_context_size: result.weighted_context_size,
+            unique_source_lines: result.unique_source_lines,
             reachable_node_count: result.reachable_set.len(),
+            boundary_count: result.boundary_count(),
             reachable_nodes_by_layer,
             reachable_nodes_ordered,
+            files_touched,
             anchor_resolutions: Some(resolutions),
         })
     }
@@ -235,7 +626,7 @@ impl ContextEngine {
             }
         }
 
-        let solver = CfSolver::new(data.graph.clone(), pruning_params(req.policy));
+        let solver = data.solver(data.pruning_params(req.policy));
         let result = solver.reachable(
             &start_indices,
             &resolved_targets
@@ -277,61 +668,359 @@ impl ContextEngine {
         })
     }
 
-    pub fn stats(&self, include_tests: bool, policy: PolicyKind) -> Result<StatsResponse> {
+    /// Shortest chain of hops by which traversal from `from` reaches `to`, with the pruning
+    /// decision at each step.
+    pub fn path(&self, from: &str, to: &str, policy: PolicyKind) -> Result<PathResponse> {
         let data = self.inner.read().unwrap();
         let graph = data.graph.as_ref();
-        let solver = CfSolver::new(data.graph.clone(), pruning_params(policy));
-        let test_detector = UniversalTestDetector::new();
+        let start = graph
+            .get_node_by_symbol(from)
+            .ok_or_else(|| anyhow!("Symbol not found: {}", from))?;
+        let target = graph
+            .get_node_by_symbol(to)
+            .ok_or_else(|| anyhow!("Symbol not found: {}", to))?;
+
+        let solver = data.solver(data.pruning_params(policy));
+        let steps = solver.path_to(start, target);
+        let symbol = |id: NodeId| data.node_id_to_symbol.get(&id).cloned().unwrap_or_default();
+        let hops = steps
+            .iter()
+            .flatten()
+            .filter_map(|step| {
+                let parent = symbol(step.parent?);
+                let node = symbol(step.node_id);
+                let (source, target, edge_kind, reached_via) = match &step.reached_via {
+                    ReachedVia::Start => return None,
+                    ReachedVia::Forward(ek) => (parent, node, edge_kind_display(ek), "Forward"),
+                    ReachedVia::CallIn => (node, parent, "Call", "CallIn"),
+                    ReachedVia::SharedStateWrite => (node, parent, "Write", "SharedStateWrite"),
+                };
+                Some(PathHop {
+                    source,
+                    target,
+                    edge_kind: edge_kind.to_string(),
+                    reached_via: reached_via.to_string(),
+                    decision: step
+                        .decision
+                        .as_ref()
+                        .map(decision_display)
+                        .map(String::from),
+                    boundary_reason: match step.decision {
+                        Some(PruningDecision::Boundary(reason)) => {
+                            Some(reason.as_str().to_string())
+                        }
+                        _ => None,
+                    },
+                })
+            })
+            .collect();
 
-        let mut function_cf: Vec<u32> = Vec::new();
+        Ok(PathResponse {
+            from: from.to_string(),
+            to: to.to_string(),
+            reachable: steps.is_some(),
+            hops,
+        })
+    }
 
-        for node_idx in graph.graph.node_indices() {
-            let node = graph.node(node_idx);
+    pub fn stats(
+        &self,
+        include_tests: bool,
+        include_variables: bool,
+        policy: PolicyKind,
+    ) -> Result<StatsResponse> {
+        self.stats_cancellable(
+            include_tests,
+            include_variables,
+            policy,
+            None,
+            Arc::new(AtomicBool::new(false)),
+        )
+    }
 
-            // Only count function nodes
-            if !matches!(node, Node::Function(_)) {
-                continue;
-            }
+    /// Like [`Self::stats`], reporting the given percentiles instead of 5% steps.
+    pub fn stats_at_percentiles(
+        &self,
+        include_tests: bool,
+        include_variables: bool,
+        policy: PolicyKind,
+        percentiles: &[u32],
+    ) -> Result<StatsResponse> {
+        self.stats_cancellable(
+            include_tests,
+            include_variables,
+            policy,
+            Some(percentiles),
+            Arc::new(AtomicBool::new(false)),
+        )
+    }
 
-            if !include_tests {
+    /// [`stats`](Self::stats) that gives up with an error once `cancel` is set.
+    pub fn stats_cancellable(
+        &self,
+        include_tests: bool,
+        include_variables: bool,
+        policy: PolicyKind,
+        percentiles: Option<&[u32]>,
+        cancel: Arc<AtomicBool>,
+    ) -> Result<StatsResponse> {
+        let data = self.inner.read().unwrap();
+        let graph = data.graph.as_ref();
+        let params = data.pruning_params(policy);
+        let solver = data.solver(params.clone()).with_cancellation(cancel);
+        let test_detector = UniversalTestDetector::new();
+
+        // Functions always; variables (globals and fields) only on request
+        let nodes: Vec<NodeIndex> = graph
+            .graph
+            .node_indices()
+            .filter(|&node_idx| {
+                let node = graph.node(node_idx);
+                if matches!(node, Node::Variable(_)) && !include_variables {
+                    return false;
+                }
+                if include_tests {
+                    return true;
+                }
                 let symbol = data
                     .node_id_to_symbol
                     .get(&node.core().id)
                     .map(|s| s.as_str())
                     .unwrap_or("");
-                if test_detector.is_test_code(symbol, &node.core().file_path) {
+                !test_detector.is_test_code(symbol, &node.core().file_path)
+            })
+            .collect();
+
+        let cfs = data.cached_cf_totals(&solver, policy, &nodes);
+        if solver.is_cancelled() {
+            return Err(anyhow!("Computation cancelled"));
+        }
+
+        let mut function_cf: Vec<u32> = Vec::new();
+        let mut variable_cf: Vec<u32> = Vec::new();
+        let mut global_cf: Vec<u32> = Vec::new();
+        let mut field_cf: Vec<u32> = Vec::new();
+        let mut boundary_count = 0;
+        for (&node_idx, cf) in nodes.iter().zip(cfs) {
+            let node = graph.node(node_idx);
+            match node {
+                Node::Function(_) => {
+                    function_cf.push(cf);
+                    if is_function_boundary(&params, node, graph) {
+                        boundary_count += 1;
+                    }
+                }
+                Node::Variable(v) => {
+                    variable_cf.push(cf);
+                    match v.variable_kind {
+                        VariableKind::Global => global_cf.push(cf),
+                        VariableKind::ClassField => field_cf.push(cf),
+                        VariableKind::Local => {}
+                    }
+                }
+            }
+        }
+
+        let transparent_count = function_cf.len() - boundary_count;
+        Ok(StatsResponse {
+            functions: compute_distribution_at(function_cf, percentiles),
+            variables: include_variables.then(|| compute_distribution_at(variable_cf, percentiles)),
+            global_variables: include_variables
+                .then(|| compute_distribution_at(global_cf, percentiles)),
+            field_variables: include_variables
+                .then(|| compute_distribution_at(field_cf, percentiles)),
+            boundary_count,
+            transparent_count,
+        })
+    }
+
+    /// Composite fitness score from median function CF, the fraction of functions that are
+    /// boundaries, the largest call cycle, and the number of god objects.
+    pub fn fitness(
+        &self,
+        include_tests: bool,
+        policy: PolicyKind,
+        config: &FitnessConfig,
+    ) -> Result<FitnessResponse> {
+        let data = self.inner.read().unwrap();
+        let graph = data.graph.as_ref();
+        let params = data.pruning_params(policy);
+        let solver = data.solver(params.clone());
+        let test_detector = UniversalTestDetector::new();
+
+        let mut scratch = CfScratch::default();
+        let mut function_cf: Vec<u32> = Vec::new();
+        let mut boundaries = 0usize;
+        let mut members_per_type: HashMap<&str, usize> = HashMap::new();
+        for node_idx in graph.graph.node_indices() {
+            let node = graph.node(node_idx);
+            let core = node.core();
+            if core.is_external {
+                continue;
+            }
+            if !include_tests {
+                let symbol = graph.symbol_of(node_idx).unwrap_or("");
+                if test_detector.is_test_code(symbol, &core.file_path) {
                     continue;
                 }
             }
+            if let Some(scope) = core.scope.as_deref()
+                && graph.type_registry.contains(scope)
+            {
+                *members_per_type.entry(scope).or_default() += 1;
+            }
+            if let Node::Function(_) = node {
+                function_cf.push(solver.compute_cf_total_with_scratch(node_idx, &mut scratch));
+                if is_function_boundary(&params, node, graph) {
+                    boundaries += 1;
+                }
+            }
+        }
+
+        let median_cf = compute_distribution(function_cf.clone()).median as f32;
+        let boundary_ratio = if function_cf.is_empty() {
+            0.0
+        } else {
+            boundaries as f32 / function_cf.len() as f32
+        };
+        let largest_cycle = graph.call_cycles().first().map_or(1, Vec::len) as f32;
+        let god_objects = members_per_type
+            .values()
+            .filter(|&&count| count > config.god_object_min_members)
+            .count() as f32;
+
+        let factor = |name: &str, value: f32, score: f32, weight: f32| FitnessFactor {
+            name: name.to_string(),
+            value,
+            score,
+            weight,
+        };
+        let factors = vec![
+            factor(
+                "median_cf",
+                median_cf,
+                config.median_cf_scale / (config.median_cf_scale + median_cf),
+                config.median_cf_weight,
+            ),
+            factor(
+                "boundary_ratio",
+                boundary_ratio,
+                boundary_ratio,
+                config.boundary_ratio_weight,
+            ),
+            factor(
+                "largest_cycle",
+                largest_cycle,
+                config.cycle_size_scale / (config.cycle_size_scale + largest_cycle - 1.0),
+                config.largest_cycle_weight,
+            ),
+            factor(
+                "god_objects",
+                god_objects,
+                1.0 / (1.0 + god_objects),
+                config.god_object_weight,
+            ),
+        ];
+
+        let total_weight: f32 = factors.iter().map(|f| f.weight).sum();
+        let score = if total_weight > 0.0 {
+            100.0 * factors.iter().map(|f| f.score * f.weight).sum::<f32>() / total_weight
+        } else {
+            0.0
+        };
+        Ok(FitnessResponse { score, factors })
+    }
+
+    /// Union CF of each project file, starting from every (non-test) definition in it.
+    pub fn file_stats(&self, include_tests: bool, policy: PolicyKind) -> Result<FileStatsResponse> {
+        let data = self.inner.read().unwrap();
+        let graph = data.graph.as_ref();
+        let solver = data.solver(data.pruning_params(policy));
+        let test_detector = UniversalTestDetector::new();
 
-            let cf = solver.compute_cf_total(node_idx);
-            function_cf.push(cf);
+        let mut by_file: HashMap<&str, Vec<NodeIndex>> = HashMap::new();
+        for (symbol, &node_idx) in &graph.symbol_to_node {
+            let core = graph.node(node_idx).core();
+            if core.is_external {
+                continue;
+            }
+            if !include_tests && test_detector.is_test_code(symbol, &core.file_path) {
+                continue;
+            }
+            by_file.entry(&core.file_path).or_default().push(node_idx);
         }
 
-        Ok(StatsResponse {
-            functions: compute_distribution(function_cf),
+        let mut files: Vec<FileCfItem> = by_file
+            .into_iter()
+            .map(|(file_path, starts)| FileCfItem {
+                file_path: file_path.to_string(),
+                node_count: starts.len(),
+                cf: solver.compute_cf(&starts, None).total_context_size,
+            })
+            .collect();
+        files.sort_by(|a, b| b.cf.cmp(&a.cf).then_with(|| a.file_path.cmp(&b.file_path)));
+
+        Ok(FileStatsResponse {
+            distribution: compute_distribution(files.iter().map(|f| f.cf).collect()),
+            files,
         })
     }
 
+    /// CF of every (non-external) symbol, for [`compare_to_baseline`].
+    pub fn snapshot(&self, include_tests: bool, policy: PolicyKind) -> Result<CfSnapshot> {
+        let data = self.inner.read().unwrap();
+        let graph = data.graph.as_ref();
+        let solver = data.solver(data.pruning_params(policy));
+        let test_detector = UniversalTestDetector::new();
+        let mut scratch = CfScratch::default();
+
+        let mut snapshot = CfSnapshot::default();
+        for (symbol, &node_idx) in &graph.symbol_to_node {
+            let core = graph.node(node_idx).core();
+            if core.is_external {
+                continue;
+            }
+            if !include_tests && test_detector.is_test_code(symbol, &core.file_path) {
+                continue;
+            }
+            let cf = solver.compute_cf_total_with_scratch(node_idx, &mut scratch);
+            snapshot.symbols.insert(symbol.clone(), cf);
+        }
+        Ok(snapshot)
+    }
+
+    /// `filter`, when set, is a regex matched against the full symbol to restrict what is ranked;
+    /// `min_cf` drops nodes below that many tokens before `limit` is applied.
     pub fn top(
         &self,
         limit: usize,
         node_type: &str,
+        filter: Option<&str>,
+        min_cf: Option<u32>,
         include_tests: bool,
         policy: PolicyKind,
     ) -> Result<TopResponse> {
+        let filter = filter.map(symbol_regex).transpose()?;
         let data = self.inner.read().unwrap();
         let graph = data.graph.as_ref();
-        let solver = CfSolver::new(data.graph.clone(), pruning_params(policy));
+        let solver = data.solver(data.pruning_params(policy));
         let test_detector = UniversalTestDetector::new();
 
-        let mut results: Vec<TopItem> = Vec::new();
+        let mut candidates: Vec<(&String, NodeIndex)> = Vec::new();
         for (symbol, &node_idx) in &graph.symbol_to_node {
             let node = graph.node(node_idx);
 
             let type_str = detailed_node_type_str(node);
             let base_type = node_type_str(node);
-            if node_type != "all" && node_type != type_str && node_type != base_type {
+            // Comma-separated filter, e.g. "function,variable"
+            if !node_type
+                .split(',')
+                .map(str::trim)
+                .any(|t| t == "all" || t == type_str || t == base_type)
+            {
+                continue;
+            }
+            if filter.as_ref().is_some_and(|re| !re.is_match(symbol)) {
                 continue;
             }
 
@@ -339,56 +1028,566 @@ impl ContextEngine {
                 continue;
             }
 
-            let cf = solver.compute_cf_total(node_idx);
-            results.push(TopItem {
+            candidates.push((symbol, node_idx));
+        }
+
+        let nodes: Vec<NodeIndex> = candidates.iter().map(|&(_, idx)| idx).collect();
+        let cfs = data.cached_cf_totals(&solver, policy, &nodes);
+        let mut results: Vec<TopItem> = candidates
+            .into_iter()
+            .zip(cfs)
+            .filter(|&(_, cf)| min_cf.is_none_or(|min| cf >= min))
+            .map(|((symbol, node_idx), cf)| TopItem {
                 symbol: symbol.clone(),
-                node_type: type_str.to_string(),
+                node_type: detailed_node_type_str(graph.node(node_idx)).to_string(),
                 cf,
-            });
-        }
+            })
+            .collect();
 
-        results.sort_by(|a, b| b.cf.cmp(&a.cf));
+        // Ties broken by symbol so the order does not depend on map or thread scheduling.
+        results.sort_by(|a, b| b.cf.cmp(&a.cf).then_with(|| a.symbol.cmp(&b.symbol)));
         results.truncate(limit);
         Ok(TopResponse { items: results })
     }
 
-    pub fn search(
+    /// Functions whose CF is above the `percentile` cutoff of the function CF distribution
+    /// (the 80/20 view): how few functions carry how much of the summed CF.
+    pub fn pareto(
         &self,
-        pattern: &str,
-        with_cf: bool,
-        limit: Option<usize>,
+        percentile: u32,
         include_tests: bool,
         policy: PolicyKind,
-    ) -> Result<SearchResponse> {
+    ) -> Result<ParetoResponse> {
         let data = self.inner.read().unwrap();
         let graph = data.graph.as_ref();
-        let solver = CfSolver::new(data.graph.clone(), pruning_params(policy));
+        let solver = data.solver(data.pruning_params(policy));
         let test_detector = UniversalTestDetector::new();
 
-        let pattern_lower = pattern.to_lowercase();
-        let mut matches: Vec<(String, String, u32)> = Vec::new();
-
-        for (symbol, &node_idx) in &graph.symbol_to_node {
-            if !symbol.to_lowercase().contains(&pattern_lower) {
-                continue;
-            }
-
-            let node = graph.node(node_idx);
-            let type_str = detailed_node_type_str(node).to_string();
+        let functions: Vec<(&String, NodeIndex)> = graph
+            .symbol_to_node
+            .iter()
+            .filter(|&(symbol, &node_idx)| {
+                let node = graph.node(node_idx);
+                matches!(node, Node::Function(_))
+                    && (include_tests
+                        || !test_detector.is_test_code(symbol, &node.core().file_path))
+            })
+            .map(|(symbol, &node_idx)| (symbol, node_idx))
+            .collect();
+        let nodes: Vec<NodeIndex> = functions.iter().map(|&(_, idx)| idx).collect();
+        let cfs = data.cached_cf_totals(&solver, policy, &nodes);
 
-            if !include_tests && test_detector.is_test_code(symbol, &node.core().file_path) {
-                continue;
-            }
+        let distribution = compute_distribution(cfs.clone());
+        let cutoff_cf = if distribution.count == 0 {
+            0
+        } else {
+            distribution
+                .percentiles
+                .iter()
+                .find(|p| p.percentile == percentile)
+                .map(|p| p.tokens)
+                .ok_or_else(|| {
+                    anyhow!("Percentile must be a multiple of 5 between 5 and 100: {percentile}")
+                })?
+        };
 
-            // Always compute CF for sorting (same as current CLI behavior).
-            let cf = solver.compute_cf_total(node_idx);
-            matches.push((symbol.clone(), type_str, cf));
-        }
+        let total_cf: u64 = cfs.iter().map(|&cf| cf as u64).sum();
+        let mut items: Vec<TopItem> = functions
+            .into_iter()
+            .zip(cfs)
+            .filter(|&(_, cf)| cf > cutoff_cf)
+            .map(|((symbol, node_idx), cf)| TopItem {
+                symbol: symbol.clone(),
+                node_type: detailed_node_type_str(graph.node(node_idx)).to_string(),
+                cf,
+            })
+            .collect();
+        items.sort_by(|a, b| b.cf.cmp(&a.cf).then_with(|| a.symbol.cmp(&b.symbol)));
+
+        let above_cf: u64 = items.iter().map(|item| item.cf as u64).sum();
+        Ok(ParetoResponse {
+            percentile,
+            cutoff_cf,
+            function_count: distribution.count,
+            total_cf,
+            share_of_total: if total_cf == 0 {
+                0.0
+            } else {
+                above_cf as f64 / total_cf as f64
+            },
+            items,
+        })
+    }
 
-        // Also search for class symbols in TypeRegistry
-        let type_ids: Vec<_> = graph.type_registry.type_ids().cloned().collect();
+    /// Transpose of all CF computations: for every project node, in how many other nodes'
+    /// reachable sets it appears. With `include_tests` false, test code is neither a start
+    /// node nor listed.
+    pub fn ubiquity(
+        &self,
+        limit: usize,
+        include_tests: bool,
+        policy: PolicyKind,
+    ) -> Result<UbiquityResponse> {
+        let data = self.inner.read().unwrap();
+        let graph = data.graph.as_ref();
+        let solver = data.solver(data.pruning_params(policy));
+        let test_detector = UniversalTestDetector::new();
+        let is_test = |idx: NodeIndex| {
+            !include_tests
+                && test_detector.is_test_code(
+                    graph.symbol_of(idx).unwrap_or(""),
+                    &graph.node(idx).core().file_path,
+                )
+        };
+
+        let mut counts: HashMap<NodeId, usize> = HashMap::new();
+        let mut start_count = 0;
+        for idx in graph.graph.node_indices() {
+            if graph.node(idx).core().is_external || is_test(idx) {
+                continue;
+            }
+            start_count += 1;
+            let start_id = graph.node(idx).core().id;
+            for &id in &solver.compute_cf(&[idx], None).reachable_set {
+                if id != start_id {
+                    *counts.entry(id).or_default() += 1;
+                }
+            }
+        }
+
+        let mut items: Vec<UbiquityItem> = counts
+            .into_iter()
+            .filter_map(|(id, reachable_set_count)| {
+                let idx = *data.node_id_to_index.get(&id)?;
+                if is_test(idx) {
+                    return None;
+                }
+                Some(UbiquityItem {
+                    symbol: graph.symbol_of(idx)?.to_string(),
+                    node_type: detailed_node_type_str(graph.node(idx)).to_string(),
+                    reachable_set_count,
+                })
+            })
+            .collect();
+        items.sort_by(|a, b| {
+            b.reachable_set_count
+                .cmp(&a.reachable_set_count)
+                .then_with(|| a.symbol.cmp(&b.symbol))
+        });
+        items.truncate(limit);
+        Ok(UbiquityResponse { start_count, items })
+    }
+
+    /// Nodes reachable from every symbol matching `entrypoints`, following all outgoing edges
+    /// regardless of pruning (a boundary still executes what it calls). Modules (source files)
+    /// whose unreachable ratio is at least `dead_threshold` are flagged as likely dead.
+    pub fn coverage(
+        &self,
+        entrypoints: &Regex,
+        include_tests: bool,
+        dead_threshold: f64,
+    ) -> Result<CoverageResponse> {
+        let data = self.inner.read().unwrap();
+        let graph = data.graph.as_ref();
+        let test_detector = UniversalTestDetector::new();
+
+        let mut entry_points: Vec<(&String, NodeIndex)> = graph
+            .symbol_to_node
+            .iter()
+            .filter(|(symbol, idx)| {
+                !graph.node(**idx).core().is_external && entrypoints.is_match(symbol)
+            })
+            .map(|(symbol, &idx)| (symbol, idx))
+            .collect();
+        if entry_points.is_empty() {
+            return Err(anyhow!(
+                "No symbol matches entry point pattern: {entrypoints}"
+            ));
+        }
+        entry_points.sort();
+
+        let mut reachable: HashSet<NodeIndex> = entry_points.iter().map(|&(_, idx)| idx).collect();
+        let mut queue: Vec<NodeIndex> = reachable.iter().copied().collect();
+        while let Some(idx) = queue.pop() {
+            for next in graph.graph.neighbors(idx) {
+                if reachable.insert(next) {
+                    queue.push(next);
+                }
+            }
+        }
+
+        let mut by_module: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+        for (symbol, &idx) in &graph.symbol_to_node {
+            let core = graph.node(idx).core();
+            if core.is_external
+                || (!include_tests && test_detector.is_test_code(symbol, &core.file_path))
+            {
+                continue;
+            }
+            let (total, hit) = by_module.entry(core.file_path.as_str()).or_default();
+            *total += 1;
+            if reachable.contains(&idx) {
+                *hit += 1;
+            }
+        }
+
+        let total_nodes: usize = by_module.values().map(|(total, _)| total).sum();
+        let reachable_nodes: usize = by_module.values().map(|(_, hit)| hit).sum();
+        let mut modules: Vec<ModuleCoverage> = by_module
+            .into_iter()
+            .map(|(module, (total, hit))| {
+                let unreachable_ratio = (total - hit) as f64 / total as f64;
+                ModuleCoverage {
+                    module: module.to_string(),
+                    total_nodes: total,
+                    reachable_nodes: hit,
+                    unreachable_ratio,
+                    likely_dead: unreachable_ratio >= dead_threshold,
+                }
+            })
+            .collect();
+        modules.sort_by(|a, b| b.unreachable_ratio.total_cmp(&a.unreachable_ratio));
+
+        Ok(CoverageResponse {
+            entry_points: entry_points.into_iter().map(|(s, _)| s.clone()).collect(),
+            total_nodes,
+            reachable_nodes,
+            coverage_percent: if total_nodes == 0 {
+                0.0
+            } else {
+                reachable_nodes as f64 * 100.0 / total_nodes as f64
+            },
+            modules,
+        })
+    }
+
+    /// Pairwise reachability between `symbols`: entry (i, j) is set when symbol j is in
+    /// symbol i's CF reachable set. Weighted entries carry j's context_size instead of 1.
+    pub fn matrix(
+        &self,
+        symbols: &[String],
+        weighted: bool,
+        policy: PolicyKind,
+    ) -> Result<MatrixResponse> {
+        let mut rows = Vec::with_capacity(symbols.len());
+        self.matrix_rows(symbols, weighted, policy, |_, row| {
+            rows.push(row);
+            Ok(())
+        })?;
+        Ok(MatrixResponse {
+            symbols: symbols.to_vec(),
+            rows,
+        })
+    }
+
+    /// [`matrix`](Self::matrix) one row at a time: `on_row` receives each start symbol and its
+    /// row as soon as it is computed, so only one row is held in memory. All symbols are
+    /// resolved before the first row is produced.
+    pub fn matrix_rows(
+        &self,
+        symbols: &[String],
+        weighted: bool,
+        policy: PolicyKind,
+        mut on_row: impl FnMut(&str, Vec<u32>) -> Result<()>,
+    ) -> Result<()> {
+        let data = self.inner.read().unwrap();
+        let graph = data.graph.as_ref();
+        let solver = data.solver(data.pruning_params(policy));
+
+        let indices = symbols
+            .iter()
+            .map(|sym| {
+                graph
+                    .get_node_by_symbol(sym)
+                    .ok_or_else(|| anyhow!("Symbol not found: {}", sym))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for (symbol, &start) in symbols.iter().zip(&indices) {
+            let result = solver.compute_cf(&[start], None);
+            let row = indices
+                .iter()
+                .map(|&target| {
+                    let core = graph.node(target).core();
+                    match (result.reachable_set.contains(&core.id), weighted) {
+                        (false, _) => 0,
+                        (true, false) => 1,
+                        (true, true) => core.context_size,
+                    }
+                })
+                .collect();
+            on_row(symbol, row)?;
+        }
+        Ok(())
+    }
+
+    /// Partition the reachable sets of `set_a` and `set_b` (each the union over its members)
+    /// into A-only, B-only and shared nodes, e.g. to see what context is unique to each half
+    /// of a module split.
+    pub fn set_diff(
+        &self,
+        set_a: &[String],
+        set_b: &[String],
+        policy: PolicyKind,
+    ) -> Result<SetDiffResponse> {
+        let data = self.inner.read().unwrap();
+        let graph = data.graph.as_ref();
+        let solver = data.solver(data.pruning_params(policy));
+
+        let starts = |symbols: &[String]| {
+            symbols
+                .iter()
+                .map(|sym| {
+                    graph
+                        .get_node_by_symbol(sym)
+                        .ok_or_else(|| anyhow!("Symbol not found: {}", sym))
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+        let result_a = solver.compute_cf(&starts(set_a)?, None);
+        let result_b = solver.compute_cf(&starts(set_b)?, None);
+
+        let part = |ids: Vec<&NodeId>| {
+            let mut symbols: Vec<String> = ids
+                .iter()
+                .filter_map(|&id| data.node_id_to_symbol.get(id).cloned())
+                .collect();
+            symbols.sort();
+            SetDiffPart {
+                node_count: ids.len(),
+                context_size: ids
+                    .iter()
+                    .filter_map(|&id| data.node_id_to_index.get(id))
+                    .map(|&idx| graph.node(idx).core().context_size)
+                    .sum(),
+                symbols,
+            }
+        };
+        let reach_a = &result_a.reachable_set;
+        let reach_b = &result_b.reachable_set;
+        Ok(SetDiffResponse {
+            cf_a: result_a.total_context_size,
+            cf_b: result_b.total_context_size,
+            only_a: part(reach_a.difference(reach_b).collect()),
+            only_b: part(reach_b.difference(reach_a).collect()),
+            shared: part(reach_a.intersection(reach_b).collect()),
+        })
+    }
+
+    /// Call cycles and self-recursive functions (see [`ContextGraph::call_cycles`]).
+    pub fn cycles(&self) -> CyclesResponse {
+        let data = self.inner.read().unwrap();
+        let graph = data.graph.as_ref();
+        let symbol = |idx: NodeIndex| graph.symbol_of(idx).unwrap_or_default().to_string();
+
+        let cycles = graph
+            .call_cycles()
+            .into_iter()
+            .map(|members| {
+                let mut symbols: Vec<String> = members.iter().map(|&idx| symbol(idx)).collect();
+                symbols.sort();
+                CallCycle {
+                    symbols,
+                    context_size: members
+                        .iter()
+                        .map(|&idx| graph.node(idx).core().context_size)
+                        .sum(),
+                }
+            })
+            .collect();
+        let mut self_calls: Vec<String> = graph.self_calls().into_iter().map(symbol).collect();
+        self_calls.sort();
+
+        CyclesResponse { cycles, self_calls }
+    }
+
+    /// Summarize the calls left unresolved by the builder: counts per file and the `limit`
+    /// most frequent callees.
+    pub fn unresolved(&self, limit: usize) -> UnresolvedResponse {
+        let data = self.inner.read().unwrap();
+        let unresolved = &data.graph.unresolved;
+
+        let mut by_file: HashMap<&str, usize> = HashMap::new();
+        let mut by_callee: HashMap<&str, (usize, Option<&str>)> = HashMap::new();
+        for reference in unresolved {
+            *by_file.entry(reference.file_path.as_str()).or_default() += 1;
+            let entry = by_callee.entry(reference.callee()).or_default();
+            entry.0 += 1;
+            if entry.1.is_none() {
+                entry.1 = reference.receiver.as_deref();
+            }
+        }
+
+        let ranked = |mut counts: Vec<UnresolvedCount>| {
+            counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+            counts.truncate(limit);
+            counts
+        };
+        UnresolvedResponse {
+            total: unresolved.len(),
+            by_file: ranked(
+                by_file
+                    .into_iter()
+                    .map(|(name, count)| UnresolvedCount {
+                        name: name.to_string(),
+                        count,
+                        example_receiver: None,
+                    })
+                    .collect(),
+            ),
+            top_callees: ranked(
+                by_callee
+                    .into_iter()
+                    .map(|(name, (count, receiver))| UnresolvedCount {
+                        name: name.to_string(),
+                        count,
+                        example_receiver: receiver.map(String::from),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Find functions with identical bodies after normalization (see [`normalize_source`]).
+    /// Functions smaller than `min_size` tokens, external symbols and unreadable sources are skipped.
+    pub fn duplicates(
+        &self,
+        min_size: u32,
+        include_tests: bool,
+        policy: PolicyKind,
+    ) -> Result<DuplicatesResponse> {
+        let data = self.inner.read().unwrap();
+        let graph = data.graph.as_ref();
+        let solver = data.solver(data.pruning_params(policy));
+        let test_detector = UniversalTestDetector::new();
+        let mut scratch = CfScratch::default();
+
+        // Each hash bucket keeps the normalized text of its groups so a hash collision
+        // never merges bodies that differ.
+        let mut buckets: HashMap<u64, Vec<(String, Vec<NodeIndex>)>> = HashMap::new();
+        for (symbol, &node_idx) in &graph.symbol_to_node {
+            let node = graph.node(node_idx);
+            let core = node.core();
+            if !matches!(node, Node::Function(_))
+                || core.is_external
+                || core.context_size < min_size
+            {
+                continue;
+            }
+            if !include_tests && test_detector.is_test_code(symbol, &core.file_path) {
+                continue;
+            }
+
+            let full_path = data.project_root.join(&core.file_path);
+            let Ok(lines) = data.source_reader.read_lines(
+                &full_path.to_string_lossy(),
+                core.span.start_line as usize,
+                core.span.end_line as usize,
+            ) else {
+                continue;
+            };
+
+            let normalized = normalize_source(&lines, &core.name);
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            normalized.hash(&mut hasher);
+            let bucket = buckets.entry(hasher.finish()).or_default();
+            match bucket.iter_mut().find(|(text, _)| *text == normalized) {
+                Some((_, members)) => members.push(node_idx),
+                None => bucket.push((normalized, vec![node_idx])),
+            }
+        }
+
+        let mut groups: Vec<DuplicateGroup> = buckets
+            .into_values()
+            .flatten()
+            .map(|(_, members)| members)
+            .filter(|members| members.len() > 1)
+            .map(|members| {
+                let mut items: Vec<TopItem> = members
+                    .iter()
+                    .map(|&idx| TopItem {
+                        symbol: graph.symbol_of(idx).unwrap_or_default().to_string(),
+                        node_type: detailed_node_type_str(graph.node(idx)).to_string(),
+                        cf: solver.compute_cf_total_with_scratch(idx, &mut scratch),
+                    })
+                    .collect();
+                items.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+                let total: u32 = items.iter().map(|i| i.cf).sum();
+                let kept = items.iter().map(|i| i.cf).max().unwrap_or(0);
+                DuplicateGroup {
+                    context_size: graph.node(members[0]).core().context_size,
+                    members: items,
+                    saved_cf: total - kept,
+                }
+            })
+            .collect();
+        groups.sort_by(|a, b| {
+            b.saved_cf
+                .cmp(&a.saved_cf)
+                .then_with(|| a.members[0].symbol.cmp(&b.members[0].symbol))
+        });
+
+        Ok(DuplicatesResponse { groups })
+    }
+
+    /// Symbols containing `pattern` (case-insensitive), or matching it as a regex against the
+    /// full symbol when `regex` is set.
+    pub fn search(
+        &self,
+        pattern: &str,
+        regex: bool,
+        with_cf: bool,
+        limit: Option<usize>,
+        include_tests: bool,
+        policy: PolicyKind,
+    ) -> Result<SearchResponse> {
+        let data = self.inner.read().unwrap();
+        let graph = data.graph.as_ref();
+        // CFs are only needed when shown or when a limit keeps the largest matches; otherwise
+        // matches are listed by symbol without running the solver.
+        let needs_cf = with_cf || limit.is_some();
+        let solver = needs_cf.then(|| data.solver(data.pruning_params(policy)));
+        let test_detector = UniversalTestDetector::new();
+
+        let pattern_re = regex.then(|| symbol_regex(pattern)).transpose()?;
+        let pattern_lower = pattern.to_lowercase();
+        let is_match = |symbol: &str| match &pattern_re {
+            Some(re) => re.is_match(symbol),
+            None => symbol.to_lowercase().contains(&pattern_lower),
+        };
+        let mut node_matches: Vec<(&String, NodeIndex)> = Vec::new();
+
+        for (symbol, &node_idx) in &graph.symbol_to_node {
+            if !is_match(symbol) {
+                continue;
+            }
+
+            let node = graph.node(node_idx);
+            if !include_tests && test_detector.is_test_code(symbol, &node.core().file_path) {
+                continue;
+            }
+
+            node_matches.push((symbol, node_idx));
+        }
+
+        let nodes: Vec<NodeIndex> = node_matches.iter().map(|&(_, idx)| idx).collect();
+        let cfs = match &solver {
+            Some(solver) => data.cached_cf_totals(solver, policy, &nodes),
+            None => vec![0; nodes.len()],
+        };
+        let mut matches: Vec<(String, String, u32)> = node_matches
+            .into_iter()
+            .zip(cfs)
+            .map(|((symbol, node_idx), cf)| {
+                let type_str = detailed_node_type_str(graph.node(node_idx)).to_string();
+                (symbol.clone(), type_str, cf)
+            })
+            .collect();
+
+        // Also search for class symbols in TypeRegistry
+        let type_ids: Vec<_> = graph.type_registry.type_ids().cloned().collect();
         for type_id in &type_ids {
-            if !type_id.to_lowercase().contains(&pattern_lower) {
+            if !is_match(type_id) {
                 continue;
             }
             // Skip if already matched as a node
@@ -403,7 +1602,10 @@ impl ContextEngine {
                     .iter()
                     .filter_map(|s| graph.get_node_by_symbol(s))
                     .collect();
-                if with_cf && !start_indices.is_empty() {
+                if let Some(solver) = solver
+                    .as_ref()
+                    .filter(|_| with_cf && !start_indices.is_empty())
+                {
                     let result = solver.compute_cf(&start_indices, None);
                     matches.push((
                         type_id.clone(),
@@ -416,7 +1618,11 @@ impl ContextEngine {
             }
         }
 
-        matches.sort_by(|a, b| b.2.cmp(&a.2));
+        if needs_cf {
+            matches.sort_by_key(|m| std::cmp::Reverse(m.2));
+        } else {
+            matches.sort_by(|a, b| a.0.cmp(&b.0));
+        }
         let total_matches = matches.len();
 
         let display_count = limit.unwrap_or(matches.len());
@@ -428,17 +1634,214 @@ impl ContextEngine {
                 .map(|(symbol, node_type, cf)| SearchItem {
                     symbol: symbol.clone(),
                     node_type: node_type.clone(),
-                    cf: if with_cf || limit.is_some() {
-                        Some(*cf)
-                    } else {
-                        None
-                    },
+                    cf: if needs_cf { Some(*cf) } else { None },
                 })
                 .collect(),
             total_matches,
         })
     }
 
+    /// Symbol of the innermost definition containing `line` (1-based, as shown in editors) of
+    /// `file` (project-relative, or absolute under the project root).
+    pub fn symbol_at(&self, file: &str, line: u32) -> Result<String> {
+        let data = self.inner.read().unwrap();
+        let graph = data.graph.as_ref();
+        let path = Path::new(file);
+        let relative = path
+            .strip_prefix(&data.project_root)
+            .unwrap_or(path)
+            .to_string_lossy();
+        let relative = relative.trim_start_matches("./");
+        line.checked_sub(1)
+            .and_then(|line| graph.innermost_node_at(relative, line))
+            .and_then(|idx| graph.symbol_of(idx))
+            .map(String::from)
+            .ok_or_else(|| anyhow!("No definition found at {}:{}", file, line))
+    }
+
+    /// Symbols of every project node defined in `file` (relative to the project root, or
+    /// absolute under it), sorted: the entry-point set for a whole-file CF. Paths are matched
+    /// exactly, case-sensitively, with `/` separators.
+    pub fn symbols_in_file(&self, file: &str) -> Result<Vec<String>> {
+        let data = self.inner.read().unwrap();
+        let graph = data.graph.as_ref();
+        let path = Path::new(file);
+        let relative = path
+            .strip_prefix(&data.project_root)
+            .unwrap_or(path)
+            .to_string_lossy();
+        let relative = relative.trim_start_matches("./");
+        let mut symbols: Vec<String> = graph
+            .symbol_to_node
+            .iter()
+            .filter(|&(_, &idx)| {
+                let core = graph.node(idx).core();
+                !core.is_external && core.file_path == relative
+            })
+            .map(|(symbol, _)| symbol.clone())
+            .collect();
+        if symbols.is_empty() {
+            return Err(anyhow!("No definitions found in {}", file));
+        }
+        symbols.sort();
+        Ok(symbols)
+    }
+
+    /// Nodes one edge away from `symbol` in `direction`, with the kind of each edge.
+    pub fn neighbors(
+        &self,
+        symbol: &str,
+        direction: NeighborDirection,
+    ) -> Result<NeighborsResponse> {
+        let data = self.inner.read().unwrap();
+        let graph = data.graph.as_ref();
+        let idx = graph
+            .get_node_by_symbol(symbol)
+            .ok_or_else(|| anyhow!("Symbol not found: {}", symbol))?;
+        let reachable_node = |idx: NodeIndex| {
+            let id = graph.node(idx).core().id;
+            self.node_id_to_reachable_node_locked(&data, id)
+        };
+
+        let mut edges: Vec<(NodeIndex, &EdgeKind, NeighborDirection)> = Vec::new();
+        if direction != NeighborDirection::In {
+            edges.extend(
+                graph
+                    .outgoing_edges(idx)
+                    .map(|(target, kind)| (target, kind, NeighborDirection::Out)),
+            );
+        }
+        if direction != NeighborDirection::Out {
+            edges.extend(
+                graph
+                    .incoming_edges(idx, None)
+                    .map(|(source, kind)| (source, kind, NeighborDirection::In)),
+            );
+        }
+        // Parallel edges are listed once per neighbor, kind and direction
+        let mut seen = HashSet::new();
+        edges.retain(|&(other, kind, dir)| seen.insert((other, kind.clone(), dir)));
+
+        let neighbors = edges
+            .into_iter()
+            .filter_map(|(other, kind, direction)| {
+                Some(Neighbor {
+                    node: reachable_node(other)?,
+                    edge_kind: edge_kind_display(kind).to_string(),
+                    direction,
+                })
+            })
+            .collect();
+        Ok(NeighborsResponse {
+            node: reachable_node(idx)
+                .ok_or_else(|| anyhow!("Internal error: missing node for {}", symbol))?,
+            neighbors,
+        })
+    }
+
+    /// Export the graph (or, with `symbol`, that symbol's reachable set) with each node's CF.
+    pub fn graph_export(&self, symbol: Option<&str>, policy: PolicyKind) -> Result<GraphExport> {
+        let data = self.inner.read().unwrap();
+        let graph = data.graph.as_ref();
+        let solver = data.solver(data.pruning_params(policy));
+
+        let mut included: Vec<NodeIndex> = match symbol {
+            Some(sym) => {
+                let start = graph
+                    .get_node_by_symbol(sym)
+                    .ok_or_else(|| anyhow!("Symbol not found: {}", sym))?;
+                solver
+                    .compute_cf(&[start], None)
+                    .reachable_nodes_ordered
+                    .iter()
+                    .filter_map(|id| data.node_id_to_index.get(id).copied())
+                    .collect()
+            }
+            None => graph.graph.node_indices().collect(),
+        };
+        included.sort_by_key(|idx| graph.symbol_of(*idx).unwrap_or_default());
+        let position: HashMap<NodeIndex, usize> = included
+            .iter()
+            .enumerate()
+            .map(|(i, &idx)| (idx, i))
+            .collect();
+
+        let mut scratch = CfScratch::default();
+        let nodes = included
+            .iter()
+            .map(|&idx| {
+                let node = graph.node(idx);
+                ExportNode {
+                    symbol: graph.symbol_of(idx).unwrap_or_default().to_string(),
+                    node_type: node_type_str(node).to_string(),
+                    cf: solver.compute_cf_total_with_scratch(idx, &mut scratch),
+                    doc_score: node.core().doc_score,
+                    is_external: node.core().is_external,
+                }
+            })
+            .collect();
+        let edges = included
+            .iter()
+            .flat_map(|&idx| {
+                graph
+                    .outgoing_edges(idx)
+                    .filter_map(|(target, kind)| {
+                        Some(ExportEdge {
+                            source: position[&idx],
+                            target: *position.get(&target)?,
+                            kind: edge_kind_display(kind).to_string(),
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        Ok(GraphExport { nodes, edges })
+    }
+
+    /// CF of each function containing a changed line (innermost definition wins). `changed`
+    /// maps project-relative paths to 1-based line numbers, see [`changed_lines_from_diff`].
+    pub fn changed_cf(
+        &self,
+        changed: &BTreeMap<String, Vec<u32>>,
+        budget: Option<u32>,
+        policy: PolicyKind,
+    ) -> Result<ChangedCfResponse> {
+        let data = self.inner.read().unwrap();
+        let graph = data.graph.as_ref();
+        let solver = data.solver(data.pruning_params(policy));
+
+        let mut functions: Vec<NodeIndex> = Vec::new();
+        for (file, lines) in changed {
+            for &line in lines {
+                if let Some(idx) = line
+                    .checked_sub(1)
+                    .and_then(|line| graph.innermost_node_at(file, line))
+                    && matches!(graph.node(idx), Node::Function(_))
+                    && !functions.contains(&idx)
+                {
+                    functions.push(idx);
+                }
+            }
+        }
+
+        let mut items: Vec<ChangedCfItem> = functions
+            .into_iter()
+            .map(|idx| {
+                let cf = solver.compute_cf_total(idx);
+                ChangedCfItem {
+                    symbol: graph.symbol_of(idx).unwrap_or_default().to_string(),
+                    file_path: graph.node(idx).core().file_path.clone(),
+                    cf,
+                    over_budget: budget.is_some_and(|limit| cf > limit),
+                }
+            })
+            .collect();
+        items.sort_by(|a, b| b.cf.cmp(&a.cf).then_with(|| a.symbol.cmp(&b.symbol)));
+        let over_budget = items.iter().filter(|item| item.over_budget).count();
+        Ok(ChangedCfResponse { items, over_budget })
+    }
+
     pub fn context(&self, req: ContextRequest) -> Result<ContextResponse> {
         let data = self.inner.read().unwrap();
         let graph = data.graph.as_ref();
@@ -446,8 +1849,10 @@ impl ContextEngine {
             .get_node_by_symbol(&req.symbol)
             .ok_or_else(|| anyhow!("Symbol not found: {}", req.symbol))?;
 
-        let solver = CfSolver::new(data.graph.clone(), pruning_params(req.policy));
-        let result = solver.compute_cf(&[node_idx], req.max_tokens);
+        let mut params = data.pruning_params(req.policy);
+        params.max_depth = req.max_depth.or(params.max_depth);
+        let solver = data.solver(params);
+        let result = solver.compute_cf_with_file_limit(&[node_idx], req.max_tokens, req.max_files);
 
         let mut layers: Vec<ContextLayer> = Vec::new();
 
@@ -540,24 +1945,37 @@ impl ContextEngine {
                         step.node_id
                     )
                 })?;
-                let is_signature_complete = match data.graph.node(node_idx) {
-                    Node::Function(f) => Some(f.is_signature_complete()),
-                    _ => None,
-                };
+                let (is_signature_complete, is_di_wired, is_interface_method) =
+                    match data.graph.node(node_idx) {
+                        Node::Function(f) => (
+                            Some(f.is_signature_complete_with_registry(&graph.type_registry)),
+                            Some(f.is_di_wired),
+                            Some(f.is_interface_method),
+                        ),
+                        _ => (None, None, None),
+                    };
 
                 steps.push(TraversalStepDto {
                     node,
-                    edge_kind: step
-                        .incoming_edge_kind
-                        .as_ref()
-                        .map(edge_kind_display)
-                        .map(String::from),
+                    edge_kind: reached_via_display(&step.reached_via).map(String::from),
                     decision: step
                         .decision
                         .as_ref()
                         .map(decision_display)
                         .map(String::from),
+                    boundary_reason: match step.decision {
+                        Some(PruningDecision::Boundary(reason)) => {
+                            Some(reason.as_str().to_string())
+                        }
+                        _ => None,
+                    },
                     is_signature_complete,
+                    is_di_wired,
+                    is_interface_method,
+                    parent: step
+                        .parent
+                        .and_then(|id| data.node_id_to_symbol.get(&id).cloned()),
+                    depth: step.depth,
                 });
             }
             Some(steps)
@@ -565,16 +1983,81 @@ impl ContextEngine {
             None
         };
 
-        Ok(ContextResponse {
-            symbol: req.symbol,
-            total_context_size: result.total_context_size,
-            reachable_node_count: result.reachable_set.len(),
-            layers,
-            traversal_steps,
-        })
-    }
+        let callers = if req.with_callers > 0 {
+            let mut callers = Vec::new();
+            let mut seen: HashSet<NodeIndex> = HashSet::from([node_idx]);
+            let mut frontier = vec![node_idx];
+            for hops in 1..=req.with_callers {
+                let mut next = Vec::new();
+                for &idx in &frontier {
+                    for (caller_idx, _) in graph.incoming_edges(idx, Some(EdgeKind::Call)) {
+                        if seen.insert(caller_idx) {
+                            next.push(caller_idx);
+                        }
+                    }
+                }
+                let mut nodes: Vec<ReachableNode> = next
+                    .iter()
+                    .filter_map(|&idx| {
+                        self.node_id_to_reachable_node_locked(&data, graph.node(idx).core().id)
+                    })
+                    .collect();
+                nodes.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+                callers.extend(nodes.into_iter().map(|node| CallerNode { hops, node }));
+                if next.is_empty() {
+                    break;
+                }
+                frontier = next;
+            }
+            Some(callers)
+        } else {
+            None
+        };
 
-    /// Resolve an input anchor symbol: function/method, class, or variable.
+        let mut raised: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for node_id in &result.reachable_set {
+            let Some(&idx) = data.node_id_to_index.get(node_id) else {
+                continue;
+            };
+            for (target_idx, edge_kind) in graph.outgoing_edges(idx) {
+                if *edge_kind != EdgeKind::Raises {
+                    continue;
+                }
+                let target = graph.node(target_idx).core();
+                let type_symbol = target
+                    .scope
+                    .clone()
+                    .or_else(|| graph.symbol_of(target_idx).map(String::from))
+                    .unwrap_or_else(|| target.name.clone());
+                if let Some(symbol) = graph.symbol_of(idx) {
+                    raised
+                        .entry(type_symbol)
+                        .or_default()
+                        .insert(symbol.to_string());
+                }
+            }
+        }
+        let raises = raised
+            .into_iter()
+            .map(|(type_symbol, raised_by)| RaisedType {
+                type_symbol,
+                raised_by: raised_by.into_iter().collect(),
+            })
+            .collect();
+
+        Ok(ContextResponse {
+            symbol: req.symbol,
+            total_context_size: result.total_context_size,
+            reachable_node_count: result.reachable_set.len(),
+            boundary_count: result.boundary_count(),
+            layers,
+            traversal_steps,
+            callers,
+            raises,
+        })
+    }
+
+    /// Resolve an input anchor symbol: function/method, class, or variable.
     fn resolve_anchor_locked(&self, data: &EngineData, symbol: &str) -> AnchorResolution {
         let graph = data.graph.as_ref();
 
@@ -665,13 +2148,209 @@ fn build_node_maps(graph: &ContextGraph) -> (HashMap<NodeId, NodeIndex>, HashMap
     (node_id_to_index, node_id_to_symbol)
 }
 
+/// Compare `current` against `baseline`: a symbol regresses when its CF grows by more than
+/// `tolerance_percent` percent of the baseline value.
+pub fn compare_to_baseline(
+    baseline: &CfSnapshot,
+    current: &CfSnapshot,
+    tolerance_percent: f32,
+) -> BaselineComparison {
+    let mut regressions = Vec::new();
+    let mut improved = 0;
+    let mut added = Vec::new();
+    for (symbol, &cf) in &current.symbols {
+        let Some(&before) = baseline.symbols.get(symbol) else {
+            added.push(symbol.clone());
+            continue;
+        };
+        let allowed = before as f32 * (1.0 + tolerance_percent / 100.0);
+        if cf as f32 > allowed {
+            regressions.push(CfChange {
+                symbol: symbol.clone(),
+                baseline: before,
+                current: cf,
+            });
+        } else if cf < before {
+            improved += 1;
+        }
+    }
+    regressions.sort_by_key(|c| std::cmp::Reverse(i64::from(c.current) - i64::from(c.baseline)));
+    let removed = baseline
+        .symbols
+        .keys()
+        .filter(|symbol| !current.symbols.contains_key(*symbol))
+        .cloned()
+        .collect();
+
+    BaselineComparison {
+        regressions,
+        improved,
+        added,
+        removed,
+    }
+}
+
+/// CF changes between two snapshots of the same project: symbols present in both whose CF
+/// moved by at least `threshold` tokens, plus counts of added and removed symbols.
+pub fn diff_snapshots(old: &CfSnapshot, new: &CfSnapshot, threshold: u32) -> CfDiffResponse {
+    let mut changes: Vec<CfDelta> = new
+        .symbols
+        .iter()
+        .filter_map(|(symbol, &after)| {
+            let &before = old.symbols.get(symbol)?;
+            let delta = after as i64 - before as i64;
+            (delta != 0 && delta.unsigned_abs() >= threshold as u64).then(|| CfDelta {
+                symbol: symbol.clone(),
+                old: before,
+                new: after,
+                delta,
+            })
+        })
+        .collect();
+    changes.sort_by_key(|c| std::cmp::Reverse(c.delta.unsigned_abs()));
+    let added = new
+        .symbols
+        .keys()
+        .filter(|symbol| !old.symbols.contains_key(*symbol))
+        .count();
+    let removed = old
+        .symbols
+        .keys()
+        .filter(|symbol| !new.symbols.contains_key(*symbol))
+        .count();
+
+    CfDiffResponse {
+        changes,
+        added,
+        removed,
+    }
+}
+
+/// Lines touched by a unified diff, per new-side file path (1-based). Added lines count as
+/// themselves; a removal counts as the new-side line it was removed before. Deleted files
+/// (`+++ /dev/null`) are skipped. Hunk bodies are consumed by the line counts in their `@@`
+/// header, so removed or added lines that look like `--- `/`+++ ` headers stay hunk content.
+pub fn changed_lines_from_diff(diff: &str) -> BTreeMap<String, Vec<u32>> {
+    let mut changed: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+    let mut file: Option<String> = None;
+    let mut new_line = 0u32;
+    let (mut old_left, mut new_left) = (0u32, 0u32);
+    for line in diff.lines() {
+        if old_left > 0 || new_left > 0 {
+            let mut lines = file.as_ref().map(|f| changed.entry(f.clone()).or_default());
+            match line.chars().next() {
+                Some('+') => {
+                    if let Some(lines) = lines.as_mut() {
+                        lines.push(new_line);
+                    }
+                    new_line += 1;
+                    new_left = new_left.saturating_sub(1);
+                }
+                Some('-') => {
+                    if let Some(lines) = lines.as_mut() {
+                        lines.push(new_line.max(1));
+                    }
+                    old_left = old_left.saturating_sub(1);
+                }
+                // "\ No newline at end of file" belongs to neither side
+                Some('\\') => {}
+                // Context line; some tools strip the leading space of empty ones
+                _ => {
+                    new_line += 1;
+                    old_left = old_left.saturating_sub(1);
+                    new_left = new_left.saturating_sub(1);
+                }
+            }
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            let path = path.split('\t').next().unwrap_or(path).trim();
+            file =
+                (path != "/dev/null").then(|| path.strip_prefix("b/").unwrap_or(path).to_string());
+        } else if let Some(hunk) = line.strip_prefix("@@ ") {
+            // @@ -old_start,old_len +new_start,new_len @@ (a missing length means 1)
+            let range = |sign: char| -> (u32, u32) {
+                let Some(range) = hunk.split_whitespace().find_map(|p| p.strip_prefix(sign)) else {
+                    return (0, 0);
+                };
+                let (start, len) = range.split_once(',').unwrap_or((range, "1"));
+                (start.parse().unwrap_or(0), len.parse().unwrap_or(0))
+            };
+            (_, old_left) = range('-');
+            (new_line, new_left) = range('+');
+        }
+    }
+    for lines in changed.values_mut() {
+        lines.sort_unstable();
+        lines.dedup();
+    }
+    changed.retain(|_, lines| !lines.is_empty());
+    changed
+}
+
+/// CF total of each node in `nodes`, in the same order. With the `parallel` feature the nodes
+/// are spread over the rayon pool, each worker reusing its own scratch buffer.
+fn compute_cf_totals(solver: &CfSolver, nodes: &[NodeIndex]) -> Vec<u32> {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        nodes
+            .par_iter()
+            .map_init(CfScratch::default, |scratch, &node_idx| {
+                solver.compute_cf_total_with_scratch(node_idx, scratch)
+            })
+            .collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        // One visited buffer for the whole pass instead of one per node.
+        let mut scratch = CfScratch::default();
+        nodes
+            .iter()
+            .map(|&node_idx| solver.compute_cf_total_with_scratch(node_idx, &mut scratch))
+            .collect()
+    }
+}
+
+/// A function is a boundary if a call into it stops there. The callee's own properties decide,
+/// so it stands in for the caller; a 0-sized function (no source) is given a non-stub size as
+/// caller so the stub-source rule does not make every such function a boundary.
+fn is_function_boundary(params: &PruningParams, function: &Node, graph: &ContextGraph) -> bool {
+    if function.core().context_size == 0 {
+        let mut caller = function.clone();
+        caller.core_mut().context_size = 1;
+        return evaluate_forward(params, &caller, function, &EdgeKind::Call, graph).is_boundary();
+    }
+    evaluate_forward(params, function, function, &EdgeKind::Call, graph).is_boundary()
+}
+
 fn pruning_params(kind: PolicyKind) -> PruningParams {
     match kind {
         PolicyKind::Academic => PruningParams::academic(0.5),
+        PolicyKind::Balanced => PruningParams::balanced(0.5),
         PolicyKind::Strict => PruningParams::strict(0.8),
     }
 }
 
+/// Normalize a function's source for duplicate detection: drop comment-only lines,
+/// collapse whitespace between tokens and replace the function's own name, so copies
+/// that differ only in name, indentation or comments compare equal.
+fn normalize_source(lines: &[String], name: &str) -> String {
+    let mut tokens: Vec<&str> = Vec::new();
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.starts_with('#') || trimmed.starts_with("//") {
+            continue;
+        }
+        tokens.extend(trimmed.split_whitespace());
+    }
+    let joined = tokens.join(" ");
+    if name.is_empty() {
+        return joined;
+    }
+    let name_re = regex::Regex::new(&format!(r"\b{}\b", regex::escape(name)))
+        .expect("escaped name is a valid regex");
+    name_re.replace_all(&joined, "_").into_owned()
+}
+
 fn node_type_str(node: &Node) -> &'static str {
     match node {
         Node::Function(_) => "function",
@@ -730,6 +2409,11 @@ fn span_dto(span: &crate::domain::node::SourceSpan) -> SpanDto {
     }
 }
 
+/// Compile a user-supplied symbol pattern, reporting a bad pattern as an error.
+fn symbol_regex(pattern: &str) -> Result<Regex> {
+    Regex::new(pattern).with_context(|| format!("Invalid regex: {pattern}"))
+}
+
 fn edge_kind_display(ek: &EdgeKind) -> &'static str {
     match ek {
         EdgeKind::Call => "Call",
@@ -737,23 +2421,44 @@ fn edge_kind_display(ek: &EdgeKind) -> &'static str {
         EdgeKind::Write => "Write",
         EdgeKind::OverriddenBy => "OverriddenBy",
         EdgeKind::Annotates => "Annotates",
+        EdgeKind::Inherits => "Inherits",
+        EdgeKind::Raises => "Raises",
+        EdgeKind::Uses => "Uses",
+    }
+}
+
+/// Edge label for a traversal step: forward edge kind, or the reverse exploration that
+/// reached the node. None for start nodes.
+fn reached_via_display(via: &ReachedVia) -> Option<&'static str> {
+    match via {
+        ReachedVia::Start => None,
+        ReachedVia::Forward(ek) => Some(edge_kind_display(ek)),
+        ReachedVia::CallIn => Some("CallIn"),
+        ReachedVia::SharedStateWrite => Some("SharedStateWrite"),
     }
 }
 
 fn decision_display(d: &PruningDecision) -> &'static str {
     match d {
-        PruningDecision::Boundary => "Boundary",
+        PruningDecision::Boundary(_) => "Boundary",
         PruningDecision::Transparent => "Transparent",
     }
 }
 
-fn compute_distribution(mut sizes: Vec<u32>) -> CfDistribution {
+fn compute_distribution(sizes: Vec<u32>) -> CfDistribution {
+    compute_distribution_at(sizes, None)
+}
+
+/// Distribution of `sizes` at the given percentiles (clamped to 100), or at 5% steps when
+/// `percentiles` is `None`.
+fn compute_distribution_at(mut sizes: Vec<u32>, percentiles: Option<&[u32]>) -> CfDistribution {
     if sizes.is_empty() {
         return CfDistribution {
             count: 0,
             percentiles: vec![],
             average: 0,
             median: 0,
+            p99: 0,
             min: 0,
             max: 0,
         };
@@ -761,15 +2466,17 @@ fn compute_distribution(mut sizes: Vec<u32>) -> CfDistribution {
 
     sizes.sort_unstable();
     let count = sizes.len();
+    let at = |p: usize| sizes[((p * (count - 1)) / 100).min(count - 1)];
 
-    let percentiles = (5..=100)
-        .step_by(5)
-        .map(|p| {
-            let idx = ((p * (count - 1)) / 100).min(count - 1);
-            PercentileValue {
-                percentile: p as u32,
-                tokens: sizes[idx],
-            }
+    let requested: Vec<u32> = match percentiles {
+        Some(percentiles) => percentiles.iter().map(|&p| p.min(100)).collect(),
+        None => (5..=100).step_by(5).collect(),
+    };
+    let percentiles = requested
+        .into_iter()
+        .map(|p| PercentileValue {
+            percentile: p,
+            tokens: at(p as usize),
         })
         .collect::<Vec<_>>();
 
@@ -782,6 +2489,7 @@ fn compute_distribution(mut sizes: Vec<u32>) -> CfDistribution {
         percentiles,
         average,
         median,
+        p99: at(99),
         min: sizes[0],
         max: sizes[count - 1],
     }
@@ -903,47 +2611,1020 @@ mod tests {
     }
 
     #[test]
-    fn test_engine_health_and_compute() {
+    fn test_engine_health_and_compute() {
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            test_graph(),
+            Arc::new(MockReader),
+        );
+
+        let health = engine.health();
+        assert_eq!(health.node_count, 2);
+        assert_eq!(health.edge_count, 1);
+
+        let res = engine
+            .compute(ComputeRequest {
+                symbols: vec!["sym/func1().".into()],
+                policy: PolicyKind::Academic,
+                max_tokens: None,
+                max_depth: None,
+                func_doc_threshold: None,
+                interface_doc_threshold: None,
+                already_read: Vec::new(),
+            })
+            .unwrap();
+        assert!(res.total_context_size > 0);
+        assert_eq!(res.reachable_node_count, 2);
+        assert!(!res.reachable_nodes_ordered.is_empty());
+        // func1 and var1 live in the same file
+        assert_eq!(res.files_touched, vec!["app/main.py"]);
+    }
+
+    #[test]
+    fn test_symbol_at_resolves_innermost_definition_and_computes_its_cf() {
+        let function = |id, name: &str, start_line, end_line| {
+            Node::Function(FunctionNode {
+                core: make_core(id, name, "app/a.py", start_line, end_line),
+                parameters: Vec::new(),
+                is_async: false,
+                is_generator: false,
+                visibility: Visibility::Public,
+                return_types: vec![],
+                is_interface_method: false,
+                is_constructor: false,
+                is_di_wired: false,
+            })
+        };
+        // handler spans lines 1-10 and contains a nested helper on lines 4-6 (1-based)
+        let mut g = ContextGraph::new();
+        let handler = g.add_node("a/handler().".into(), function(0, "handler", 0, 9));
+        let helper = g.add_node("a/handler().helper().".into(), function(1, "helper", 3, 5));
+        let util = g.add_node("a/util().".into(), function(2, "util", 12, 14));
+        g.add_edge(handler, helper, EdgeKind::Call);
+        g.add_edge(helper, util, EdgeKind::Call);
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            g,
+            Arc::new(MockReader),
+        );
+
+        assert_eq!(
+            engine.symbol_at("app/a.py", 5).unwrap(),
+            "a/handler().helper()."
+        );
+        assert_eq!(engine.symbol_at("app/a.py", 2).unwrap(), "a/handler().");
+        assert_eq!(engine.symbol_at("/repo/app/a.py", 13).unwrap(), "a/util().");
+        assert!(engine.symbol_at("app/a.py", 12).is_err());
+        assert!(engine.symbol_at("app/a.py", 0).is_err());
+
+        let symbol = engine.symbol_at("./app/a.py", 5).unwrap();
+        let res = engine
+            .compute(ComputeRequest {
+                symbols: vec![symbol],
+                policy: PolicyKind::Academic,
+                max_tokens: None,
+                max_depth: None,
+                func_doc_threshold: None,
+                interface_doc_threshold: None,
+                already_read: Vec::new(),
+            })
+            .unwrap();
+        assert_eq!(res.reachable_node_count, 2);
+        assert_eq!(res.total_context_size, 20);
+    }
+
+    #[test]
+    fn test_symbols_in_file_form_the_entry_set_of_a_file_cf() {
+        let function = |id, name: &str, file: &str| {
+            Node::Function(FunctionNode {
+                core: make_core(id, name, file, 0, 1),
+                parameters: Vec::new(),
+                is_async: false,
+                is_generator: false,
+                visibility: Visibility::Public,
+                return_types: vec![],
+                is_interface_method: false,
+                is_constructor: false,
+                is_di_wired: false,
+            })
+        };
+        let mut g = ContextGraph::new();
+        let login = g.add_node(
+            "auth/login().".into(),
+            function(0, "login", "auth/service.py"),
+        );
+        let logout = g.add_node(
+            "auth/logout().".into(),
+            function(1, "logout", "auth/service.py"),
+        );
+        let hash = g.add_node("util/hash().".into(), function(2, "hash", "util.py"));
+        g.add_edge(login, hash, EdgeKind::Call);
+        g.add_edge(logout, hash, EdgeKind::Call);
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            g,
+            Arc::new(MockReader),
+        );
+
+        let symbols = engine.symbols_in_file("/repo/auth/service.py").unwrap();
+        assert_eq!(symbols, vec!["auth/login().", "auth/logout()."]);
+        assert!(engine.symbols_in_file("Auth/Service.py").is_err());
+        assert!(engine.symbols_in_file("auth").is_err());
+
+        let res = engine
+            .compute(ComputeRequest {
+                symbols,
+                policy: PolicyKind::Academic,
+                max_tokens: None,
+                max_depth: None,
+                func_doc_threshold: None,
+                interface_doc_threshold: None,
+                already_read: Vec::new(),
+            })
+            .unwrap();
+        assert_eq!(res.reachable_node_count, 3);
+        assert_eq!(res.files_touched, vec!["auth/service.py", "util.py"]);
+    }
+
+    #[test]
+    fn test_compute_already_read_excludes_shared_dependency() {
+        let function = |id, name: &str| {
+            Node::Function(FunctionNode {
+                core: make_core(id, name, "app/a.py", id, id),
+                parameters: Vec::new(),
+                is_async: false,
+                is_generator: false,
+                visibility: Visibility::Public,
+                return_types: vec![],
+                is_interface_method: false,
+                is_constructor: false,
+                is_di_wired: false,
+            })
+        };
+        // reviewed() and new_code() both call shared()
+        let mut g = ContextGraph::new();
+        let reviewed = g.add_node("a/reviewed().".into(), function(0, "reviewed"));
+        let new_code = g.add_node("a/new_code().".into(), function(1, "new_code"));
+        let shared = g.add_node("a/shared().".into(), function(2, "shared"));
+        g.add_edge(reviewed, shared, EdgeKind::Call);
+        g.add_edge(new_code, shared, EdgeKind::Call);
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            g,
+            Arc::new(MockReader),
+        );
+        let compute = |already_read: &[&str]| {
+            engine.compute(ComputeRequest {
+                symbols: vec!["a/new_code().".into()],
+                policy: PolicyKind::Academic,
+                max_tokens: None,
+                max_depth: None,
+                func_doc_threshold: None,
+                interface_doc_threshold: None,
+                already_read: already_read.iter().map(|s| s.to_string()).collect(),
+            })
+        };
+
+        let full = compute(&[]).unwrap();
+        let rest = compute(&["a/shared()."]).unwrap();
+        assert_eq!(rest.total_context_size, full.total_context_size - 10);
+        assert_eq!(rest.reachable_node_count, full.reachable_node_count - 1);
+        assert!(
+            rest.reachable_nodes_ordered
+                .iter()
+                .all(|n| n.symbol != "a/shared().")
+        );
+        assert!(compute(&["a/missing()."]).is_err());
+    }
+
+    #[test]
+    fn test_engine_search_and_top() {
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            test_graph(),
+            Arc::new(MockReader),
+        );
+
+        let search = engine
+            .search("func", false, true, None, true, PolicyKind::Academic)
+            .unwrap();
+        assert_eq!(search.total_matches, 1);
+        assert_eq!(search.items[0].symbol, "sym/func1().");
+
+        let top = engine
+            .top(10, "all", None, None, true, PolicyKind::Academic)
+            .unwrap();
+        assert_eq!(top.items.len(), 2);
+    }
+
+    /// Counts pruning evaluations, i.e. solver traversal work.
+    #[derive(Debug, Default)]
+    struct CountingRule(std::sync::atomic::AtomicUsize);
+
+    impl CountingRule {
+        fn evaluations(&self) -> usize {
+            self.0.load(std::sync::atomic::Ordering::Relaxed)
+        }
+    }
+
+    impl crate::domain::policy::CustomPruningRule for CountingRule {
+        fn evaluate(
+            &self,
+            _source: &Node,
+            _target: &Node,
+            _edge_kind: &EdgeKind,
+            _graph: &ContextGraph,
+        ) -> Option<PruningDecision> {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            None
+        }
+    }
+
+    #[test]
+    fn test_search_without_cf_skips_solver() {
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            test_graph(),
+            Arc::new(MockReader),
+        );
+        let counter = Arc::new(CountingRule::default());
+        engine.set_policy_override(Some(PruningParams {
+            custom_rules: vec![counter.clone()],
+            ..PruningParams::academic(0.5)
+        }));
+
+        let search = engine
+            .search("sym", false, false, None, true, PolicyKind::Academic)
+            .unwrap();
+        assert_eq!(counter.evaluations(), 0);
+        let symbols: Vec<_> = search.items.iter().map(|i| i.symbol.as_str()).collect();
+        assert_eq!(symbols, ["sym/func1().", "sym/var1."]);
+        assert!(search.items.iter().all(|i| i.cf.is_none()));
+
+        let search = engine
+            .search("sym", false, true, None, true, PolicyKind::Academic)
+            .unwrap();
+        assert!(counter.evaluations() > 0);
+        assert!(search.items.iter().all(|i| i.cf.is_some()));
+    }
+
+    #[test]
+    fn test_warm_cache_turns_top_and_search_into_lookups() {
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            test_graph(),
+            Arc::new(MockReader),
+        );
+        let counter = Arc::new(CountingRule::default());
+        let params = PruningParams {
+            custom_rules: vec![counter.clone()],
+            ..PruningParams::academic(0.5)
+        };
+        engine.set_policy_override(Some(params.clone()));
+        let cold = engine
+            .top(10, "all", None, None, true, PolicyKind::Academic)
+            .unwrap();
+
+        engine.set_policy_override(Some(params));
+        engine.warm_cache(PolicyKind::Academic);
+        let evaluations = counter.evaluations();
+        assert!(evaluations > 0);
+
+        let warm = engine
+            .top(10, "all", None, None, true, PolicyKind::Academic)
+            .unwrap();
+        let search = engine
+            .search("sym", false, true, None, true, PolicyKind::Academic)
+            .unwrap();
+        assert_eq!(counter.evaluations(), evaluations);
+
+        // stats reads CFs from the cache too; it only re-classifies each function as
+        // boundary/transparent (one rule evaluation per function, no traversal)
+        let stats = engine.stats(true, true, PolicyKind::Academic).unwrap();
+        assert_eq!(counter.evaluations(), evaluations + stats.functions.count);
+
+        let cfs = |items: &[TopItem]| {
+            items
+                .iter()
+                .map(|i| (i.symbol.clone(), i.cf))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(cfs(&warm.items), cfs(&cold.items));
+        assert_eq!(search.items[0].cf, Some(warm.items[0].cf));
+    }
+
+    #[test]
+    fn test_stats_include_variables_distribution() {
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            test_graph(),
+            Arc::new(MockReader),
+        );
+
+        let without = engine.stats(true, false, PolicyKind::Academic).unwrap();
+        assert_eq!(without.functions.count, 1);
+        assert!(without.variables.is_none());
+        assert!(without.global_variables.is_none());
+
+        let with = engine.stats(true, true, PolicyKind::Academic).unwrap();
+        assert_eq!(with.functions.count, 1);
+        let variables = with.variables.expect("variable distribution");
+        assert_eq!(variables.count, 1);
+        assert_eq!(variables.max, 10);
+        // var1 is module-level
+        assert_eq!(with.global_variables.unwrap().count, 1);
+        assert_eq!(with.field_variables.unwrap().count, 0);
+
+        let top = engine
+            .top(
+                10,
+                "function,variable",
+                None,
+                None,
+                true,
+                PolicyKind::Academic,
+            )
+            .unwrap();
+        assert_eq!(top.items.len(), 2);
+    }
+
+    #[test]
+    fn test_stats_boundary_counts_follow_doc_threshold() {
+        let function = |id, name: &str, doc_score| {
+            let mut core = make_core(id, name, "app/a.py", id, id);
+            core.doc_score = doc_score;
+            Node::Function(FunctionNode {
+                core,
+                parameters: Vec::new(),
+                is_async: false,
+                is_generator: false,
+                visibility: Visibility::Public,
+                return_types: vec!["int#".into()],
+                is_interface_method: false,
+                is_constructor: false,
+                is_di_wired: false,
+            })
+        };
+        let mut g = ContextGraph::new();
+        g.add_node("a/ok().".into(), function(0, "ok", 0.6));
+        g.add_node("a/good().".into(), function(1, "good", 0.9));
+        g.add_node("a/bare().".into(), function(2, "bare", 0.0));
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            g,
+            Arc::new(MockReader),
+        );
+
+        let lenient = engine.stats(true, false, PolicyKind::Academic).unwrap();
+        assert_eq!(
+            lenient.boundary_count + lenient.transparent_count,
+            lenient.functions.count
+        );
+        assert_eq!(lenient.boundary_count, 2);
+
+        engine.set_policy_override(Some(PruningParams::academic(0.8)));
+        let demanding = engine.stats(true, false, PolicyKind::Academic).unwrap();
+        assert_eq!(
+            demanding.boundary_count + demanding.transparent_count,
+            demanding.functions.count
+        );
+        assert!(demanding.boundary_count < lenient.boundary_count);
+    }
+
+    #[test]
+    fn test_stats_boundary_counts_ignore_zero_size_functions_as_callers() {
+        // Functions without source (--no-source, missing files) have context_size 0
+        let function = |id, name: &str, doc_score| {
+            let mut core = make_core(id, name, "app/a.py", id, id);
+            core.context_size = 0;
+            core.doc_score = doc_score;
+            Node::Function(FunctionNode {
+                core,
+                parameters: Vec::new(),
+                is_async: false,
+                is_generator: false,
+                visibility: Visibility::Public,
+                return_types: vec!["int#".into()],
+                is_interface_method: false,
+                is_constructor: false,
+                is_di_wired: false,
+            })
+        };
+        let mut g = ContextGraph::new();
+        g.add_node("a/good().".into(), function(0, "good", 0.9));
+        g.add_node("a/bare().".into(), function(1, "bare", 0.0));
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            g,
+            Arc::new(MockReader),
+        );
+
+        let stats = engine.stats(true, false, PolicyKind::Academic).unwrap();
+        assert_eq!(stats.boundary_count, 1);
+        assert_eq!(stats.transparent_count, 1);
+    }
+
+    #[test]
+    fn test_compute_distribution_at_requested_percentiles() {
+        let sizes: Vec<u32> = (1..=100).collect();
+
+        let dist = compute_distribution_at(sizes.clone(), Some(&[50, 90, 150]));
+        let got: Vec<_> = dist
+            .percentiles
+            .iter()
+            .map(|p| (p.percentile, p.tokens))
+            .collect();
+        // Out-of-range percentiles are clamped to 100
+        assert_eq!(got, vec![(50, 50), (90, 90), (100, 100)]);
+        assert_eq!(dist.p99, 99);
+        assert_eq!(dist.max, 100);
+
+        // Without a list: 5% steps, and p99 is still reported
+        let dist = compute_distribution(sizes);
+        assert_eq!(dist.percentiles.len(), 20);
+        assert_eq!(dist.p99, 99);
+    }
+
+    #[test]
+    fn test_stats_scratch_matches_per_call_distribution() {
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            test_graph(),
+            Arc::new(MockReader),
+        );
+
+        let stats = engine.stats(true, true, PolicyKind::Academic).unwrap();
+
+        let graph = Arc::new(test_graph());
+        let solver = CfSolver::new(Arc::clone(&graph), pruning_params(PolicyKind::Academic));
+        let (mut function_cf, mut variable_cf) = (Vec::new(), Vec::new());
+        for idx in graph.graph.node_indices() {
+            let cf = solver.compute_cf_total(idx);
+            match graph.node(idx) {
+                Node::Function(_) => function_cf.push(cf),
+                Node::Variable(_) => variable_cf.push(cf),
+            }
+        }
+
+        let functions = compute_distribution(function_cf);
+        let variables = compute_distribution(variable_cf);
+        let got_variables = stats.variables.expect("variable distribution");
+        assert_eq!(
+            serde_json::to_value(&stats.functions).unwrap(),
+            serde_json::to_value(&functions).unwrap()
+        );
+        assert_eq!(
+            serde_json::to_value(&got_variables).unwrap(),
+            serde_json::to_value(&variables).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_policy_file_overrides_preset() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.json");
+        std::fs::write(
+            &path,
+            r#"{"doc_threshold": 0.9, "depth_size_multiplier": 1.0}"#,
+        )
+        .unwrap();
+
+        let params = ContextEngine::load_policy_file(&path).unwrap();
+        assert_eq!(params.func_doc_threshold, 0.9);
+        assert_eq!(params.depth_size_multiplier, 1.0);
+        assert!(params.treat_typed_documented_function_as_boundary);
+
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            test_graph(),
+            Arc::new(MockReader),
+        );
+        let preset = engine.stats(true, false, PolicyKind::Academic).unwrap();
+        engine.set_policy_override(Some(params));
+        let custom = engine.stats(true, false, PolicyKind::Academic).unwrap();
+        assert!(custom.functions.max > preset.functions.max);
+
+        std::fs::write(&path, r#"{"doc_treshold": 0.9}"#).unwrap();
+        assert!(ContextEngine::load_policy_file(&path).is_err());
+
+        std::fs::write(
+            &path,
+            r#"{"exclude_edge_targets": ["logger\\.", "metrics"]}"#,
+        )
+        .unwrap();
+        let params = ContextEngine::load_policy_file(&path).unwrap();
+        assert_eq!(params.exclude_edge_targets.len(), 2);
+        assert!(params.exclude_edge_targets[0].is_match("pkg/logger.info()."));
+
+        std::fs::write(&path, r#"{"exclude_edge_targets": ["("]}"#).unwrap();
+        assert!(ContextEngine::load_policy_file(&path).is_err());
+
+        // Build-time exclusions sit beside the pruning params
+        std::fs::write(
+            &path,
+            r#"{"exclude": ["*_pb2.py", "migrations/**"], "doc_threshold": 0.7}"#,
+        )
+        .unwrap();
+        let policy = PolicyFile::load(&path).unwrap();
+        assert_eq!(policy.params.func_doc_threshold, 0.7);
+        assert_eq!(policy.exclude.len(), 2);
+        assert!(policy.exclude[1].is_match("migrations/0001_init.py"));
+    }
+
+    #[test]
+    fn test_policy_file_in_toml() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.toml");
+        std::fs::write(
+            &path,
+            "doc_threshold = 0.75\nboundary_size_discount = 0.5\nexclude = [\"gen/**\"]\n",
+        )
+        .unwrap();
+
+        let policy = PolicyFile::load(&path).unwrap();
+        assert_eq!(policy.params.func_doc_threshold, 0.75);
+        assert_eq!(policy.params.interface_doc_threshold, 0.75);
+        assert_eq!(policy.params.boundary_size_discount, 0.5);
+        assert_eq!(policy.exclude.len(), 1);
+
+        std::fs::write(&path, "boundary_size_discunt = 0.5\n").unwrap();
+        assert!(PolicyFile::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_reload_with_concurrent_readers_swaps_graph() {
+        fn write_semantic_data(dir: &Path, functions: &[&str]) {
+            use crate::domain::semantic::{
+                DocumentSemantics, FunctionDetails, SourceLocation, SourceSpan as SemanticSpan,
+                SymbolDefinition, SymbolDetails, SymbolKind,
+            };
+
+            let definitions = functions
+                .iter()
+                .zip(0u32..)
+                .map(|(name, line)| SymbolDefinition {
+                    symbol_id: format!("{name}()."),
+                    kind: SymbolKind::Function,
+                    name: name.to_string(),
+                    display_name: name.to_string(),
+                    location: SourceLocation {
+                        file_path: "main.py".into(),
+                        line,
+                        column: 0,
+                    },
+                    span: SemanticSpan {
+                        start_line: line,
+                        start_column: 0,
+                        end_line: line,
+                        end_column: 13,
+                    },
+                    enclosing_symbol: None,
+                    is_external: false,
+                    is_generated: false,
+                    documentation: vec![],
+                    details: SymbolDetails::Function(FunctionDetails::default()),
+                })
+                .collect();
+            let data = SemanticData {
+                project_root: dir.to_string_lossy().to_string(),
+                documents: vec![DocumentSemantics {
+                    relative_path: "main.py".into(),
+                    language: "python".into(),
+                    definitions,
+                    references: vec![],
+                }],
+                external_symbols: vec![],
+            };
+            std::fs::write(
+                dir.join("semantic_data.json"),
+                serde_json::to_string(&data).unwrap(),
+            )
+            .unwrap();
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("main.py"), "def a(): pass\ndef b(): pass\n").unwrap();
+        write_semantic_data(dir.path(), &["a"]);
+        let engine = ContextEngine::load_from_json(&dir.path().join("semantic_data.json")).unwrap();
+        assert_eq!(engine.health().node_count, 1);
+
+        write_semantic_data(dir.path(), &["a", "b"]);
+        let done = Arc::new(AtomicBool::new(false));
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let engine = engine.clone();
+                let done = done.clone();
+                std::thread::spawn(move || {
+                    while !done.load(std::sync::atomic::Ordering::Relaxed) {
+                        let count = engine.health().node_count;
+                        assert!(count == 1 || count == 2);
+                    }
+                })
+            })
+            .collect();
+
+        let health = engine.reload().unwrap();
+        done.store(true, std::sync::atomic::Ordering::Relaxed);
+        for reader in readers {
+            reader.join().unwrap();
+        }
+
+        assert_eq!(health.node_count, 2);
+        assert_eq!(engine.health().node_count, 2);
+        assert!(
+            engine
+                .inner
+                .read()
+                .unwrap()
+                .node_id_to_symbol
+                .values()
+                .any(|s| s == "b().")
+        );
+    }
+
+    #[test]
+    fn test_duplicates_groups_identical_bodies() {
+        struct DupReader;
+        impl SourceReader for DupReader {
+            fn read(&self, path: &Path) -> Result<String> {
+                Ok(match path.to_string_lossy().as_ref() {
+                    p if p.ends_with("a.py") => "def load(x):\n    # fetch\n    return x + 1\n",
+                    p if p.ends_with("b.py") => "def fetch(x):\n        return   x + 1\n",
+                    _ => "def other(x):\n    return x * 2\n",
+                }
+                .to_string())
+            }
+
+            fn read_lines(&self, path: &str, start: usize, end: usize) -> Result<Vec<String>> {
+                let content = self.read(Path::new(path))?;
+                let lines: Vec<String> = content.lines().map(String::from).collect();
+                Ok(lines[start.min(lines.len())..(end + 1).min(lines.len())].to_vec())
+            }
+        }
+
+        let function = |id, name: &str, file: &str| {
+            Node::Function(FunctionNode {
+                core: make_core(id, name, file, 0, 2),
+                parameters: Vec::new(),
+                is_async: false,
+                is_generator: false,
+                visibility: Visibility::Public,
+                return_types: vec![],
+                is_interface_method: false,
+                is_constructor: false,
+                is_di_wired: false,
+            })
+        };
+        let mut g = ContextGraph::new();
+        g.add_node("sym/load().".into(), function(0, "load", "a.py"));
+        g.add_node("sym/fetch().".into(), function(1, "fetch", "b.py"));
+        g.add_node("sym/other().".into(), function(2, "other", "c.py"));
+
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            g,
+            Arc::new(DupReader),
+        );
+
+        let result = engine.duplicates(0, true, PolicyKind::Academic).unwrap();
+        assert_eq!(result.groups.len(), 1);
+        let group = &result.groups[0];
+        let symbols: Vec<&str> = group.members.iter().map(|m| m.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["sym/fetch().", "sym/load()."]);
+        assert_eq!(group.saved_cf, 10);
+
+        // Below the size floor nothing is considered
+        let result = engine.duplicates(11, true, PolicyKind::Academic).unwrap();
+        assert!(result.groups.is_empty());
+    }
+
+    #[test]
+    fn test_file_stats_reports_union_cf_per_file() {
+        let function = |id, name: &str, file: &str| {
+            Node::Function(FunctionNode {
+                core: make_core(id, name, file, 0, 1),
+                parameters: Vec::new(),
+                is_async: false,
+                is_generator: false,
+                visibility: Visibility::Public,
+                return_types: vec![],
+                is_interface_method: false,
+                is_constructor: false,
+                is_di_wired: false,
+            })
+        };
+        // a.py: handler -> b.py: helper -> b.py: util; a.py: main (isolated)
+        let mut g = ContextGraph::new();
+        let handler = g.add_node("a/handler().".into(), function(0, "handler", "a.py"));
+        g.add_node("a/main().".into(), function(1, "main", "a.py"));
+        let helper = g.add_node("b/helper().".into(), function(2, "helper", "b.py"));
+        let util = g.add_node("b/util().".into(), function(3, "util", "b.py"));
+        g.add_edge(handler, helper, EdgeKind::Call);
+        g.add_edge(helper, util, EdgeKind::Call);
+
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            g,
+            Arc::new(MockReader),
+        );
+        let result = engine.file_stats(true, PolicyKind::Academic).unwrap();
+        assert_eq!(result.distribution.count, 2);
+
+        let union_cf = |symbols: &[&str]| {
+            engine
+                .compute(ComputeRequest {
+                    symbols: symbols.iter().map(|s| s.to_string()).collect(),
+                    policy: PolicyKind::Academic,
+                    max_tokens: None,
+                    max_depth: None,
+                    func_doc_threshold: None,
+                    interface_doc_threshold: None,
+                    already_read: Vec::new(),
+                })
+                .unwrap()
+                .total_context_size
+        };
+        let a = result.files.iter().find(|f| f.file_path == "a.py").unwrap();
+        assert_eq!(a.node_count, 2);
+        assert_eq!(a.cf, union_cf(&["a/handler().", "a/main()."]));
+        let b = result.files.iter().find(|f| f.file_path == "b.py").unwrap();
+        assert_eq!(b.cf, union_cf(&["b/helper().", "b/util()."]));
+        // Heaviest first
+        assert_eq!(result.files[0].file_path, "a.py");
+        assert!(a.cf > b.cf);
+    }
+
+    #[test]
+    fn test_fitness_prefers_boundaries_over_call_cycles() {
+        let function = |id, name: &str, documented: bool| {
+            let mut core = make_core(id, name, "m.py", 0, 1);
+            core.doc_score = if documented { 1.0 } else { 0.0 };
+            Node::Function(FunctionNode {
+                core,
+                parameters: Vec::new(),
+                is_async: false,
+                is_generator: false,
+                visibility: Visibility::Public,
+                return_types: if documented {
+                    vec!["int#".to_string()]
+                } else {
+                    vec![]
+                },
+                is_interface_method: false,
+                is_constructor: false,
+                is_di_wired: false,
+            })
+        };
+        let engine_for = |documented: bool, cyclic: bool| {
+            let mut g = ContextGraph::new();
+            let nodes: Vec<NodeIndex> = (0..4)
+                .map(|i| g.add_node(format!("m/f{i}()."), function(i, "f", documented)))
+                .collect();
+            for pair in nodes.windows(2) {
+                g.add_edge(pair[0], pair[1], EdgeKind::Call);
+            }
+            if cyclic {
+                g.add_edge(nodes[3], nodes[0], EdgeKind::Call);
+            }
+            ContextEngine::from_prebuilt(
+                PathBuf::from("semantic_data.json"),
+                PathBuf::from("/repo"),
+                g,
+                Arc::new(MockReader),
+            )
+        };
+        let config = FitnessConfig::default();
+
+        // Typed, documented callees in a chain: every call stops at a boundary.
+        let layered = engine_for(true, false)
+            .fitness(true, PolicyKind::Academic, &config)
+            .unwrap();
+        // Undocumented functions calling each other in a loop.
+        let spaghetti = engine_for(false, true)
+            .fitness(true, PolicyKind::Academic, &config)
+            .unwrap();
+
+        let value = |r: &FitnessResponse, name: &str| {
+            r.factors.iter().find(|f| f.name == name).unwrap().value
+        };
+        assert_eq!(value(&layered, "boundary_ratio"), 1.0);
+        assert_eq!(value(&layered, "largest_cycle"), 1.0);
+        assert_eq!(value(&spaghetti, "boundary_ratio"), 0.0);
+        assert_eq!(value(&spaghetti, "largest_cycle"), 4.0);
+        assert!(value(&spaghetti, "median_cf") > value(&layered, "median_cf"));
+        assert!(layered.score > spaghetti.score);
+        assert!(layered.score <= 100.0 && spaghetti.score >= 0.0);
+
+        // Weighting is configurable: scoring only god objects makes both perfect.
+        let only_god_objects = FitnessConfig {
+            median_cf_weight: 0.0,
+            boundary_ratio_weight: 0.0,
+            largest_cycle_weight: 0.0,
+            ..FitnessConfig::default()
+        };
+        let spaghetti = engine_for(false, true)
+            .fitness(true, PolicyKind::Academic, &only_god_objects)
+            .unwrap();
+        assert_eq!(spaghetti.score, 100.0);
+    }
+
+    #[test]
+    fn test_cancelled_stats_and_compute_return_error() {
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            test_graph(),
+            Arc::new(MockReader),
+        );
+        let cancel = Arc::new(AtomicBool::new(true));
+
+        let err = engine
+            .stats_cancellable(true, true, PolicyKind::Academic, None, Arc::clone(&cancel))
+            .unwrap_err();
+        assert!(err.to_string().contains("cancelled"));
+
+        let req = ComputeRequest {
+            symbols: vec!["sym/func1().".into()],
+            policy: PolicyKind::Academic,
+            max_tokens: None,
+            max_depth: None,
+            func_doc_threshold: None,
+            interface_doc_threshold: None,
+            already_read: Vec::new(),
+        };
+        assert!(engine.compute_cancellable(req.clone(), cancel).is_err());
+        assert!(engine.compute(req).is_ok());
+    }
+
+    #[test]
+    fn test_baseline_comparison_flags_regressed_symbol() {
         let engine = ContextEngine::from_prebuilt(
             PathBuf::from("semantic_data.json"),
             PathBuf::from("/repo"),
             test_graph(),
             Arc::new(MockReader),
         );
+        let current = engine.snapshot(true, PolicyKind::Academic).unwrap();
+        assert_eq!(current.symbols.len(), 2);
+
+        // Unchanged baseline: nothing to report
+        let clean = compare_to_baseline(&current, &current, 0.0);
+        assert!(clean.regressions.is_empty());
+
+        // Baseline recorded a smaller CF for func1: it has regressed since
+        let mut baseline = current.clone();
+        let func1_cf = current.symbols["sym/func1()."];
+        baseline.symbols.insert("sym/func1().".into(), func1_cf - 5);
+        baseline.symbols.insert("sym/gone().".into(), 1);
+        let result = compare_to_baseline(&baseline, &current, 10.0);
+        assert_eq!(result.regressions.len(), 1);
+        assert_eq!(result.regressions[0].symbol, "sym/func1().");
+        assert_eq!(result.regressions[0].baseline, func1_cf - 5);
+        assert_eq!(result.regressions[0].current, func1_cf);
+        assert_eq!(result.removed, vec!["sym/gone().".to_string()]);
+
+        // Within tolerance: not a regression
+        let lenient = compare_to_baseline(&baseline, &current, 100.0);
+        assert!(lenient.regressions.is_empty());
+    }
 
-        let health = engine.health();
-        assert_eq!(health.node_count, 2);
-        assert_eq!(health.edge_count, 1);
+    #[test]
+    fn test_diff_snapshots_sorts_by_absolute_delta() {
+        let snapshot = |entries: &[(&str, u32)]| CfSnapshot {
+            symbols: entries.iter().map(|&(s, cf)| (s.to_string(), cf)).collect(),
+        };
+        let old = snapshot(&[("a", 100), ("b", 50), ("c", 10), ("gone", 5)]);
+        let new = snapshot(&[("a", 90), ("b", 80), ("c", 12), ("fresh", 7)]);
+
+        let diff = diff_snapshots(&old, &new, 0);
+        let order: Vec<_> = diff.changes.iter().map(|c| c.symbol.as_str()).collect();
+        assert_eq!(order, vec!["b", "a", "c"]);
+        assert_eq!(diff.changes[1].delta, -10);
+        assert_eq!(diff.added, 1);
+        assert_eq!(diff.removed, 1);
+
+        // Deltas below the threshold are hidden
+        let diff = diff_snapshots(&old, &new, 10);
+        let order: Vec<_> = diff.changes.iter().map(|c| c.symbol.as_str()).collect();
+        assert_eq!(order, vec!["b", "a"]);
+    }
 
-        let res = engine
-            .compute(ComputeRequest {
-                symbols: vec!["sym/func1().".into()],
+    #[test]
+    fn test_traversal_steps_json_carries_pruning_inputs() {
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            test_graph(),
+            Arc::new(MockReader),
+        );
+        let ctx = engine
+            .context(ContextRequest {
+                symbol: "sym/func1().".into(),
                 policy: PolicyKind::Academic,
                 max_tokens: None,
+                max_depth: None,
+                include_code: false,
+                show_traversal: true,
+                with_callers: 0,
+                max_files: None,
             })
             .unwrap();
-        assert!(res.total_context_size > 0);
-        assert_eq!(res.reachable_node_count, 2);
-        assert!(!res.reachable_nodes_ordered.is_empty());
+
+        let steps = serde_json::to_value(ctx.traversal_steps.unwrap()).unwrap();
+        let steps = steps.as_array().unwrap();
+        assert_eq!(steps.len(), 2);
+        for step in steps {
+            for key in ["edge_kind", "decision"] {
+                assert!(step.get(key).is_some(), "missing {key} in {step}");
+            }
+            assert!(step["node"].get("doc_score").is_some());
+            assert!(step["node"].get("is_external").is_some());
+        }
+        for key in [
+            "is_signature_complete",
+            "is_di_wired",
+            "is_interface_method",
+        ] {
+            assert!(steps[0].get(key).is_some(), "missing {key} in {}", steps[0]);
+            // Function-only inputs are omitted for the variable step
+            assert!(
+                steps[1].get(key).is_none(),
+                "unexpected {key} in {}",
+                steps[1]
+            );
+        }
+        // func1 has no return type annotation
+        assert_eq!(steps[0]["is_signature_complete"], false);
+        assert_eq!(steps[1]["edge_kind"], "Read");
+        // Mutable variable is Transparent: no boundary reason
+        assert!(steps[1].get("boundary_reason").is_none());
     }
 
     #[test]
-    fn test_engine_search_and_top() {
+    fn test_context_groups_declared_exceptions_under_raises() {
+        let mut g = test_graph();
+        let mut core = make_core(2, "__init__", "app/errors.py", 0, 1);
+        core.scope = Some("sym/NotFound#".into());
+        let init = g.add_node(
+            "sym/NotFound#__init__().".into(),
+            Node::Function(FunctionNode {
+                core,
+                parameters: Vec::new(),
+                is_async: false,
+                is_generator: false,
+                visibility: Visibility::Public,
+                return_types: vec![],
+                is_interface_method: false,
+                is_constructor: true,
+                is_di_wired: false,
+            }),
+        );
+        let f1 = g.get_node_by_symbol("sym/func1().").unwrap();
+        g.add_edge(f1, init, EdgeKind::Raises);
         let engine = ContextEngine::from_prebuilt(
             PathBuf::from("semantic_data.json"),
             PathBuf::from("/repo"),
-            test_graph(),
+            g,
             Arc::new(MockReader),
         );
 
-        let search = engine
-            .search("func", true, None, true, PolicyKind::Academic)
+        let ctx = engine
+            .context(ContextRequest {
+                symbol: "sym/func1().".into(),
+                policy: PolicyKind::Academic,
+                max_tokens: None,
+                max_depth: None,
+                include_code: false,
+                show_traversal: true,
+                with_callers: 0,
+                max_files: None,
+            })
             .unwrap();
-        assert_eq!(search.total_matches, 1);
-        assert_eq!(search.items[0].symbol, "sym/func1().");
 
-        let top = engine.top(10, "all", true, PolicyKind::Academic).unwrap();
-        assert_eq!(top.items.len(), 2);
+        assert_eq!(ctx.raises.len(), 1);
+        assert_eq!(ctx.raises[0].type_symbol, "sym/NotFound#");
+        assert_eq!(ctx.raises[0].raised_by, vec!["sym/func1()."]);
+        let step = ctx
+            .traversal_steps
+            .unwrap()
+            .into_iter()
+            .find(|s| s.edge_kind.as_deref() == Some("Raises"))
+            .unwrap();
+        assert_eq!(step.boundary_reason.as_deref(), Some("RaisedType"));
     }
 
     #[test]
@@ -960,8 +3641,11 @@ mod tests {
                 symbol: "sym/func1().".into(),
                 policy: PolicyKind::Academic,
                 max_tokens: None,
+                max_depth: None,
                 include_code: true,
                 show_traversal: false,
+                with_callers: 0,
+                max_files: None,
             })
             .unwrap();
         assert_eq!(ctx.symbol, "sym/func1().");
@@ -975,6 +3659,71 @@ mod tests {
         assert!(any_code);
     }
 
+    #[test]
+    fn test_context_with_callers_lists_callers_without_changing_size() {
+        let function = |id, name: &str| {
+            Node::Function(FunctionNode {
+                core: make_core(id, name, "app/main.py", 0, 1),
+                parameters: Vec::new(),
+                is_async: false,
+                is_generator: false,
+                visibility: Visibility::Public,
+                return_types: vec![],
+                is_interface_method: false,
+                is_constructor: false,
+                is_di_wired: false,
+            })
+        };
+        // outer -> caller_a -> target, caller_b -> target
+        let mut g = ContextGraph::new();
+        let target = g.add_node("sym/target().".into(), function(0, "target"));
+        let caller_a = g.add_node("sym/caller_a().".into(), function(1, "caller_a"));
+        let caller_b = g.add_node("sym/caller_b().".into(), function(2, "caller_b"));
+        let outer = g.add_node("sym/outer().".into(), function(3, "outer"));
+        g.add_edge(caller_a, target, EdgeKind::Call);
+        g.add_edge(caller_b, target, EdgeKind::Call);
+        g.add_edge(outer, caller_a, EdgeKind::Call);
+
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            g,
+            Arc::new(MockReader),
+        );
+        let request = |with_callers| ContextRequest {
+            symbol: "sym/target().".into(),
+            policy: PolicyKind::Academic,
+            max_tokens: None,
+            max_depth: None,
+            include_code: false,
+            show_traversal: false,
+            with_callers,
+            max_files: None,
+        };
+
+        let plain = engine.context(request(0)).unwrap();
+        assert!(plain.callers.is_none());
+
+        let direct = engine.context(request(1)).unwrap();
+        assert_eq!(direct.total_context_size, plain.total_context_size);
+        let symbols: Vec<(u32, &str)> = direct
+            .callers
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|c| (c.hops, c.node.symbol.as_str()))
+            .collect();
+        assert_eq!(
+            symbols,
+            vec![(1, "sym/caller_a()."), (1, "sym/caller_b().")]
+        );
+
+        let two_hops = engine.context(request(2)).unwrap();
+        assert_eq!(two_hops.total_context_size, plain.total_context_size);
+        let last = two_hops.callers.unwrap().pop().unwrap();
+        assert_eq!((last.hops, last.node.symbol.as_str()), (2, "sym/outer()."));
+    }
+
     #[test]
     fn test_engine_reachable_reports_unresolved_and_witness_paths() {
         let engine = ContextEngine::from_prebuilt(
@@ -1042,6 +3791,8 @@ mod tests {
                 definition: TypeDefAttribute {
                     type_kind: TypeKind::Class,
                     is_abstract: false,
+                    is_data_class: false,
+                    is_frozen: false,
                     type_param_count: 0,
                     type_var_info: None,
                 },
@@ -1115,7 +3866,7 @@ mod tests {
 
         let i_run = g.add_node("pkg/Plugin#run().".into(), run);
         let i_render = g.add_node("pkg/Plugin#render().".into(), render);
-        let i_call = g.add_node("pkg/Plugin#__call__().".into(), call);
+        let _i_call = g.add_node("pkg/Plugin#__call__().".into(), call);
         let i_helper = g.add_node("pkg/Plugin#_helper().".into(), helper);
         let i_ext = g.add_node("lib/ext_func().".into(), ext_func);
 
@@ -1140,6 +3891,10 @@ mod tests {
                 symbols: vec!["pkg/Plugin#".into()],
                 policy: PolicyKind::Academic,
                 max_tokens: None,
+                max_depth: None,
+                func_doc_threshold: None,
+                interface_doc_threshold: None,
+                already_read: Vec::new(),
             })
             .unwrap();
 
@@ -1172,6 +3927,10 @@ mod tests {
                 symbols: vec!["sym/var1.".into()],
                 policy: PolicyKind::Academic,
                 max_tokens: None,
+                max_depth: None,
+                func_doc_threshold: None,
+                interface_doc_threshold: None,
+                already_read: Vec::new(),
             })
             .unwrap();
 
@@ -1224,6 +3983,10 @@ mod tests {
                 ],
                 policy: PolicyKind::Academic,
                 max_tokens: None,
+                max_depth: None,
+                func_doc_threshold: None,
+                interface_doc_threshold: None,
+                already_read: Vec::new(),
             })
             .unwrap();
 
@@ -1254,11 +4017,501 @@ mod tests {
                 symbols: vec!["nonexistent/symbol".into()],
                 policy: PolicyKind::Academic,
                 max_tokens: None,
+                max_depth: None,
+                func_doc_threshold: None,
+                interface_doc_threshold: None,
+                already_read: Vec::new(),
             })
             .unwrap_err();
         assert!(err.to_string().contains("not found"));
     }
 
+    #[test]
+    fn test_compute_lists_every_missing_symbol() {
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            test_graph(),
+            Arc::new(MockReader),
+        );
+
+        let err = engine
+            .compute(ComputeRequest {
+                symbols: vec![
+                    "missing/a".into(),
+                    "sym/func1().".into(),
+                    "missing/b".into(),
+                ],
+                policy: PolicyKind::Academic,
+                max_tokens: None,
+                max_depth: None,
+                func_doc_threshold: None,
+                interface_doc_threshold: None,
+                already_read: Vec::new(),
+            })
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Symbol not found: missing/a, missing/b");
+    }
+
+    #[test]
+    fn test_changed_lines_from_diff_tracks_new_side_lines() {
+        let diff = "\
+diff --git a/app/main.py b/app/main.py
+--- a/app/main.py
++++ b/app/main.py
+@@ -3,3 +3,4 @@ def a():
+ ctx
+-old
++new
++extra
+ ctx
+--- a/gone.py
++++ /dev/null
+@@ -1,1 +0,0 @@
+-bye
+--- a/sql.py
++++ b/sql.py
+@@ -1,2 +1,2 @@
+--- drop the old comment
++++ counter
+ keep
+";
+        let changed = changed_lines_from_diff(diff);
+        assert_eq!(changed.len(), 2);
+        assert_eq!(changed["app/main.py"], vec![4, 5]);
+        assert_eq!(changed["sql.py"], vec![1]);
+    }
+
+    #[test]
+    fn test_changed_cf_reports_only_touched_functions() {
+        // a (lines 1-3) calls c (lines 10-12); b (lines 5-7) is untouched
+        let function = |id, name: &str, start_line, end_line| {
+            Node::Function(FunctionNode {
+                core: make_core(id, name, "m.py", start_line, end_line),
+                parameters: Vec::new(),
+                is_async: false,
+                is_generator: false,
+                visibility: Visibility::Public,
+                return_types: vec![],
+                is_interface_method: false,
+                is_constructor: false,
+                is_di_wired: false,
+            })
+        };
+        let mut g = ContextGraph::new();
+        let a = g.add_node("m/a().".into(), function(0, "a", 0, 2));
+        g.add_node("m/b().".into(), function(1, "b", 4, 6));
+        let c = g.add_node("m/c().".into(), function(2, "c", 9, 11));
+        g.add_edge(a, c, EdgeKind::Call);
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            g,
+            Arc::new(MockReader),
+        );
+
+        let diff = "--- a/m.py\n+++ b/m.py\n@@ -1,3 +1,3 @@\n def a():\n-    pass\n+    c()\n \n";
+        let changed = changed_lines_from_diff(diff);
+        let res = engine
+            .changed_cf(&changed, Some(15), PolicyKind::Strict)
+            .unwrap();
+        assert_eq!(res.items.len(), 1);
+        assert_eq!(res.items[0].symbol, "m/a().");
+        assert_eq!(res.items[0].cf, 20);
+        assert!(res.items[0].over_budget);
+        assert_eq!(res.over_budget, 1);
+    }
+
+    #[test]
+    fn test_graph_export_whole_graph_and_reachable_set() {
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            test_graph(),
+            Arc::new(MockReader),
+        );
+
+        let all = engine.graph_export(None, PolicyKind::Academic).unwrap();
+        assert_eq!(all.nodes.len(), 2);
+        assert_eq!(all.edges.len(), 1);
+        let edge = &all.edges[0];
+        assert_eq!(all.nodes[edge.source].symbol, "sym/func1().");
+        assert_eq!(all.nodes[edge.target].symbol, "sym/var1.");
+        assert_eq!(edge.kind, "Read");
+
+        let reachable = engine
+            .graph_export(Some("sym/var1."), PolicyKind::Academic)
+            .unwrap();
+        assert_eq!(reachable.nodes.len(), 1);
+        assert!(reachable.edges.is_empty());
+    }
+
+    #[test]
+    fn test_ubiquity_ranks_shared_leaf_above_niche_one() {
+        // a, b, c all call shared; only c calls niche
+        let mut g = ContextGraph::new();
+        let mut idx = HashMap::new();
+        for (id, name) in ["a", "b", "c", "shared", "niche"].iter().enumerate() {
+            let f = Node::Function(FunctionNode {
+                core: make_core(id as u32, name, "m.py", id as u32, id as u32),
+                parameters: Vec::new(),
+                is_async: false,
+                is_generator: false,
+                visibility: Visibility::Public,
+                return_types: vec![],
+                is_interface_method: false,
+                is_constructor: false,
+                is_di_wired: false,
+            });
+            idx.insert(*name, g.add_node(format!("m/{name}()."), f));
+        }
+        for caller in ["a", "b", "c"] {
+            g.add_edge(idx[caller], idx["shared"], EdgeKind::Call);
+        }
+        g.add_edge(idx["c"], idx["niche"], EdgeKind::Call);
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            g,
+            Arc::new(MockReader),
+        );
+
+        let res = engine.ubiquity(10, true, PolicyKind::Strict).unwrap();
+        assert_eq!(res.start_count, 5);
+        let count = |symbol: &str| {
+            res.items
+                .iter()
+                .find(|item| item.symbol == symbol)
+                .map_or(0, |item| item.reachable_set_count)
+        };
+        assert_eq!(res.items[0].symbol, "m/shared().");
+        assert_eq!(count("m/shared()."), 3);
+        assert_eq!(count("m/niche()."), 1);
+        assert!(count("m/shared().") > count("m/niche()."));
+
+        let top1 = engine.ubiquity(1, true, PolicyKind::Strict).unwrap();
+        assert_eq!(top1.items.len(), 1);
+    }
+
+    #[test]
+    fn test_pareto_top_five_percent_share_of_skewed_distribution() {
+        // 19 small functions and one hub: the top 5% is the hub alone
+        let mut g = ContextGraph::new();
+        for id in 0..20u32 {
+            let mut core = make_core(id, &format!("f{id}"), "m.py", id, id);
+            core.context_size = if id == 7 { 1000 } else { 10 };
+            let f = Node::Function(FunctionNode {
+                core,
+                parameters: Vec::new(),
+                is_async: false,
+                is_generator: false,
+                visibility: Visibility::Public,
+                return_types: vec![],
+                is_interface_method: false,
+                is_constructor: false,
+                is_di_wired: false,
+            });
+            g.add_node(format!("m/f{id}()."), f);
+        }
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            g,
+            Arc::new(MockReader),
+        );
+
+        let res = engine.pareto(95, true, PolicyKind::Academic).unwrap();
+        assert_eq!(res.function_count, 20);
+        assert_eq!(res.cutoff_cf, 10);
+        assert_eq!(res.total_cf, 19 * 10 + 1000);
+        assert_eq!(res.items.len(), 1);
+        assert_eq!(res.items[0].symbol, "m/f7().");
+        assert!((res.share_of_total - 1000.0 / 1190.0).abs() < 1e-9);
+
+        assert!(engine.pareto(97, true, PolicyKind::Academic).is_err());
+    }
+
+    #[test]
+    fn test_coverage_reports_share_reachable_from_entry_points() {
+        // main -> handler; orphan -> helper is never reached; test_main is test code
+        let mut g = ContextGraph::new();
+        let mut idx = HashMap::new();
+        for (id, (name, file)) in [
+            ("main", "app/main.py"),
+            ("handler", "app/main.py"),
+            ("orphan", "app/legacy.py"),
+            ("helper", "app/legacy.py"),
+            ("test_main", "tests/test_main.py"),
+        ]
+        .iter()
+        .enumerate()
+        {
+            let f = Node::Function(FunctionNode {
+                core: make_core(id as u32, name, file, id as u32, id as u32),
+                parameters: Vec::new(),
+                is_async: false,
+                is_generator: false,
+                visibility: Visibility::Public,
+                return_types: vec![],
+                is_interface_method: false,
+                is_constructor: false,
+                is_di_wired: false,
+            });
+            idx.insert(*name, g.add_node(format!("m/{name}()."), f));
+        }
+        g.add_edge(idx["main"], idx["handler"], EdgeKind::Call);
+        g.add_edge(idx["orphan"], idx["helper"], EdgeKind::Call);
+        g.add_edge(idx["test_main"], idx["main"], EdgeKind::Call);
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            g,
+            Arc::new(MockReader),
+        );
+
+        let entry = Regex::new(r"^m/main\(\)\.$").unwrap();
+        let res = engine.coverage(&entry, false, 0.8).unwrap();
+        assert_eq!(res.entry_points, ["m/main()."]);
+        assert_eq!((res.reachable_nodes, res.total_nodes), (2, 4));
+        assert_eq!(res.coverage_percent, 50.0);
+        assert_eq!(res.modules[0].module, "app/legacy.py");
+        assert!(res.modules[0].likely_dead);
+        assert!(!res.modules[1].likely_dead);
+
+        let with_tests = engine.coverage(&entry, true, 0.8).unwrap();
+        assert_eq!(with_tests.total_nodes, 5);
+
+        let none = Regex::new("nothing").unwrap();
+        assert!(engine.coverage(&none, false, 0.8).is_err());
+    }
+
+    #[test]
+    fn test_path_reports_hops_or_unreachable() {
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            test_graph(),
+            Arc::new(MockReader),
+        );
+
+        let res = engine
+            .path("sym/func1().", "sym/var1.", PolicyKind::Academic)
+            .unwrap();
+        assert!(res.reachable);
+        assert_eq!(res.hops.len(), 1);
+        let hop = &res.hops[0];
+        assert_eq!(hop.source, "sym/func1().");
+        assert_eq!(hop.target, "sym/var1.");
+        assert_eq!(hop.edge_kind, "Read");
+        assert_eq!(hop.decision.as_deref(), Some("Transparent"));
+
+        // Nothing writes var1, so nothing leads back to func1
+        let res = engine
+            .path("sym/var1.", "sym/func1().", PolicyKind::Academic)
+            .unwrap();
+        assert!(!res.reachable);
+        assert!(res.hops.is_empty());
+
+        assert!(
+            engine
+                .path("sym/func1().", "sym/missing.", PolicyKind::Academic)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_set_diff_partitions_union_into_exclusive_and_shared() {
+        // a -> shared, a -> only_a; b -> shared, b -> only_b (all transparent under Strict)
+        let mut g = ContextGraph::new();
+        let mut idx = HashMap::new();
+        for (id, name) in ["a", "b", "shared", "only_a", "only_b"].iter().enumerate() {
+            let mut core = make_core(id as u32, name, "m.py", id as u32, id as u32);
+            core.context_size = 10 * (id as u32 + 1);
+            let f = Node::Function(FunctionNode {
+                core,
+                parameters: Vec::new(),
+                is_async: false,
+                is_generator: false,
+                visibility: Visibility::Public,
+                return_types: vec![],
+                is_interface_method: false,
+                is_constructor: false,
+                is_di_wired: false,
+            });
+            idx.insert(*name, g.add_node(format!("m/{name}()."), f));
+        }
+        for (from, to) in [
+            ("a", "shared"),
+            ("a", "only_a"),
+            ("b", "shared"),
+            ("b", "only_b"),
+        ] {
+            g.add_edge(idx[from], idx[to], EdgeKind::Call);
+        }
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            g,
+            Arc::new(MockReader),
+        );
+
+        let res = engine
+            .set_diff(
+                &["m/a().".to_string()],
+                &["m/b().".to_string()],
+                PolicyKind::Strict,
+            )
+            .unwrap();
+        assert_eq!(res.only_a.symbols, ["m/a().", "m/only_a()."]);
+        assert_eq!(res.only_b.symbols, ["m/b().", "m/only_b()."]);
+        assert_eq!(res.shared.symbols, ["m/shared()."]);
+        assert_eq!(res.only_a.context_size, 10 + 40);
+        assert_eq!(res.only_b.context_size, 20 + 50);
+        assert_eq!(res.shared.context_size, 30);
+        assert_eq!(res.cf_a, res.only_a.context_size + res.shared.context_size);
+        assert_eq!(res.cf_b, res.only_b.context_size + res.shared.context_size);
+
+        let union = engine
+            .set_diff(
+                &["m/a().".to_string(), "m/b().".to_string()],
+                &[],
+                PolicyKind::Strict,
+            )
+            .unwrap();
+        assert_eq!(
+            union.only_a.context_size,
+            res.only_a.context_size + res.only_b.context_size + res.shared.context_size
+        );
+        assert_eq!(union.only_a.node_count, 5);
+    }
+
+    #[test]
+    fn test_matrix_matches_reachability() {
+        // a -> b -> c (all transparent under Strict)
+        let mut g = ContextGraph::new();
+        let mut idx = Vec::new();
+        for (id, name) in ["a", "b", "c"].iter().enumerate() {
+            let f = Node::Function(FunctionNode {
+                core: make_core(id as u32, name, "m.py", id as u32, id as u32),
+                parameters: Vec::new(),
+                is_async: false,
+                is_generator: false,
+                visibility: Visibility::Public,
+                return_types: vec![],
+                is_interface_method: false,
+                is_constructor: false,
+                is_di_wired: false,
+            });
+            idx.push(g.add_node(format!("m/{name}()."), f));
+        }
+        g.add_edge(idx[0], idx[1], EdgeKind::Call);
+        g.add_edge(idx[1], idx[2], EdgeKind::Call);
+
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            g,
+            Arc::new(MockReader),
+        );
+        let symbols = vec!["m/a().".to_string(), "m/b().".into(), "m/c().".into()];
+
+        let matrix = engine.matrix(&symbols, false, PolicyKind::Strict).unwrap();
+        assert_eq!(matrix.symbols, symbols);
+        assert_eq!(
+            matrix.rows,
+            vec![vec![1, 1, 1], vec![0, 1, 1], vec![0, 0, 1]]
+        );
+
+        let weighted = engine.matrix(&symbols, true, PolicyKind::Strict).unwrap();
+        assert_eq!(weighted.rows[0], vec![10, 10, 10]);
+        assert_eq!(weighted.rows[2], vec![0, 0, 10]);
+
+        assert!(
+            engine
+                .matrix(&["m/missing().".to_string()], false, PolicyKind::Strict)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_search_regex_and_top_filter_match_full_symbol() {
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            test_graph(),
+            Arc::new(MockReader),
+        );
+
+        let regex = engine
+            .search(
+                r"^sym/\w+\(\)\.$",
+                true,
+                false,
+                None,
+                true,
+                PolicyKind::Academic,
+            )
+            .unwrap();
+        assert_eq!(regex.total_matches, 1);
+        assert_eq!(regex.items[0].symbol, "sym/func1().");
+        // Without the flag the same text is a (non-matching) substring
+        let substring = engine
+            .search(r"^sym/\w+", false, false, None, true, PolicyKind::Academic)
+            .unwrap();
+        assert_eq!(substring.total_matches, 0);
+
+        let top = engine
+            .top(10, "all", Some(r"var\d"), None, true, PolicyKind::Academic)
+            .unwrap();
+        let symbols: Vec<_> = top.items.iter().map(|item| item.symbol.as_str()).collect();
+        assert_eq!(symbols, ["sym/var1."]);
+
+        let err = engine
+            .search("(", true, false, None, true, PolicyKind::Academic)
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid regex"));
+        assert!(
+            engine
+                .top(10, "all", Some("["), None, true, PolicyKind::Academic)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_top_min_cf_keeps_nodes_at_or_above_threshold() {
+        let engine = ContextEngine::from_prebuilt(
+            PathBuf::from("semantic_data.json"),
+            PathBuf::from("/repo"),
+            test_graph(),
+            Arc::new(MockReader),
+        );
+        let all = engine
+            .top(10, "all", None, None, true, PolicyKind::Academic)
+            .unwrap();
+        assert_eq!(all.items.len(), 2);
+        let highest = all.items[0].cf;
+        assert!(all.items[1].cf < highest);
+
+        let heavy = engine
+            .top(
+                usize::MAX,
+                "all",
+                None,
+                Some(highest),
+                true,
+                PolicyKind::Academic,
+            )
+            .unwrap();
+        assert_eq!(heavy.items.len(), 1);
+        assert_eq!(heavy.items[0].symbol, all.items[0].symbol);
+
+        // The limit still caps the thresholded list
+        let capped = engine
+            .top(0, "all", None, Some(1), true, PolicyKind::Academic)
+            .unwrap();
+        assert!(capped.items.is_empty());
+    }
+
     #[test]
     fn test_search_returns_class_symbols() {
         let engine = ContextEngine::from_prebuilt(
@@ -1269,7 +4522,7 @@ mod tests {
         );
 
         let result = engine
-            .search("Plugin", false, None, true, PolicyKind::Academic)
+            .search("Plugin", false, false, None, true, PolicyKind::Academic)
             .unwrap();
 
         let class_items: Vec<_> = result
@@ -1282,6 +4535,25 @@ mod tests {
             "Search should return class symbols from TypeRegistry"
         );
         assert_eq!(class_items[0].symbol, "pkg/Plugin#");
+
+        // Regex patterns apply to type ids the same way as to node symbols
+        let regex = engine
+            .search(
+                r"^pkg/Plugin#$",
+                true,
+                false,
+                None,
+                true,
+                PolicyKind::Academic,
+            )
+            .unwrap();
+        let symbols: Vec<_> = regex
+            .items
+            .iter()
+            .map(|item| item.symbol.as_str())
+            .collect();
+        assert_eq!(symbols, ["pkg/Plugin#"]);
+        assert_eq!(regex.items[0].node_type, "class");
     }
 
     #[test]
@@ -1312,7 +4584,7 @@ mod tests {
         );
 
         let result = engine
-            .search("pkg", false, None, true, PolicyKind::Academic)
+            .search("pkg", false, false, None, true, PolicyKind::Academic)
             .unwrap();
 
         let kinds: Vec<_> = result.items.iter().map(|i| i.node_type.as_str()).collect();
@@ -1331,6 +4603,8 @@ mod tests {
                 definition: TypeDefAttribute {
                     type_kind: TypeKind::Class,
                     is_abstract: false,
+                    is_data_class: false,
+                    is_frozen: false,
                     type_param_count: 0,
                     type_var_info: None,
                 },
@@ -1,14 +1,36 @@
 use crate::adapters::doc_scorer::heuristic::HeuristicDocScorer;
 use crate::adapters::size_function::tiktoken::TiktokenSizeFunction;
-use crate::app::dto::{ComputeRequest, ContextRequest, PolicyKind, ReachabilityRequest};
-use crate::app::engine::ContextEngine;
+use crate::app::dto::{
+    CfSnapshot, ComputeRequest, ComputeResponse, ContextRequest, FitnessConfig, GraphExport,
+    NeighborDirection, NeighborsResponse, PolicyKind, ReachabilityRequest, StatsResponse,
+    TopResponse, TraversalStepDto,
+};
+use crate::app::engine::{
+    ContextEngine, changed_lines_from_diff, compare_to_baseline, diff_snapshots,
+};
 use crate::domain::builder::GraphBuilder;
 use crate::domain::node::Node;
 use crate::domain::ports::SourceReader;
 use crate::domain::semantic::SemanticData;
 use anyhow::{Context as _, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write as _;
 use std::path::Path;
 
+/// Write the SemanticData read from `json_path` (JSON or LSIF) to `out` as JSON.
+pub fn dump_semantic_data(json_path: &Path, out: &Path) -> Result<()> {
+    let semantic_data = ContextEngine::load_semantic_data(json_path)?;
+    let json = serde_json::to_string(&semantic_data)?;
+    std::fs::write(out, json)
+        .with_context(|| format!("Failed to write SemanticData: {}", out.display()))?;
+    println!(
+        "Wrote SemanticData ({} documents) to {}",
+        semantic_data.documents.len(),
+        out.display()
+    );
+    Ok(())
+}
+
 /// Build graph from SemanticData JSON file and print the graph structure as JSON.
 pub fn debug_graph_data(json_path: &Path) -> Result<()> {
     let json_content = std::fs::read_to_string(json_path).context("Failed to read JSON file")?;
@@ -66,10 +88,7 @@ pub fn debug_graph_data(json_path: &Path) -> Result<()> {
             let target_node = graph.node(target_idx);
             edges_out.push(serde_json::json!({
                 "target": target_node.core().name,
-                "target_symbol": graph.symbol_to_node.iter()
-                    .find(|&(_, &v)| v == target_idx)
-                    .map(|(k, _)| k.as_str())
-                    .unwrap_or("unknown"),
+                "target_symbol": graph.symbol_of(target_idx).unwrap_or("unknown"),
                 "kind": format!("{:?}", edge_kind),
             }));
         }
@@ -102,13 +121,7 @@ pub fn debug_graph_data(json_path: &Path) -> Result<()> {
             }
         }
 
-        let symbol = graph
-            .symbol_to_node
-            .iter()
-            .find(|&(_, &v)| v == idx)
-            .map(|(k, _)| k.clone())
-            .unwrap_or_default();
-        node_json["symbol"] = serde_json::json!(symbol);
+        node_json["symbol"] = serde_json::json!(graph.symbol_of(idx).unwrap_or_default());
 
         nodes.push(node_json);
     }
@@ -124,13 +137,54 @@ pub fn debug_graph_data(json_path: &Path) -> Result<()> {
     Ok(())
 }
 
-pub fn compute_cf_for_symbols(engine: &ContextEngine, symbols: &[String]) -> Result<()> {
-    println!("Computing CF for symbols: {:?}", symbols);
+pub fn compute_cf_for_symbols(
+    engine: &ContextEngine,
+    symbols: &[String],
+    already_read: &[String],
+    max_depth: Option<u32>,
+    weighted: bool,
+    summary_only: bool,
+    json_output: bool,
+) -> Result<()> {
+    if !summary_only && !json_output {
+        println!("Computing CF for symbols: {:?}", symbols);
+        if !already_read.is_empty() {
+            println!("Excluding {} already-read symbol(s)", already_read.len());
+        }
+    }
     let result = engine.compute(ComputeRequest {
         symbols: symbols.to_vec(),
         policy: PolicyKind::Academic,
         max_tokens: None,
+        max_depth,
+        func_doc_threshold: None,
+        interface_doc_threshold: None,
+        already_read: already_read.to_vec(),
     })?;
+    print_compute_response(&result, weighted, summary_only, json_output)
+}
+
+fn print_compute_response(
+    result: &ComputeResponse,
+    weighted: bool,
+    summary_only: bool,
+    json_output: bool,
+) -> Result<()> {
+    if json_output {
+        return print_json(result);
+    }
+    if summary_only {
+        println!("Total context size: {} tokens", result.total_context_size);
+        if weighted {
+            println!(
+                "Weighted context size: {} tokens",
+                result.weighted_context_size
+            );
+        }
+        println!("Reachable nodes: {}", result.reachable_node_count);
+        println!("Boundaries: {}", result.boundary_count);
+        return Ok(());
+    }
 
     if let Some(resolutions) = &result.anchor_resolutions {
         println!("\nAnchor Resolutions:");
@@ -153,26 +207,130 @@ pub fn compute_cf_for_symbols(engine: &ContextEngine, symbols: &[String]) -> Res
     println!("\nCF Result:");
     println!("  Starting symbols: {}", result.starting_symbols.len());
     println!("  Total context size: {} tokens", result.total_context_size);
+    if weighted {
+        println!(
+            "  Weighted context size: {} tokens (boundaries by signature)",
+            result.weighted_context_size
+        );
+    }
+    println!("  Unique source lines: {}", result.unique_source_lines);
     println!("  Reachable nodes: {}", result.reachable_node_count);
+    println!("  Boundaries: {}", result.boundary_count);
+    println!("  Files touched: {}", result.files_touched.len());
+
+    Ok(())
+}
+
+/// Compute CF for the innermost definition at `file:line` (1-based line).
+pub fn compute_cf_at(
+    engine: &ContextEngine,
+    file: &str,
+    line: u32,
+    summary_only: bool,
+    json_output: bool,
+) -> Result<()> {
+    let symbol = engine.symbol_at(file, line)?;
+    if !summary_only && !json_output {
+        println!("{}:{} -> {}", file, line, symbol);
+    }
+    compute_cf_for_symbols(
+        engine,
+        &[symbol],
+        &[],
+        None,
+        false,
+        summary_only,
+        json_output,
+    )
+}
 
+/// CF of everything defined in `file`, treating the file's symbols as one entry-point set.
+pub fn compute_cf_for_file(
+    engine: &ContextEngine,
+    file: &str,
+    summary_only: bool,
+    json_output: bool,
+) -> Result<()> {
+    let symbols = engine.symbols_in_file(file)?;
+    if !summary_only && !json_output {
+        println!("{} -> {} symbols", file, symbols.len());
+    }
+    let result = engine.compute(ComputeRequest {
+        symbols,
+        policy: PolicyKind::Academic,
+        max_tokens: None,
+        max_depth: None,
+        func_doc_threshold: None,
+        interface_doc_threshold: None,
+        already_read: Vec::new(),
+    })?;
+    print_compute_response(&result, false, summary_only, json_output)?;
+    if !summary_only && !json_output {
+        for path in &result.files_touched {
+            println!("    {}", path);
+        }
+    }
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn display_top_cf_nodes(
     engine: &ContextEngine,
-    limit: usize,
+    limit: Option<usize>,
     node_type: &str,
+    filter: Option<&str>,
+    min_cf: Option<u32>,
     include_tests: bool,
+    include_variables: bool,
+    summary_only: bool,
+    json_output: bool,
 ) -> Result<()> {
-    println!("Computing CF for all nodes...");
-    let result = engine.top(limit, node_type, include_tests, PolicyKind::Academic)?;
+    if !summary_only && !json_output {
+        println!("Computing CF for all nodes...");
+    }
+    let node_type = if include_variables {
+        format!("{node_type},variable")
+    } else {
+        node_type.to_string()
+    };
+    let result = engine.top(
+        top_limit(limit, min_cf),
+        &node_type,
+        filter,
+        min_cf,
+        include_tests,
+        PolicyKind::Academic,
+    )?;
+
+    if json_output {
+        return print_json(&result);
+    }
+    if summary_only {
+        let cfs: Vec<u32> = result.items.iter().map(|item| item.cf).collect();
+        println!("Nodes listed: {}", cfs.len());
+        println!("Highest CF: {} tokens", cfs.first().copied().unwrap_or(0));
+        println!("Lowest CF: {} tokens", cfs.last().copied().unwrap_or(0));
+        let total: u64 = cfs.iter().map(|&cf| cf as u64).sum();
+        println!("Total CF: {} tokens", total);
+        return Ok(());
+    }
 
     let filter_msg = if !include_tests {
         " (excluding tests)"
     } else {
         ""
     };
-    println!("\nTop {} nodes by Context Footprint{}:", limit, filter_msg);
+    match min_cf {
+        Some(min) => println!(
+            "\nNodes with Context Footprint >= {} tokens{}:",
+            min, filter_msg
+        ),
+        None => println!(
+            "\nTop {} nodes by Context Footprint{}:",
+            top_limit(limit, None),
+            filter_msg
+        ),
+    }
     println!("{}", "=".repeat(80));
 
     for (i, item) in result.items.iter().enumerate() {
@@ -184,16 +342,453 @@ pub fn display_top_cf_nodes(
     Ok(())
 }
 
+/// Print the top CF nodes as CSV rows of `rank,node_type,symbol,cf_tokens`.
+#[allow(clippy::too_many_arguments)]
+pub fn display_top_csv(
+    engine: &ContextEngine,
+    limit: Option<usize>,
+    node_type: &str,
+    filter: Option<&str>,
+    min_cf: Option<u32>,
+    include_tests: bool,
+    include_variables: bool,
+    header: bool,
+) -> Result<()> {
+    let node_type = if include_variables {
+        format!("{node_type},variable")
+    } else {
+        node_type.to_string()
+    };
+    let result = engine.top(
+        top_limit(limit, min_cf),
+        &node_type,
+        filter,
+        min_cf,
+        include_tests,
+        PolicyKind::Academic,
+    )?;
+    print!("{}", render_top_csv(&result, header));
+    Ok(())
+}
+
+/// `top` shows 10 nodes by default, or every node past the threshold when `--min-cf` is set.
+fn top_limit(limit: Option<usize>, min_cf: Option<u32>) -> usize {
+    limit.unwrap_or(if min_cf.is_some() { usize::MAX } else { 10 })
+}
+
+pub fn render_top_csv(result: &TopResponse, header: bool) -> String {
+    let mut out = String::new();
+    if header {
+        out.push_str("rank,node_type,symbol,cf_tokens\n");
+    }
+    for (i, item) in result.items.iter().enumerate() {
+        out.push_str(&format!(
+            "{},{},{},{}\n",
+            i + 1,
+            csv_field(&item.node_type),
+            csv_field(&item.symbol),
+            item.cf
+        ));
+    }
+    out
+}
+
+/// Print the functions above the CF `percentile` cutoff and the share of total CF they hold.
+pub fn display_pareto(
+    engine: &ContextEngine,
+    percentile: u32,
+    include_tests: bool,
+    json_output: bool,
+) -> Result<()> {
+    if !json_output {
+        println!("Computing CF for all functions...");
+    }
+    let result = engine.pareto(percentile, include_tests, PolicyKind::Academic)?;
+    if json_output {
+        return print_json(&result);
+    }
+
+    println!(
+        "\n{} of {} functions above P{} ({} tokens) account for {:.1}% of total CF ({} tokens)",
+        result.items.len(),
+        result.function_count,
+        result.percentile,
+        result.cutoff_cf,
+        result.share_of_total * 100.0,
+        result.total_cf
+    );
+    println!("{}", "=".repeat(80));
+    for (i, item) in result.items.iter().enumerate() {
+        println!("{}. [{}] {} tokens", i + 1, item.node_type, item.cf);
+        println!("   {}", item.symbol);
+        println!();
+    }
+
+    Ok(())
+}
+
+pub fn display_ubiquity(
+    engine: &ContextEngine,
+    limit: usize,
+    include_tests: bool,
+    json_output: bool,
+) -> Result<()> {
+    if !json_output {
+        println!("Computing reachable sets for all nodes...");
+    }
+    let result = engine.ubiquity(limit, include_tests, PolicyKind::Academic)?;
+    if json_output {
+        return print_json(&result);
+    }
+
+    println!(
+        "\nTop {} most ubiquitous nodes (across {} reachable sets):",
+        limit, result.start_count
+    );
+    println!("{}", "=".repeat(80));
+    for (i, item) in result.items.iter().enumerate() {
+        println!(
+            "{}. [{}] in {} reachable sets",
+            i + 1,
+            item.node_type,
+            item.reachable_set_count
+        );
+        println!("   {}", item.symbol);
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Print the share of code reachable from the entry points, with likely-dead modules.
+pub fn display_coverage(
+    engine: &ContextEngine,
+    entrypoints: &str,
+    include_tests: bool,
+    dead_threshold: f64,
+    json_output: bool,
+) -> Result<()> {
+    let pattern = regex::Regex::new(entrypoints)
+        .with_context(|| format!("Invalid --entrypoints: {entrypoints}"))?;
+    let result = engine.coverage(&pattern, include_tests, dead_threshold)?;
+    if json_output {
+        return print_json(&result);
+    }
+
+    println!(
+        "Entry points: {} | Reachable: {} of {} nodes ({:.1}%)",
+        result.entry_points.len(),
+        result.reachable_nodes,
+        result.total_nodes,
+        result.coverage_percent
+    );
+    println!("{}", "=".repeat(80));
+    for module in &result.modules {
+        let marker = if module.likely_dead {
+            " (likely dead)"
+        } else {
+            ""
+        };
+        println!(
+            "  {:>5.1}% unreachable  {}/{}  {}{}",
+            module.unreachable_ratio * 100.0,
+            module.total_nodes - module.reachable_nodes,
+            module.total_nodes,
+            module.module,
+            marker
+        );
+    }
+    println!("{}", "=".repeat(80));
+    Ok(())
+}
+
+/// Read a symbol list: one symbol per line, blank lines and `#` comments ignored.
+pub fn read_symbols_file(path: &Path) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read symbols file: {}", path.display()))?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(String::from)
+        .collect())
+}
+
+/// Print the pairwise reachability matrix for the symbols listed in `symbols_file`
+/// (one per line; blank lines and `#` comments ignored) as CSV.
+///
+/// Rows are streamed as they are computed, so memory stays at one row for large symbol sets.
+pub fn display_matrix_csv(
+    engine: &ContextEngine,
+    symbols_file: &Path,
+    weighted: bool,
+) -> Result<()> {
+    let symbols = read_symbols_file(symbols_file)?;
+    let mut out = std::io::stdout().lock();
+
+    let header: Vec<String> = std::iter::once(String::new())
+        .chain(symbols.iter().map(|s| csv_field(s)))
+        .collect();
+    writeln!(out, "{}", header.join(","))?;
+    engine.matrix_rows(&symbols, weighted, PolicyKind::Academic, |symbol, row| {
+        let cells: Vec<String> = std::iter::once(csv_field(symbol))
+            .chain(row.iter().map(|v| v.to_string()))
+            .collect();
+        writeln!(out, "{}", cells.join(","))?;
+        Ok(())
+    })
+}
+
+/// Print the nodes reachable only from set A, only from set B, and from both, for the symbols
+/// listed in `set_a_file` and `set_b_file` (one per line).
+pub fn display_set_diff(
+    engine: &ContextEngine,
+    set_a_file: &Path,
+    set_b_file: &Path,
+    json_output: bool,
+) -> Result<()> {
+    let set_a = read_symbols_file(set_a_file)?;
+    let set_b = read_symbols_file(set_b_file)?;
+    let result = engine.set_diff(&set_a, &set_b, PolicyKind::Academic)?;
+    if json_output {
+        return print_json(&result);
+    }
+
+    println!("CF of set A: {} tokens", result.cf_a);
+    println!("CF of set B: {} tokens", result.cf_b);
+    for (title, part) in [
+        ("Only A", &result.only_a),
+        ("Only B", &result.only_b),
+        ("Shared", &result.shared),
+    ] {
+        println!(
+            "\n{}: {} nodes, {} tokens",
+            title, part.node_count, part.context_size
+        );
+        println!("{}", "=".repeat(80));
+        for symbol in &part.symbols {
+            println!("  {}", symbol);
+        }
+    }
+
+    Ok(())
+}
+
+/// Print the hops by which traversal from `from` reaches `to`, one `source --Kind--> target`
+/// line each with its pruning decision.
+pub fn display_path(engine: &ContextEngine, from: &str, to: &str, json_output: bool) -> Result<()> {
+    let result = engine.path(from, to, PolicyKind::Academic)?;
+    if json_output {
+        return print_json(&result);
+    }
+
+    if !result.reachable {
+        println!("{} is not in the context of {}", to, from);
+        return Ok(());
+    }
+    println!("Path from {} to {} ({} hops):", from, to, result.hops.len());
+    println!("{}", "=".repeat(80));
+    for hop in &result.hops {
+        let via = if hop.reached_via == "Forward" {
+            String::new()
+        } else {
+            format!(" (via {})", hop.reached_via)
+        };
+        let decision = match (&hop.decision, &hop.boundary_reason) {
+            (Some(d), Some(reason)) => format!("  [{d}: {reason}]"),
+            (Some(d), None) => format!("  [{d}]"),
+            _ => String::new(),
+        };
+        println!(
+            "  {} --{}--> {}{}{}",
+            hop.source, hop.edge_kind, hop.target, via, decision
+        );
+    }
+
+    Ok(())
+}
+
+pub fn display_neighbors(
+    engine: &ContextEngine,
+    symbol: &str,
+    direction: NeighborDirection,
+    json_output: bool,
+) -> Result<()> {
+    let result = engine.neighbors(symbol, direction)?;
+    if json_output {
+        return print_json(&result);
+    }
+    print!("{}", render_neighbors(&result));
+    Ok(())
+}
+
+/// Render a node's neighbors grouped by edge kind, with `->` for outgoing and `<-` for
+/// incoming edges.
+pub fn render_neighbors(result: &NeighborsResponse) -> String {
+    let mut out = format!(
+        "{} ({}, {} tokens): {} neighbors\n{}\n",
+        result.node.symbol,
+        result.node.node_type,
+        result.node.context_size,
+        result.neighbors.len(),
+        "=".repeat(80)
+    );
+    let mut by_kind: BTreeMap<&str, Vec<_>> = BTreeMap::new();
+    for neighbor in &result.neighbors {
+        by_kind
+            .entry(neighbor.edge_kind.as_str())
+            .or_default()
+            .push(neighbor);
+    }
+    for (kind, neighbors) in by_kind {
+        out.push_str(&format!("{}:\n", kind));
+        for neighbor in neighbors {
+            let arrow = match neighbor.direction {
+                NeighborDirection::In => "<-",
+                _ => "->",
+            };
+            out.push_str(&format!(
+                "  {} {} ({} tokens, {})\n",
+                arrow, neighbor.node.symbol, neighbor.node.context_size, neighbor.node.file_path
+            ));
+        }
+    }
+    out
+}
+
+/// Quote a CSV field (RFC 4180) when it contains a delimiter, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn display_duplicates(
+    engine: &ContextEngine,
+    min_size: u32,
+    include_tests: bool,
+    json_output: bool,
+) -> Result<()> {
+    if !json_output {
+        println!("Hashing normalized function bodies...");
+    }
+    let result = engine.duplicates(min_size, include_tests, PolicyKind::Academic)?;
+    if json_output {
+        return print_json(&result);
+    }
+
+    let total_saved: u32 = result.groups.iter().map(|g| g.saved_cf).sum();
+    println!(
+        "\nFound {} duplicate groups (CF saved by deduplication: {} tokens):",
+        result.groups.len(),
+        total_saved
+    );
+    println!("{}", "=".repeat(80));
+
+    for (i, group) in result.groups.iter().enumerate() {
+        println!(
+            "{}. {} copies, {} tokens each, saves {} tokens of CF",
+            i + 1,
+            group.members.len(),
+            group.context_size,
+            group.saved_cf
+        );
+        for member in &group.members {
+            println!("   {} (CF {})", member.symbol, member.cf);
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Print every call cycle with its members and combined context size, then the functions
+/// that call themselves.
+pub fn display_cycles(engine: &ContextEngine, json_output: bool) -> Result<()> {
+    let result = engine.cycles();
+    if json_output {
+        return print_json(&result);
+    }
+
+    println!("\nFound {} call cycles:", result.cycles.len());
+    println!("{}", "=".repeat(80));
+    for (i, cycle) in result.cycles.iter().enumerate() {
+        println!(
+            "{}. {} functions, {} tokens combined",
+            i + 1,
+            cycle.symbols.len(),
+            cycle.context_size
+        );
+        for symbol in &cycle.symbols {
+            println!("   {}", symbol);
+        }
+        println!();
+    }
+
+    println!("Self-recursive functions: {}", result.self_calls.len());
+    for symbol in &result.self_calls {
+        println!("   {}", symbol);
+    }
+
+    Ok(())
+}
+
+/// Print the unresolved-call report to stderr, so it can accompany any command's output.
+pub fn report_unresolved(engine: &ContextEngine, limit: usize) {
+    let report = engine.unresolved(limit);
+    eprintln!("\nUnresolved calls: {}", report.total);
+    if report.total == 0 {
+        return;
+    }
+    eprintln!("{}", "=".repeat(80));
+    eprintln!("By file:");
+    for item in &report.by_file {
+        eprintln!("  {:>6}  {}", item.count, item.name);
+    }
+    eprintln!("Top callees:");
+    for item in &report.top_callees {
+        match &item.example_receiver {
+            Some(receiver) => {
+                eprintln!("  {:>6}  {}  (e.g. on {})", item.count, item.name, receiver)
+            }
+            None => eprintln!("  {:>6}  {}", item.count, item.name),
+        }
+    }
+    eprintln!();
+}
+
 pub fn search_symbols(
     engine: &ContextEngine,
     pattern: &str,
+    regex: bool,
     with_cf: bool,
     limit: Option<usize>,
     include_tests: bool,
+    json_output: bool,
 ) -> Result<()> {
+    if json_output {
+        let result = engine.search(
+            pattern,
+            regex,
+            with_cf,
+            limit,
+            include_tests,
+            PolicyKind::Academic,
+        )?;
+        return print_json(&result);
+    }
     println!("Searching for symbols matching: \"{}\"", pattern);
     println!("{}", "=".repeat(80));
-    let result = engine.search(pattern, with_cf, limit, include_tests, PolicyKind::Academic)?;
+    let result = engine.search(
+        pattern,
+        regex,
+        with_cf,
+        limit,
+        include_tests,
+        PolicyKind::Academic,
+    )?;
 
     let filter_msg = if !include_tests {
         " (excluding tests)"
@@ -221,21 +816,35 @@ pub fn search_symbols(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn display_context_code(
     engine: &ContextEngine,
     symbol: &str,
-    _show_boundaries: bool,
     show_traversal: bool,
+    tree: bool,
     max_tokens: Option<u32>,
+    max_depth: Option<u32>,
+    max_files: Option<usize>,
+    with_callers: u32,
+    json_output: bool,
 ) -> Result<()> {
-    println!("Computing context for symbol: {}", symbol);
+    if !json_output {
+        println!("Computing context for symbol: {}", symbol);
+    }
     let result = engine.context(ContextRequest {
         symbol: symbol.to_string(),
         policy: PolicyKind::Academic,
         max_tokens,
-        include_code: !show_traversal, // skip loading source when only showing traversal
-        show_traversal,
+        max_depth,
+        // skip loading source when only showing traversal / tree
+        include_code: !show_traversal && !tree,
+        show_traversal: show_traversal || tree,
+        with_callers,
+        max_files,
     })?;
+    if json_output {
+        return print_json(&result);
+    }
 
     let meaningful_node_count: usize = result
         .layers
@@ -251,11 +860,60 @@ pub fn display_context_code(
         "  Reachable nodes: {} ({} total including 0-token stubs)",
         meaningful_node_count, result.reachable_node_count
     );
+    println!("  Boundaries: {}", result.boundary_count);
     if let Some(limit) = max_tokens {
         println!("  Max tokens: {}", limit);
     }
+    if let Some(limit) = max_files {
+        println!("  Max files: {}", limit);
+    }
     println!("{}", "=".repeat(80));
 
+    if let Some(callers) = &result.callers {
+        println!(
+            "\nCallers (up to {} hops, not counted in CF):",
+            with_callers
+        );
+        println!("{}", "=".repeat(80));
+        if callers.is_empty() {
+            println!("  (none)");
+        }
+        for caller in callers {
+            println!(
+                "  [{} hop{}] {} ({} tokens)",
+                caller.hops,
+                if caller.hops == 1 { "" } else { "s" },
+                caller.node.symbol,
+                caller.node.context_size
+            );
+        }
+        println!("{}", "=".repeat(80));
+    }
+
+    if !result.raises.is_empty() {
+        println!("\nRaises:");
+        println!("{}", "=".repeat(80));
+        for raised in &result.raises {
+            println!(
+                "  {} (declared by {})",
+                raised.type_symbol,
+                raised.raised_by.join(", ")
+            );
+        }
+        println!("{}", "=".repeat(80));
+    }
+
+    if tree {
+        println!("\nDependency tree (edge, symbol, size):");
+        println!("{}", "=".repeat(80));
+        print!(
+            "{}",
+            render_traversal_tree(result.traversal_steps.as_deref().unwrap_or_default())
+        );
+        println!("{}", "=".repeat(80));
+        return Ok(());
+    }
+
     if let Some(steps) = &result.traversal_steps {
         println!("\nTraversal (BFS order, edge + decision):");
         println!("{}", "=".repeat(80));
@@ -272,15 +930,21 @@ pub fn display_context_code(
                 .is_signature_complete
                 .map(|v| if v { "sig=complete" } else { "sig=incomplete" })
                 .unwrap_or("sig=na");
+            let reason = step
+                .boundary_reason
+                .as_deref()
+                .map(|r| format!("  stopped here because: {r}"))
+                .unwrap_or_default();
             println!(
-                "  {:4}  {}  {}  {}  ({} tokens, doc={:.2}, {})",
+                "  {:4}  {}  {}  {}  ({} tokens, doc={:.2}, {}){}",
                 i + 1,
                 edge,
                 decision,
                 short,
                 step.node.context_size,
                 step.node.doc_score,
-                sig
+                sig,
+                reason
             );
         }
         println!("{}", "=".repeat(80));
@@ -328,16 +992,392 @@ pub fn display_context_code(
     Ok(())
 }
 
-pub fn display_reachability(
+/// Print only the headline numbers of a symbol's context (no node listing or code).
+pub fn display_context_summary(
     engine: &ContextEngine,
-    from: &[String],
-    to: &[String],
+    symbol: &str,
+    max_tokens: Option<u32>,
+    max_depth: Option<u32>,
+    max_files: Option<usize>,
     json_output: bool,
-    witness_paths: bool,
-    max_paths: usize,
 ) -> Result<()> {
-    let result = engine.reachable(ReachabilityRequest {
-        from: from.to_vec(),
+    let result = engine.context(ContextRequest {
+        symbol: symbol.to_string(),
+        policy: PolicyKind::Academic,
+        max_tokens,
+        max_depth,
+        include_code: false,
+        show_traversal: false,
+        with_callers: 0,
+        max_files,
+    })?;
+    if json_output {
+        return print_json(&result);
+    }
+    println!("Total size: {} tokens", result.total_context_size);
+    println!("Reachable nodes: {}", result.reachable_node_count);
+    println!("Boundaries: {}", result.boundary_count);
+    Ok(())
+}
+
+/// Print the distinct files a symbol's context spans, with per-file node counts and summed
+/// context_size (largest first).
+pub fn display_context_files(
+    engine: &ContextEngine,
+    symbol: &str,
+    max_tokens: Option<u32>,
+    max_depth: Option<u32>,
+    max_files: Option<usize>,
+    json_output: bool,
+) -> Result<()> {
+    let result = engine.context(ContextRequest {
+        symbol: symbol.to_string(),
+        policy: PolicyKind::Academic,
+        max_tokens,
+        max_depth,
+        include_code: false,
+        // Steps list every reached node, including those nested in another node's span
+        show_traversal: true,
+        with_callers: 0,
+        max_files,
+    })?;
+    let mut files: BTreeMap<String, (usize, u32)> = BTreeMap::new();
+    for step in result.traversal_steps.unwrap_or_default() {
+        let entry = files.entry(step.node.file_path).or_default();
+        entry.0 += 1;
+        entry.1 += step.node.context_size;
+    }
+    let mut files: Vec<(String, (usize, u32))> = files.into_iter().collect();
+    files.sort_by(|a, b| b.1.1.cmp(&a.1.1).then_with(|| a.0.cmp(&b.0)));
+
+    if json_output {
+        let rows: Vec<_> = files
+            .iter()
+            .map(|(file_path, (node_count, context_size))| {
+                serde_json::json!({
+                    "file_path": file_path,
+                    "node_count": node_count,
+                    "context_size": context_size,
+                })
+            })
+            .collect();
+        return print_json(&rows);
+    }
+    println!("{} files", files.len());
+    for (file_path, (node_count, context_size)) in &files {
+        println!(
+            "{:>8} tokens  {:>4} nodes  {}",
+            context_size, node_count, file_path
+        );
+    }
+    Ok(())
+}
+
+/// Print the traversal steps with every pruning input (doc score, signature completeness,
+/// DI wiring, interface method, incoming edge, decision) as JSON.
+pub fn display_context_explain_json(
+    engine: &ContextEngine,
+    symbol: &str,
+    max_tokens: Option<u32>,
+    max_depth: Option<u32>,
+) -> Result<()> {
+    let result = engine.context(ContextRequest {
+        symbol: symbol.to_string(),
+        policy: PolicyKind::Academic,
+        max_tokens,
+        max_depth,
+        include_code: false,
+        show_traversal: true,
+        with_callers: 0,
+        max_files: None,
+    })?;
+    let steps = result.traversal_steps.unwrap_or_default();
+    println!("{}", serde_json::to_string_pretty(&steps)?);
+    Ok(())
+}
+
+/// Render traversal steps as an indented tree: each node appears under the node whose
+/// expansion reached it, indented by depth and labeled with the edge kind.
+pub fn render_traversal_tree(steps: &[TraversalStepDto]) -> String {
+    let mut children: HashMap<&str, Vec<&TraversalStepDto>> = HashMap::new();
+    let mut roots = Vec::new();
+    for step in steps {
+        match &step.parent {
+            Some(parent) => children.entry(parent.as_str()).or_default().push(step),
+            None => roots.push(step),
+        }
+    }
+
+    fn render(
+        step: &TraversalStepDto,
+        level: usize,
+        children: &HashMap<&str, Vec<&TraversalStepDto>>,
+        out: &mut String,
+    ) {
+        let indent = "  ".repeat(level);
+        let edge = step
+            .edge_kind
+            .as_deref()
+            .map(|e| format!("[{e}] "))
+            .unwrap_or_default();
+        let boundary = if step.decision.as_deref() == Some("Boundary") {
+            " [boundary]"
+        } else {
+            ""
+        };
+        out.push_str(&format!(
+            "{indent}{edge}{} ({} tokens){boundary}\n",
+            step.node.symbol, step.node.context_size
+        ));
+        for child in children
+            .get(step.node.symbol.as_str())
+            .into_iter()
+            .flatten()
+        {
+            render(child, level + 1, children, out);
+        }
+    }
+
+    let mut out = String::new();
+    for root in roots {
+        render(root, 0, &children, &mut out);
+    }
+    out
+}
+
+/// Print the reachable set of `symbol` as a Mermaid `graph TD` diagram.
+pub fn display_context_mermaid(
+    engine: &ContextEngine,
+    symbol: &str,
+    max_tokens: Option<u32>,
+) -> Result<()> {
+    let result = engine.context(ContextRequest {
+        symbol: symbol.to_string(),
+        policy: PolicyKind::Academic,
+        max_tokens,
+        max_depth: None,
+        include_code: false,
+        show_traversal: true,
+        with_callers: 0,
+        max_files: None,
+    })?;
+    print!(
+        "{}",
+        render_mermaid(&result.traversal_steps.unwrap_or_default())
+    );
+    Ok(())
+}
+
+/// Render traversal steps as a Mermaid flowchart: one node per reached symbol, one edge
+/// (labeled with its kind) from the node whose expansion reached it. Boundaries and the
+/// start node(s) get their own classes so they stand out in rendered Markdown.
+pub fn render_mermaid(steps: &[TraversalStepDto]) -> String {
+    let ids: HashMap<&str, String> = steps
+        .iter()
+        .enumerate()
+        .map(|(i, step)| (step.node.symbol.as_str(), format!("n{i}")))
+        .collect();
+
+    let mut out = String::from("graph TD\n");
+    for step in steps {
+        let id = &ids[step.node.symbol.as_str()];
+        out.push_str(&format!(
+            "    {id}[\"{} ({} tokens)\"]\n",
+            escape_mermaid_label(&step.node.symbol),
+            step.node.context_size
+        ));
+    }
+    for step in steps {
+        if let Some(parent_id) = step.parent.as_deref().and_then(|p| ids.get(p)) {
+            let id = &ids[step.node.symbol.as_str()];
+            let kind = step.edge_kind.as_deref().unwrap_or("?");
+            out.push_str(&format!("    {parent_id} -->|{kind}| {id}\n"));
+        }
+    }
+
+    out.push_str("    classDef start stroke-width:3px\n");
+    out.push_str("    classDef boundary fill:#eee,stroke-dasharray:4 2\n");
+    for step in steps {
+        let id = &ids[step.node.symbol.as_str()];
+        if step.parent.is_none() {
+            out.push_str(&format!("    class {id} start\n"));
+        } else if step.decision.as_deref() == Some("Boundary") {
+            out.push_str(&format!("    class {id} boundary\n"));
+        }
+    }
+    out
+}
+
+/// Print `symbol`'s reachable set as Graphviz DOT, limited to `depth` BFS layers when set.
+pub fn display_context_dot(
+    engine: &ContextEngine,
+    symbol: &str,
+    max_tokens: Option<u32>,
+    depth: Option<u32>,
+) -> Result<()> {
+    let result = engine.context(ContextRequest {
+        symbol: symbol.to_string(),
+        policy: PolicyKind::Academic,
+        max_tokens,
+        max_depth: None,
+        include_code: false,
+        show_traversal: true,
+        with_callers: 0,
+        max_files: None,
+    })?;
+    let steps: Vec<_> = result
+        .traversal_steps
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|step| depth.is_none_or(|d| step.depth <= d))
+        .collect();
+    print!("{}", render_dot(&steps));
+    Ok(())
+}
+
+/// Render traversal steps as a Graphviz digraph: nodes labeled with the short symbol name,
+/// edges styled per kind (Call solid, Read dashed, Write bold red, OverriddenBy dotted,
+/// Annotates gray). Boundaries get a double border, the start node(s) a bold one.
+pub fn render_dot(steps: &[TraversalStepDto]) -> String {
+    let ids: HashMap<&str, String> = steps
+        .iter()
+        .enumerate()
+        .map(|(i, step)| (step.node.symbol.as_str(), format!("n{i}")))
+        .collect();
+
+    let mut out = String::from("digraph context {\n    rankdir=LR;\n    node [shape=box];\n");
+    for step in steps {
+        let id = &ids[step.node.symbol.as_str()];
+        let short = step
+            .node
+            .symbol
+            .rsplit('/')
+            .next()
+            .unwrap_or(&step.node.symbol);
+        let style = if step.parent.is_none() {
+            ", style=bold"
+        } else if step.decision.as_deref() == Some("Boundary") {
+            ", peripheries=2"
+        } else {
+            ""
+        };
+        out.push_str(&format!(
+            "    {id} [label=\"{}\\n{} tokens\", tooltip=\"{}\"{style}];\n",
+            escape_dot(short),
+            step.node.context_size,
+            escape_dot(&step.node.symbol)
+        ));
+    }
+    for step in steps {
+        if let Some(parent_id) = step.parent.as_deref().and_then(|p| ids.get(p)) {
+            let id = &ids[step.node.symbol.as_str()];
+            let kind = step.edge_kind.as_deref().unwrap_or("?");
+            let style = match kind {
+                "Read" => "style=dashed",
+                "Write" => "style=bold, color=red",
+                "OverriddenBy" => "style=dotted",
+                "Annotates" => "color=gray",
+                "Inherits" => "style=dotted, color=blue",
+                "Raises" => "style=dashed, color=orange",
+                "Uses" => "style=dashed, color=purple",
+                _ => "style=solid",
+            };
+            out.push_str(&format!(
+                "    {parent_id} -> {id} [label=\"{}\", {style}];\n",
+                escape_dot(kind)
+            ));
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Print the graph (or `symbol`'s reachable set) as GEXF 1.3 for Gephi.
+pub fn display_graph_gexf(engine: &ContextEngine, symbol: Option<&str>) -> Result<()> {
+    let export = engine.graph_export(symbol, PolicyKind::Academic)?;
+    print!("{}", render_gexf(&export));
+    Ok(())
+}
+
+/// Render a [`GraphExport`] as a directed GEXF graph: node attributes carry symbol, type,
+/// CF, doc score and externality; edge attribute `kind` carries the edge kind.
+pub fn render_gexf(export: &GraphExport) -> String {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n\
+         \x20 <graph defaultedgetype=\"directed\">\n\
+         \x20   <attributes class=\"node\">\n\
+         \x20     <attribute id=\"0\" title=\"symbol\" type=\"string\"/>\n\
+         \x20     <attribute id=\"1\" title=\"type\" type=\"string\"/>\n\
+         \x20     <attribute id=\"2\" title=\"cf\" type=\"integer\"/>\n\
+         \x20     <attribute id=\"3\" title=\"doc_score\" type=\"float\"/>\n\
+         \x20     <attribute id=\"4\" title=\"is_external\" type=\"boolean\"/>\n\
+         \x20   </attributes>\n\
+         \x20   <attributes class=\"edge\">\n\
+         \x20     <attribute id=\"0\" title=\"kind\" type=\"string\"/>\n\
+         \x20   </attributes>\n\
+         \x20   <nodes>\n",
+    );
+    for (i, node) in export.nodes.iter().enumerate() {
+        let symbol = escape_xml(&node.symbol);
+        out.push_str(&format!(
+            "      <node id=\"{i}\" label=\"{symbol}\">\n\
+             \x20       <attvalues>\n\
+             \x20         <attvalue for=\"0\" value=\"{symbol}\"/>\n\
+             \x20         <attvalue for=\"1\" value=\"{}\"/>\n\
+             \x20         <attvalue for=\"2\" value=\"{}\"/>\n\
+             \x20         <attvalue for=\"3\" value=\"{}\"/>\n\
+             \x20         <attvalue for=\"4\" value=\"{}\"/>\n\
+             \x20       </attvalues>\n\
+             \x20     </node>\n",
+            escape_xml(&node.node_type),
+            node.cf,
+            node.doc_score,
+            node.is_external
+        ));
+    }
+    out.push_str("    </nodes>\n    <edges>\n");
+    for (i, edge) in export.edges.iter().enumerate() {
+        out.push_str(&format!(
+            "      <edge id=\"{i}\" source=\"{}\" target=\"{}\" label=\"{kind}\">\n\
+             \x20       <attvalues><attvalue for=\"0\" value=\"{kind}\"/></attvalues>\n\
+             \x20     </edge>\n",
+            edge.source,
+            edge.target,
+            kind = escape_xml(&edge.kind)
+        ));
+    }
+    out.push_str("    </edges>\n  </graph>\n</gexf>\n");
+    out
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Mermaid labels are quoted: replace characters that would end or break the label.
+fn escape_mermaid_label(label: &str) -> String {
+    label.replace('"', "#quot;").replace('\n', " ")
+}
+
+pub fn display_reachability(
+    engine: &ContextEngine,
+    from: &[String],
+    to: &[String],
+    json_output: bool,
+    witness_paths: bool,
+    max_paths: usize,
+) -> Result<()> {
+    let result = engine.reachable(ReachabilityRequest {
+        from: from.to_vec(),
         to: to.to_vec(),
         policy: PolicyKind::Academic,
         witness_paths,
@@ -374,22 +1414,336 @@ pub fn display_reachability(
     Ok(())
 }
 
-pub fn compute_and_display_cf_stats(engine: &ContextEngine, include_tests: bool) -> Result<()> {
+/// Stats at `percentiles`, or at 5% steps when none were requested.
+fn stats_at(
+    engine: &ContextEngine,
+    include_tests: bool,
+    include_variables: bool,
+    percentiles: Option<&[u32]>,
+) -> Result<StatsResponse> {
+    match percentiles {
+        Some(percentiles) => engine.stats_at_percentiles(
+            include_tests,
+            include_variables,
+            PolicyKind::Academic,
+            percentiles,
+        ),
+        None => engine.stats(include_tests, include_variables, PolicyKind::Academic),
+    }
+}
+
+pub fn compute_and_display_cf_stats(
+    engine: &ContextEngine,
+    include_tests: bool,
+    include_variables: bool,
+    percentiles: Option<&[u32]>,
+    json_output: bool,
+) -> Result<()> {
+    if json_output {
+        let result = stats_at(engine, include_tests, include_variables, percentiles)?;
+        return print_json(&result);
+    }
     let filter_msg = if !include_tests {
         " (excluding tests)"
     } else {
         ""
     };
     println!("Calculating CF stats{}...", filter_msg);
-    let result = engine.stats(include_tests, PolicyKind::Academic)?;
+    let result = stats_at(engine, include_tests, include_variables, percentiles)?;
 
     println!("\n{}", "=".repeat(60));
     print_distribution(&format!("Functions{}", filter_msg), &result.functions);
+    println!(
+        "\n  Boundary functions: {} | Transparent: {}",
+        result.boundary_count, result.transparent_count
+    );
+    if let Some(variables) = &result.variables {
+        println!("\n{}", "=".repeat(60));
+        print_distribution(&format!("Variables{}", filter_msg), variables);
+        println!("\n  Immutable variables are boundaries and cluster near their own size.");
+    }
+    let split = [
+        ("Global variables", &result.global_variables),
+        ("Field variables", &result.field_variables),
+    ];
+    for (name, dist) in split {
+        if let Some(dist) = dist {
+            println!("\n{}", "=".repeat(60));
+            print_distribution(&format!("{}{}", name, filter_msg), dist);
+        }
+    }
     println!("{}", "=".repeat(60));
 
     Ok(())
 }
 
+/// Print the CF distribution as CSV rows of `distribution,statistic,tokens`: one row per
+/// percentile (`p50`, ...; `p99` is always included) followed by count, average, median, min
+/// and max.
+pub fn display_stats_csv(
+    engine: &ContextEngine,
+    include_tests: bool,
+    include_variables: bool,
+    percentiles: Option<&[u32]>,
+    header: bool,
+) -> Result<()> {
+    let result = stats_at(engine, include_tests, include_variables, percentiles)?;
+    print!("{}", render_stats_csv(&result, header));
+    Ok(())
+}
+
+pub fn render_stats_csv(result: &StatsResponse, header: bool) -> String {
+    let mut out = String::new();
+    if header {
+        out.push_str("distribution,statistic,tokens\n");
+    }
+    let distributions = std::iter::once(("functions", &result.functions))
+        .chain(result.variables.as_ref().map(|v| ("variables", v)))
+        .chain(result.global_variables.as_ref().map(|v| ("globals", v)))
+        .chain(result.field_variables.as_ref().map(|v| ("fields", v)));
+    for (name, dist) in distributions {
+        for p in &dist.percentiles {
+            out.push_str(&format!("{name},p{},{}\n", p.percentile, p.tokens));
+        }
+        if !dist.percentiles.iter().any(|p| p.percentile == 99) {
+            out.push_str(&format!("{name},p99,{}\n", dist.p99));
+        }
+        out.push_str(&format!("{name},count,{}\n", dist.count));
+        out.push_str(&format!("{name},average,{}\n", dist.average));
+        out.push_str(&format!("{name},median,{}\n", dist.median));
+        out.push_str(&format!("{name},min,{}\n", dist.min));
+        out.push_str(&format!("{name},max,{}\n", dist.max));
+    }
+    out
+}
+
+pub fn display_file_stats(
+    engine: &ContextEngine,
+    limit: usize,
+    include_tests: bool,
+    json_output: bool,
+) -> Result<()> {
+    if json_output {
+        let mut result = engine.file_stats(include_tests, PolicyKind::Academic)?;
+        result.files.truncate(limit);
+        return print_json(&result);
+    }
+    let filter_msg = if !include_tests {
+        " (excluding tests)"
+    } else {
+        ""
+    };
+    println!("Calculating per-file CF stats{}...", filter_msg);
+    let result = engine.file_stats(include_tests, PolicyKind::Academic)?;
+
+    println!("\n{}", "=".repeat(60));
+    print_distribution(&format!("Files{}", filter_msg), &result.distribution);
+    println!("{}", "=".repeat(60));
+
+    println!("\nTop {} files by Context Footprint:", limit);
+    println!("{}", "=".repeat(80));
+    for (i, file) in result.files.iter().take(limit).enumerate() {
+        println!(
+            "{}. {} tokens ({} definitions)",
+            i + 1,
+            file.cf,
+            file.node_count
+        );
+        println!("   {}", file.file_path);
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Print the composite fitness score with each factor's contribution. `config_path` is an
+/// optional JSON file with any subset of [`FitnessConfig`] fields.
+pub fn display_fitness(
+    engine: &ContextEngine,
+    include_tests: bool,
+    config_path: Option<&Path>,
+    json_output: bool,
+) -> Result<()> {
+    let config = match config_path {
+        Some(path) => {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read fitness config: {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse fitness config: {}", path.display()))?
+        }
+        None => FitnessConfig::default(),
+    };
+    let result = engine.fitness(include_tests, PolicyKind::Academic, &config)?;
+    if json_output {
+        return print_json(&result);
+    }
+
+    println!("Architecture fitness: {:.1} / 100", result.score);
+    println!("{}", "=".repeat(60));
+    println!(
+        "{:<16} {:>12} {:>8} {:>8}",
+        "factor", "value", "score", "weight"
+    );
+    for factor in &result.factors {
+        println!(
+            "{:<16} {:>12.2} {:>8.2} {:>8.2}",
+            factor.name, factor.value, factor.score, factor.weight
+        );
+    }
+    Ok(())
+}
+
+/// Write every symbol's CF to `output` as JSON, for later `check --baseline`.
+pub fn write_snapshot(engine: &ContextEngine, output: &Path, include_tests: bool) -> Result<()> {
+    println!("Computing CF for all symbols...");
+    let snapshot = engine.snapshot(include_tests, PolicyKind::Academic)?;
+    let json = serde_json::to_string_pretty(&snapshot)?;
+    std::fs::write(output, json)
+        .with_context(|| format!("Failed to write snapshot: {}", output.display()))?;
+    println!(
+        "Wrote CF of {} symbols to {}",
+        snapshot.symbols.len(),
+        output.display()
+    );
+    Ok(())
+}
+
+/// Print the CF of every function touched by the unified diff in `diff_path`; fails when any
+/// of them exceeds `budget`.
+pub fn display_changed_cf(
+    engine: &ContextEngine,
+    diff_path: &Path,
+    budget: Option<u32>,
+    json_output: bool,
+) -> Result<()> {
+    let diff = std::fs::read_to_string(diff_path)
+        .with_context(|| format!("Failed to read diff: {}", diff_path.display()))?;
+    let changed = changed_lines_from_diff(&diff);
+    let result = engine.changed_cf(&changed, budget, PolicyKind::Academic)?;
+
+    if json_output {
+        print_json(&result)?;
+    } else {
+        println!("\nChanged functions: {}", result.items.len());
+        println!("{}", "=".repeat(80));
+        for item in &result.items {
+            let marker = if item.over_budget {
+                " (over budget)"
+            } else {
+                ""
+            };
+            println!("  {:>8} tokens  {}{}", item.cf, item.symbol, marker);
+        }
+        println!("{}", "=".repeat(80));
+    }
+
+    if let Some(limit) = budget
+        && result.over_budget > 0
+    {
+        anyhow::bail!(
+            "{} changed function(s) exceed the CF budget of {} tokens",
+            result.over_budget,
+            limit
+        );
+    }
+    Ok(())
+}
+
+/// Compare current CF against a snapshot; fails when any symbol regressed beyond
+/// `tolerance` percent.
+pub fn check_against_baseline(
+    engine: &ContextEngine,
+    baseline_path: &Path,
+    tolerance: f32,
+    include_tests: bool,
+    json_output: bool,
+) -> Result<()> {
+    let content = std::fs::read_to_string(baseline_path)
+        .with_context(|| format!("Failed to read baseline: {}", baseline_path.display()))?;
+    let baseline: CfSnapshot = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse baseline: {}", baseline_path.display()))?;
+
+    if !json_output {
+        println!("Computing CF for all symbols...");
+    }
+    let current = engine.snapshot(include_tests, PolicyKind::Academic)?;
+    let result = compare_to_baseline(&baseline, &current, tolerance);
+
+    if json_output {
+        print_json(&result)?;
+    } else {
+        println!("\nBaseline comparison (tolerance {}%):", tolerance);
+        println!("{}", "=".repeat(80));
+        println!("  Regressed: {}", result.regressions.len());
+        println!("  Improved:  {}", result.improved);
+        println!("  Added:     {}", result.added.len());
+        println!("  Removed:   {}", result.removed.len());
+        for change in &result.regressions {
+            println!(
+                "  {} -> {} tokens ({:+})  {}",
+                change.baseline,
+                change.current,
+                i64::from(change.current) - i64::from(change.baseline),
+                change.symbol
+            );
+        }
+        println!("{}", "=".repeat(80));
+    }
+
+    if !result.regressions.is_empty() {
+        anyhow::bail!(
+            "{} symbol(s) regressed beyond {}% of the baseline CF",
+            result.regressions.len(),
+            tolerance
+        );
+    }
+    Ok(())
+}
+
+/// Print how every symbol's CF changed between `old` and `new` (two indexes of the same
+/// project), largest absolute change first; deltas below `threshold` tokens are hidden.
+pub fn display_cf_diff(
+    old: &ContextEngine,
+    new: &ContextEngine,
+    threshold: u32,
+    include_tests: bool,
+    json_output: bool,
+) -> Result<()> {
+    if !json_output {
+        println!("Computing CF for all symbols in both indexes...");
+    }
+    let before = old.snapshot(include_tests, PolicyKind::Academic)?;
+    let after = new.snapshot(include_tests, PolicyKind::Academic)?;
+    let result = diff_snapshots(&before, &after, threshold);
+
+    if json_output {
+        return print_json(&result);
+    }
+    println!("\nCF diff (threshold {} tokens):", threshold);
+    println!("{}", "=".repeat(80));
+    println!("  Changed: {}", result.changes.len());
+    println!("  Added:   {}", result.added);
+    println!("  Removed: {}", result.removed);
+    if !result.changes.is_empty() {
+        println!();
+        println!("  {:>8}  {:>8}  {:>8}  Symbol", "Old", "New", "Delta");
+        for change in &result.changes {
+            println!(
+                "  {:>8}  {:>8}  {:>+8}  {}",
+                change.old, change.new, change.delta, change.symbol
+            );
+        }
+    }
+    println!("{}", "=".repeat(80));
+    Ok(())
+}
+
+/// Print `value` as pretty JSON (the `--format json` output of report commands).
+fn print_json<T: serde::Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
 fn print_distribution(name: &str, dist: &crate::app::dto::CfDistribution) {
     println!("\n{} - Context Footprint Distribution:", name);
     println!("  Total count: {}", dist.count);
@@ -402,6 +1756,277 @@ fn print_distribution(name: &str, dist: &crate::app::dto::CfDistribution) {
     println!("\n  Summary:");
     println!("    Average: {:>8} tokens", dist.average);
     println!("    Median:  {:>8} tokens", dist.median);
+    println!("    P99:     {:>8} tokens", dist.p99);
     println!("    Min:     {:>8} tokens", dist.min);
     println!("    Max:     {:>8} tokens", dist.max);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::dto::{ReachableNode, SpanDto};
+
+    fn step(
+        symbol: &str,
+        parent: Option<&str>,
+        edge: Option<&str>,
+        depth: u32,
+    ) -> TraversalStepDto {
+        TraversalStepDto {
+            node: ReachableNode {
+                id: 0,
+                symbol: symbol.to_string(),
+                node_type: "function".to_string(),
+                context_size: 10,
+                file_path: "m.py".to_string(),
+                span: SpanDto {
+                    start_line: 0,
+                    start_column: 0,
+                    end_line: 0,
+                    end_column: 0,
+                    start_line_1based: 1,
+                    end_line_1based: 1,
+                },
+                doc_score: 0.0,
+                is_external: false,
+            },
+            edge_kind: edge.map(String::from),
+            decision: edge.map(|_| "Transparent".to_string()),
+            boundary_reason: None,
+            is_signature_complete: None,
+            is_di_wired: None,
+            is_interface_method: None,
+            parent: parent.map(String::from),
+            depth,
+        }
+    }
+
+    #[test]
+    fn test_render_top_csv_escapes_symbols() {
+        use crate::app::dto::TopItem;
+
+        let result = TopResponse {
+            items: vec![
+                TopItem {
+                    symbol: "scip-python python pkg 0.1 `mod`/f().".into(),
+                    node_type: "function".into(),
+                    cf: 120,
+                },
+                TopItem {
+                    symbol: "pkg/Map[str, \"v\"]#".into(),
+                    node_type: "variable".into(),
+                    cf: 7,
+                },
+            ],
+        };
+        assert_eq!(
+            render_top_csv(&result, true),
+            "rank,node_type,symbol,cf_tokens\n\
+             1,function,scip-python python pkg 0.1 `mod`/f().,120\n\
+             2,variable,\"pkg/Map[str, \"\"v\"\"]#\",7\n"
+        );
+        assert!(render_top_csv(&result, false).starts_with("1,function,"));
+    }
+
+    #[test]
+    fn test_render_stats_csv_lists_percentiles_then_summary() {
+        use crate::app::dto::{CfDistribution, PercentileValue};
+
+        let dist = |max| CfDistribution {
+            count: 4,
+            percentiles: vec![
+                PercentileValue {
+                    percentile: 50,
+                    tokens: 10,
+                },
+                PercentileValue {
+                    percentile: 90,
+                    tokens: max,
+                },
+            ],
+            average: 12,
+            median: 10,
+            p99: max - 1,
+            min: 1,
+            max,
+        };
+        let result = StatsResponse {
+            functions: dist(30),
+            variables: Some(dist(5)),
+            global_variables: None,
+            field_variables: None,
+            boundary_count: 0,
+            transparent_count: 0,
+        };
+        let csv = render_stats_csv(&result, true);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "distribution,statistic,tokens");
+        assert_eq!(
+            &lines[1..9],
+            [
+                "functions,p50,10",
+                "functions,p90,30",
+                "functions,p99,29",
+                "functions,count,4",
+                "functions,average,12",
+                "functions,median,10",
+                "functions,min,1",
+                "functions,max,30",
+            ]
+        );
+        assert_eq!(lines.last(), Some(&"variables,max,5"));
+        assert!(!render_stats_csv(&result, false).contains("statistic"));
+    }
+
+    #[test]
+    fn test_render_neighbors_groups_by_edge_kind() {
+        use crate::app::dto::Neighbor;
+
+        let neighbor = |symbol: &str, edge_kind: &str, direction| Neighbor {
+            node: step(symbol, None, None, 0).node,
+            edge_kind: edge_kind.to_string(),
+            direction,
+        };
+        let result = NeighborsResponse {
+            node: step("a", None, None, 0).node,
+            neighbors: vec![
+                neighbor("c", "Call", NeighborDirection::Out),
+                neighbor("b", "Read", NeighborDirection::Out),
+                neighbor("d", "Call", NeighborDirection::In),
+            ],
+        };
+        assert_eq!(
+            render_neighbors(&result),
+            format!(
+                "a (function, 10 tokens): 3 neighbors\n{}\n\
+                 Call:\n  -> c (10 tokens, m.py)\n  <- d (10 tokens, m.py)\n\
+                 Read:\n  -> b (10 tokens, m.py)\n",
+                "=".repeat(80)
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_mermaid_labels_edges_and_styles_boundaries() {
+        let mut boundary = step("pkg.helper", Some("pkg.main"), Some("Call"), 1);
+        boundary.decision = Some("Boundary".to_string());
+        let steps = vec![
+            step("pkg.main", None, None, 0),
+            boundary,
+            step("pkg.\"state\"", Some("pkg.main"), Some("Read"), 1),
+        ];
+        let out = render_mermaid(&steps);
+
+        assert!(out.starts_with("graph TD\n"));
+        assert!(out.contains("n0[\"pkg.main (10 tokens)\"]"));
+        assert!(out.contains("n0 -->|Call| n1"));
+        assert!(out.contains("n0 -->|Read| n2"));
+        assert!(out.contains("n2[\"pkg.#quot;state#quot; (10 tokens)\"]"));
+        assert!(out.contains("class n0 start"));
+        assert!(out.contains("class n1 boundary"));
+        assert!(!out.contains("class n2"));
+    }
+
+    #[test]
+    fn test_render_dot_styles_edges_and_double_borders_boundaries() {
+        let mut boundary = step("pkg/helper().", Some("pkg/main()."), Some("Call"), 1);
+        boundary.decision = Some("Boundary".to_string());
+        let steps = vec![
+            step("pkg/main().", None, None, 0),
+            boundary,
+            step("pkg/\"state\".", Some("pkg/main()."), Some("Read"), 1),
+            step("pkg/cache.", Some("pkg/main()."), Some("Write"), 1),
+        ];
+        let out = render_dot(&steps);
+
+        assert!(out.starts_with("digraph context {\n"));
+        assert!(out.trim_end().ends_with('}'));
+        assert!(
+            out.contains(
+                "n0 [label=\"main().\\n10 tokens\", tooltip=\"pkg/main().\", style=bold];"
+            )
+        );
+        assert!(out.contains(
+            "n1 [label=\"helper().\\n10 tokens\", tooltip=\"pkg/helper().\", peripheries=2];"
+        ));
+        assert!(out.contains("label=\"\\\"state\\\".\\n10 tokens\""));
+        assert!(out.contains("n0 -> n1 [label=\"Call\", style=solid];"));
+        assert!(out.contains("n0 -> n2 [label=\"Read\", style=dashed];"));
+        assert!(out.contains("n0 -> n3 [label=\"Write\", style=bold, color=red];"));
+        assert_eq!(out.matches("peripheries=2").count(), 1);
+    }
+
+    #[test]
+    fn test_render_gexf_is_well_formed_with_node_and_edge_counts() {
+        use crate::app::dto::{ExportEdge, ExportNode};
+
+        let node = |symbol: &str, cf| ExportNode {
+            symbol: symbol.to_string(),
+            node_type: "function".to_string(),
+            cf,
+            doc_score: 0.5,
+            is_external: false,
+        };
+        let export = GraphExport {
+            nodes: vec![
+                node("m/a().", 30),
+                node("m/<b>&\"c\"().", 20),
+                node("m/d.", 10),
+            ],
+            edges: vec![
+                ExportEdge {
+                    source: 0,
+                    target: 1,
+                    kind: "Call".to_string(),
+                },
+                ExportEdge {
+                    source: 0,
+                    target: 2,
+                    kind: "Read".to_string(),
+                },
+            ],
+        };
+        let xml = render_gexf(&export);
+
+        // Every opened element is closed in order, and no raw markup leaks from labels.
+        let mut open: Vec<String> = Vec::new();
+        for tag in xml.split('<').skip(1).map(|t| &t[..t.find('>').unwrap()]) {
+            if tag.starts_with('?') || tag.ends_with('/') {
+                continue;
+            }
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(open.pop().as_deref(), Some(name));
+            } else {
+                open.push(tag.split_whitespace().next().unwrap().to_string());
+            }
+        }
+        assert!(open.is_empty(), "unclosed: {open:?}");
+        assert!(xml.contains("m/&lt;b&gt;&amp;&quot;c&quot;()."));
+
+        assert_eq!(xml.matches("<node id=").count(), 3);
+        assert_eq!(xml.matches("<edge id=").count(), 2);
+        assert!(xml.contains("<edge id=\"1\" source=\"0\" target=\"2\" label=\"Read\">"));
+        assert!(xml.contains("<attvalue for=\"2\" value=\"30\"/>"));
+    }
+
+    #[test]
+    fn test_render_traversal_tree_indents_children_under_parent() {
+        let steps = vec![
+            step("m/a().", None, None, 0),
+            step("m/b().", Some("m/a()."), Some("Call"), 1),
+            step("m/X.", Some("m/a()."), Some("Read"), 1),
+            step("m/c().", Some("m/b()."), Some("Call"), 2),
+        ];
+        let tree = render_traversal_tree(&steps);
+        let lines: Vec<&str> = tree.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "m/a(). (10 tokens)",
+                "  [Call] m/b(). (10 tokens)",
+                "    [Call] m/c(). (10 tokens)",
+                "  [Read] m/X. (10 tokens)",
+            ]
+        );
+    }
+}
@@ -1,28 +1,39 @@
+use crate::domain::comment_syntax::comment_syntax;
 use crate::domain::edge::EdgeKind;
-use crate::domain::graph::ContextGraph;
+use crate::domain::graph::{ContextGraph, UnresolvedRef};
 use crate::domain::node::{
     FunctionNode, Mutability as NodeMutability, Node, NodeCore, SourceSpan, VariableKind,
     VariableNode, Visibility as NodeVisibility,
 };
+use crate::domain::path_glob::PathGlob;
 use crate::domain::policy::{DocumentationScorer, NodeInfo, NodeType, SizeFunction};
 use crate::domain::ports::SourceReader;
 use crate::domain::semantic::{
-    Mutability, ReferenceRole, SemanticData, SourceSpan as SemanticSpan, SymbolDefinition,
-    SymbolDetails, SymbolId, SymbolKind, SymbolReference, VariableScope as SemanticVarScope,
-    Visibility,
+    Mutability, Parameter, ReferenceRole, SemanticData, SourceSpan as SemanticSpan,
+    SymbolDefinition, SymbolDetails, SymbolId, SymbolKind, SymbolReference,
+    VariableScope as SemanticVarScope, Visibility, normalize_symbol_version,
 };
 use crate::domain::type_registry::{
     TypeDefAttribute, TypeInfo, TypeKind, TypeRegistry, TypeVarInfo,
 };
 use anyhow::Result;
 use petgraph::graph::NodeIndex;
-use std::collections::{HashMap, HashSet};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::Path;
 
 /// Graph builder - Domain Service for constructing ContextGraph
 pub struct GraphBuilder {
     size_function: Box<dyn SizeFunction>,
     doc_scorer: Box<dyn DocumentationScorer>,
+    normalize_symbol_versions: bool,
+    infer_config_constants: bool,
+    exclude: Vec<PathGlob>,
+    missing_source_is_fatal: bool,
+    external_max_tokens: u32,
+    external_signature_len: usize,
+    param_type_edges: bool,
+    signature_sizes: bool,
 }
 
 impl GraphBuilder {
@@ -33,15 +44,90 @@ impl GraphBuilder {
         Self {
             size_function,
             doc_scorer,
+            normalize_symbol_versions: false,
+            infer_config_constants: false,
+            exclude: Vec::new(),
+            missing_source_is_fatal: true,
+            external_max_tokens: EXTERNAL_SYMBOL_MAX_TOKENS,
+            external_signature_len: EXTERNAL_SIGNATURE_TRUNCATE_LEN,
+            param_type_edges: false,
+            signature_sizes: false,
         }
     }
 
+    /// Match external symbols ignoring their package version (see [`normalize_symbol_version`]),
+    /// so indexes built at different versions merge into one node per symbol.
+    pub fn with_symbol_version_normalization(mut self, enabled: bool) -> Self {
+        self.normalize_symbol_versions = enabled;
+        self
+    }
+
+    /// Treat never-written module-level `ALL_CAPS` / `settings` globals as `Const`, for
+    /// adapters that default every global to `Mutable`.
+    pub fn with_config_constant_inference(mut self, enabled: bool) -> Self {
+        self.infer_config_constants = enabled;
+        self
+    }
+
+    /// Drop documents whose relative path matches any of `patterns` (generated code, vendored
+    /// directories) before building: their definitions never become nodes, and references
+    /// into them stay unresolved.
+    pub fn with_exclude(mut self, patterns: Vec<PathGlob>) -> Self {
+        self.exclude = patterns;
+        self
+    }
+
+    /// Cap on the context_size of external symbols and declaration-only definitions
+    /// (default [`EXTERNAL_SYMBOL_MAX_TOKENS`]).
+    pub fn with_external_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.external_max_tokens = max_tokens;
+        self
+    }
+
+    /// Characters of an external symbol's signature kept before truncation
+    /// (default [`EXTERNAL_SIGNATURE_TRUNCATE_LEN`]).
+    pub fn with_external_signature_len(mut self, len: usize) -> Self {
+        self.external_signature_len = len;
+        self
+    }
+
+    /// Add [`EdgeKind::Uses`] edges from each function to the project types its parameters are
+    /// annotated with, so a DTO-style parameter's definition counts toward the function's CF.
+    pub fn with_param_type_edges(mut self, enabled: bool) -> Self {
+        self.param_type_edges = enabled;
+        self
+    }
+
+    /// Also size the signature (+ docs) of each full-body function, for policies that count
+    /// boundaries by signature only (`boundary_counts_signature_only`). Off by default: it
+    /// sizes every function twice.
+    pub fn with_signature_sizes(mut self, enabled: bool) -> Self {
+        self.signature_sizes = enabled;
+        self
+    }
+
+    /// When `false`, a document whose source cannot be read still gets its nodes, sized 0,
+    /// and the build records one warning instead of failing (index-only analysis).
+    pub fn with_missing_source_fatal(mut self, fatal: bool) -> Self {
+        self.missing_source_is_fatal = fatal;
+        self
+    }
+
     /// Three-pass build strategy
     pub fn build(
         &self,
-        semantic_data: SemanticData,
+        mut semantic_data: SemanticData,
         source_reader: &dyn SourceReader,
     ) -> Result<ContextGraph> {
+        if !self.exclude.is_empty() {
+            semantic_data.documents.retain(|document| {
+                !self
+                    .exclude
+                    .iter()
+                    .any(|glob| glob.is_match(&document.relative_path))
+            });
+        }
+
         let mut graph = ContextGraph::new();
         let mut type_registry = TypeRegistry::new();
 
@@ -51,15 +137,43 @@ impl GraphBuilder {
         // Collect all node candidate symbols
         let mut node_symbols: HashSet<SymbolId> = HashSet::new();
 
+        // Return type names read from signatures of functions the adapter left untyped
+        // (resolved against type definitions in Pass 2.5)
+        let mut inferred_return_names: HashMap<SymbolId, (String, String)> = HashMap::new();
+
+        // Declared base classes per type, for Inherits edges (wired in Pass 3)
+        let mut base_types: HashMap<SymbolId, Vec<SymbolId>> = HashMap::new();
+
+        // Documents whose source could not be read (only when missing sources are not fatal)
+        let mut missing_sources = 0usize;
+
         // Pass 1: Node Allocation - Create FunctionNode/VariableNode and TypeRegistry entries
         for document in &semantic_data.documents {
             let source_path = Path::new(&semantic_data.project_root).join(&document.relative_path);
-            let source_code = source_reader.read(&source_path)?;
+            let (source_code, source_missing) = match source_reader.read(&source_path) {
+                Ok(source_code) => (source_code, false),
+                Err(_) if !self.missing_source_is_fatal => {
+                    missing_sources += 1;
+                    (String::new(), true)
+                }
+                Err(e) => return Err(e),
+            };
+            // Ambient declaration files (.d.ts) carry declarations without implementation:
+            // treat their definitions like external symbols (signature-only, capped, boundary).
+            let is_declaration_file = is_declaration_file(&document.relative_path);
 
             for def in &document.definitions {
                 let node_id = graph.graph.node_count() as u32;
+                // Generated code is treated like a declaration file: read as a signature only.
+                let is_declaration_only = is_declaration_file || def.is_generated;
                 let doc_texts = def.documentation.clone();
-                let span = convert_span(&def.span);
+                // Variables often come with a name-only range: size the whole declaration.
+                let def_span = if def.kind == SymbolKind::Variable {
+                    expand_variable_span(&def.span, &source_code, &document.language)
+                } else {
+                    def.span.clone()
+                };
+                let span = convert_span(&def_span);
 
                 // Check if this is an interface/abstract method
                 // Now we check is_abstract directly from FunctionModifiers
@@ -82,18 +196,52 @@ impl GraphBuilder {
                             .is_some_and(|f| f.modifiers.use_signature_only_for_size));
 
                 // For interface methods and annotated-style factories, only compute context_size for signature (not implementation body)
-                let context_size = if use_signature_only {
+                let context_size = if source_missing {
+                    0
+                } else if is_declaration_only {
                     let signature_span = extract_signature_span(&def.span, &source_code);
                     self.size_function
-                        .compute(&source_code, &signature_span, &doc_texts)
+                        .compute_in_language(
+                            &document.language,
+                            &source_code,
+                            &signature_span,
+                            &doc_texts,
+                        )
+                        .min(self.external_max_tokens)
+                } else if use_signature_only {
+                    let signature_span = extract_signature_span(&def.span, &source_code);
+                    self.size_function.compute_in_language(
+                        &document.language,
+                        &source_code,
+                        &signature_span,
+                        &doc_texts,
+                    )
                 } else {
-                    self.size_function.compute(
+                    self.size_function.compute_in_language(
+                        &document.language,
                         &source_code,
-                        &convert_span_for_size(&def.span),
+                        &convert_span_for_size(&def_span),
                         &doc_texts,
                     )
                 };
 
+                // Signature-only size of full-body functions, counted when they are reached as
+                // a boundary under `boundary_counts_signature_only`.
+                let signature_size = (self.signature_sizes
+                    && def.kind == SymbolKind::Function
+                    && !is_declaration_only
+                    && !use_signature_only)
+                    .then(|| {
+                        let signature_span = extract_signature_span(&def.span, &source_code);
+                        self.size_function.compute_in_language(
+                            &document.language,
+                            &source_code,
+                            &signature_span,
+                            &doc_texts,
+                        )
+                    })
+                    .filter(|&size| size < context_size);
+
                 // Use all documentation entries for scoring (e.g. Annotated Doc() per parameter);
                 // joining so the heuristic can see parameter coverage across all entries.
                 let doc_text_combined = doc_texts.join("\n\n");
@@ -109,13 +257,8 @@ impl GraphBuilder {
                     .next_back()
                     .map(|ext| ext.to_lowercase());
 
-                let node_info = NodeInfo {
-                    node_type: infer_node_type_from_kind(&def.kind),
-                    name: def.name.clone(),
-                    signature: extract_signature(def),
-                    language,
-                };
-                let doc_score = self.doc_scorer.score(&node_info, doc_text);
+                let node_info = node_info(def, extract_signature(def), language);
+                let doc_score = self.doc_scorer.score_node(def, &node_info, doc_text);
 
                 match def.kind {
                     SymbolKind::Type => {
@@ -135,24 +278,59 @@ impl GraphBuilder {
                                 type_registry
                                     .register_implementor(base_id.clone(), def.symbol_id.clone());
                             }
+                            if !type_details.inherits.is_empty() {
+                                base_types
+                                    .insert(def.symbol_id.clone(), type_details.inherits.clone());
+                            }
                         }
                     }
                     SymbolKind::Function | SymbolKind::Variable => {
                         // Create graph node
                         node_symbols.insert(def.symbol_id.clone());
 
-                        let core = NodeCore::new(
+                        if let Some(func) = def.as_function()
+                            && func.return_types.is_empty()
+                            && let Some(name) =
+                                infer_return_type_name(&document.language, &def.span, &source_code)
+                        {
+                            inferred_return_names.insert(
+                                def.symbol_id.clone(),
+                                (document.relative_path.clone(), name),
+                            );
+                        }
+
+                        let mut core = NodeCore::new(
                             node_id,
                             def.name.clone(),
                             def.enclosing_symbol.clone(),
                             context_size,
                             span,
                             doc_score,
-                            def.is_external,
+                            def.is_external || is_declaration_only,
                             document.relative_path.clone(),
                         );
-
-                        let node = create_node_from_definition(core, def, is_interface_method)?;
+                        core.signature_size = signature_size;
+
+                        let mut node = create_node_from_definition(core, def, is_interface_method)?;
+                        // Adapters without signature support leave parameters empty: read them
+                        // from the source signature instead.
+                        if let Node::Function(f) = &mut node
+                            && f.parameters.is_empty()
+                            && let Some(parsed) = parse_function_signature_for_language(
+                                &document.language,
+                                &span_text(&def.span, &source_code),
+                            )
+                        {
+                            f.parameters = parsed
+                                .parameters
+                                .into_iter()
+                                .map(|p| crate::domain::node::Parameter {
+                                    name: p.name,
+                                    param_type: p.param_type,
+                                    is_high_freedom_type: p.is_high_freedom_type,
+                                })
+                                .collect();
+                        }
                         graph.add_node(def.symbol_id.clone(), node);
                     }
                 }
@@ -163,7 +341,12 @@ impl GraphBuilder {
         // External symbols have no project source file. For context_size we use ONLY the
         // signature (no doc/implementation) since external library bodies are not useful
         // for understanding project code.
-        for def in &semantic_data.external_symbols {
+        let (external_symbols, external_aliases, conflict_warnings) = dedup_external_symbols(
+            &semantic_data.external_symbols,
+            self.normalize_symbol_versions,
+        );
+        graph.warnings.extend(conflict_warnings);
+        for def in external_symbols {
             // Skip if this symbol was already found as an internal project symbol
             // (Extractors might incorrectly flag intra-project imports as external)
             if node_symbols.contains(&def.symbol_id) || type_registry.contains(&def.symbol_id) {
@@ -174,7 +357,8 @@ impl GraphBuilder {
             let doc_texts = def.documentation.clone();
 
             let signature = extract_signature(def);
-            let synthetic_source = build_external_signature_only(&signature, def);
+            let synthetic_source =
+                build_external_signature_only(&signature, def, self.external_signature_len);
             let line_count = synthetic_source.lines().count().max(1) as u32;
             let synthetic_span = crate::domain::node::SourceSpan {
                 start_line: 0,
@@ -186,7 +370,7 @@ impl GraphBuilder {
             let raw_size = self
                 .size_function
                 .compute(&synthetic_source, &synthetic_span, &[]);
-            let context_size = raw_size.min(EXTERNAL_SYMBOL_MAX_TOKENS);
+            let context_size = raw_size.min(self.external_max_tokens);
 
             let doc_text_combined = doc_texts.join("\n\n");
             let doc_text = if doc_text_combined.is_empty() {
@@ -202,13 +386,8 @@ impl GraphBuilder {
                 .next_back()
                 .map(|ext| ext.to_lowercase());
 
-            let node_info = NodeInfo {
-                node_type: infer_node_type_from_kind(&def.kind),
-                name: def.name.clone(),
-                signature,
-                language,
-            };
-            let doc_score = self.doc_scorer.score(&node_info, doc_text);
+            let node_info = node_info(def, signature, language);
+            let doc_score = self.doc_scorer.score_node(def, &node_info, doc_text);
 
             match def.kind {
                 SymbolKind::Type => {
@@ -235,6 +414,13 @@ impl GraphBuilder {
             }
         }
 
+        if missing_sources > 0 {
+            graph.warnings.push(format!(
+                "{} source file(s) could not be read; their definitions were sized as 0",
+                missing_sources
+            ));
+        }
+
         // Build constructor init map: type_symbol -> init_node_symbol
         let mut init_map: HashMap<SymbolId, SymbolId> = HashMap::new();
         for document in &semantic_data.documents {
@@ -254,9 +440,30 @@ impl GraphBuilder {
         // Collect unresolved calls (target unknown) and call_assignments (for type propagation)
         let mut unresolved_calls: Vec<(SymbolReference, NodeIndex)> = Vec::new();
         let mut call_assignments: HashMap<SymbolId, (NodeIndex, Option<SymbolId>)> = HashMap::new();
+        // Locals are not nodes, so `x = Concrete()` leaves no var_type to recover `x.method()`
+        // from: remember the constructed type per (enclosing node, local symbol) instead.
+        let mut local_constructions: HashMap<(NodeIndex, SymbolId), SymbolId> = HashMap::new();
 
         for document in &semantic_data.documents {
+            let is_typescript = document.language.to_lowercase().starts_with("typescript");
+            // References the indexer could not place inside any definition (module-level
+            // code, unattributed occurrences) have no source node: report rather than drop.
+            let unattributed = document
+                .references
+                .iter()
+                .filter(|r| r.enclosing_symbol.is_empty())
+                .count();
+            if unattributed > 0 {
+                graph.warnings.push(format!(
+                    "{} reference(s) in {} have no enclosing definition and were not attributed",
+                    unattributed, document.relative_path
+                ));
+            }
+
             for reference in &document.references {
+                if reference.enclosing_symbol.is_empty() {
+                    continue;
+                }
                 let source_node_sym = Self::resolve_to_node_symbol(
                     &reference.enclosing_symbol,
                     &node_symbols,
@@ -267,7 +474,16 @@ impl GraphBuilder {
                 let target_node_sym = reference
                     .target_symbol
                     .as_ref()
-                    .and_then(|t| Self::resolve_to_node_symbol(t, &node_symbols, &enclosing_map));
+                    .map(|t| external_aliases.get(t).unwrap_or(t))
+                    .and_then(|t| {
+                        Self::resolve_to_node_symbol(t, &node_symbols, &enclosing_map).or_else(
+                            || {
+                                let alias =
+                                    typescript_namespace_alias(t).filter(|_| is_typescript)?;
+                                Self::resolve_to_node_symbol(&alias, &node_symbols, &enclosing_map)
+                            },
+                        )
+                    });
 
                 if let Some(source_sym) = source_node_sym {
                     let source_idx = match graph.get_node_by_symbol(&source_sym) {
@@ -275,6 +491,29 @@ impl GraphBuilder {
                         None => continue,
                     };
 
+                    if reference.role == ReferenceRole::Call
+                        && let Some(assigned_var) = &reference.assigned_to
+                        && !node_symbols.contains(assigned_var)
+                        && let Some(target) = &reference.target_symbol
+                    {
+                        // Constructor call: target is the type itself, or a constructor method
+                        let constructed_type = if type_registry.contains(target) {
+                            Some(target.clone())
+                        } else {
+                            target_node_sym
+                                .as_ref()
+                                .and_then(|sym| graph.get_node_by_symbol(sym))
+                                .and_then(|idx| match graph.node(idx) {
+                                    Node::Function(f) if f.is_constructor => f.core.scope.clone(),
+                                    _ => None,
+                                })
+                                .filter(|scope| type_registry.contains(scope))
+                        };
+                        if let Some(type_id) = constructed_type {
+                            local_constructions.insert((source_idx, assigned_var.clone()), type_id);
+                        }
+                    }
+
                     if reference.role == ReferenceRole::Call {
                         let resolved_target = target_node_sym
                             .as_ref()
@@ -328,7 +567,102 @@ impl GraphBuilder {
             }
         }
 
+        // Pass 2 (continued): Raises edges. Types are not nodes, so a declared exception type
+        // is reached through its constructor(s); types without a constructor node get no edge.
+        // Node index order keeps each type's constructors, and so the edge order, stable
+        let mut constructors_by_type: HashMap<&SymbolId, Vec<NodeIndex>> = HashMap::new();
+        for idx in graph.graph.node_indices() {
+            if let Node::Function(f) = graph.node(idx)
+                && f.is_constructor
+                && let Some(scope) = &f.core.scope
+            {
+                constructors_by_type.entry(scope).or_default().push(idx);
+            }
+        }
+        for (type_id, init_sym) in &init_map {
+            if let Some(idx) = graph.get_node_by_symbol(init_sym) {
+                let entry = constructors_by_type.entry(type_id).or_default();
+                if !entry.contains(&idx) {
+                    entry.push(idx);
+                }
+            }
+        }
+        let mut raises_edges = Vec::new();
+        for def in semantic_data.documents.iter().flat_map(|d| &d.definitions) {
+            let Some(func) = def.as_function() else {
+                continue;
+            };
+            let Some(source_idx) = graph.get_node_by_symbol(&def.symbol_id) else {
+                continue;
+            };
+            for thrown in &func.throws {
+                for &target_idx in constructors_by_type.get(thrown).into_iter().flatten() {
+                    if source_idx != target_idx {
+                        raises_edges.push((source_idx, target_idx));
+                    }
+                }
+            }
+        }
+
+        // Pass 2 (continued): Uses edges (opt-in). A parameter's project type is reached through
+        // its constructor(s), or through its fields when it has none (plain DTOs, records).
+        let mut uses_edges = Vec::new();
+        if self.param_type_edges {
+            // Node index order keeps each type's fields, and so the edge order, stable
+            let mut fields_by_type: HashMap<&SymbolId, Vec<NodeIndex>> = HashMap::new();
+            for idx in graph.graph.node_indices() {
+                if let Node::Variable(v) = graph.node(idx)
+                    && v.variable_kind == VariableKind::ClassField
+                    && let Some(scope) = &v.core.scope
+                {
+                    fields_by_type.entry(scope).or_default().push(idx);
+                }
+            }
+            for source_idx in graph.graph.node_indices() {
+                let Node::Function(f) = graph.node(source_idx) else {
+                    continue;
+                };
+                let mut seen: HashSet<&SymbolId> = HashSet::new();
+                for type_id in f.parameters.iter().filter_map(|p| p.param_type.as_ref()) {
+                    // Methods taking their own type (`other: Self`) add nothing to read
+                    if f.core.scope.as_ref() == Some(type_id)
+                        || !type_registry.contains(type_id)
+                        || !seen.insert(type_id)
+                    {
+                        continue;
+                    }
+                    let targets = constructors_by_type
+                        .get(type_id)
+                        .filter(|ctors| !ctors.is_empty())
+                        .or_else(|| fields_by_type.get(type_id));
+                    for &target_idx in targets.into_iter().flatten() {
+                        if source_idx != target_idx {
+                            uses_edges.push((source_idx, target_idx));
+                        }
+                    }
+                }
+            }
+        }
+
+        for (source_idx, target_idx) in raises_edges {
+            graph.add_edge(source_idx, target_idx, EdgeKind::Raises);
+        }
+        for (source_idx, target_idx) in uses_edges {
+            graph.add_edge(source_idx, target_idx, EdgeKind::Uses);
+        }
+
         // Pass 2.5: Fill in type references in nodes from SymbolDetails
+        let mut types_by_name: HashMap<&str, Vec<(&str, &SymbolId)>> = HashMap::new();
+        for document in &semantic_data.documents {
+            for def in &document.definitions {
+                if def.kind == SymbolKind::Type {
+                    types_by_name
+                        .entry(def.name.as_str())
+                        .or_default()
+                        .push((document.relative_path.as_str(), &def.symbol_id));
+                }
+            }
+        }
         for document in &semantic_data.documents {
             for def in &document.definitions {
                 if let Some(node_idx) = graph.get_node_by_symbol(&def.symbol_id) {
@@ -343,6 +677,14 @@ impl GraphBuilder {
                                         func_node.return_types.push(type_id.clone());
                                     }
                                 }
+                                if func_details.return_types.is_empty()
+                                    && let Some((path, name)) =
+                                        inferred_return_names.get(&def.symbol_id)
+                                    && let Some(type_id) =
+                                        resolve_type_by_name(&types_by_name, path, name)
+                                {
+                                    func_node.return_types.push(type_id.clone());
+                                }
                                 // Note: Parameters are already set in create_node_from_definition
                             }
                         }
@@ -417,6 +759,9 @@ impl GraphBuilder {
             }
         }
 
+        // Structurally typed languages (Go) never declare `implements`: infer it from method sets.
+        infer_structural_implementors(&semantic_data, &method_by_scope, &mut type_registry);
+
         // For each interface method, find concrete implementations via implementors map
         let interface_methods: Vec<_> = graph
             .symbol_to_node
@@ -459,33 +804,47 @@ impl GraphBuilder {
                     still_unresolved.push((reference, source_idx));
                     continue;
                 };
-                let Some(method_name) = &reference.method_name else {
+                // Adapters that only emit target symbols: the method descriptor names the method
+                let Some(method_name) = reference.method_name.clone().or_else(|| {
+                    reference
+                        .target_symbol
+                        .as_deref()
+                        .and_then(method_name_from_symbol)
+                }) else {
                     still_unresolved.push((reference, source_idx));
                     continue;
                 };
-                // Resolve receiver to a node (variable)
-                let receiver_node_sym =
-                    Self::resolve_to_node_symbol(receiver_sym, &node_symbols, &enclosing_map);
-                let Some(receiver_sym) = receiver_node_sym else {
-                    still_unresolved.push((reference, source_idx));
-                    continue;
-                };
-                let Some(receiver_idx) = graph.get_node_by_symbol(&receiver_sym) else {
-                    still_unresolved.push((reference, source_idx));
-                    continue;
-                };
-                let var_type = match graph.graph.node_weight(receiver_idx) {
-                    Some(Node::Variable(v)) => v.var_type.clone(),
-                    _ => {
+                let type_id = if let Some(type_id) =
+                    local_constructions.get(&(source_idx, receiver_sym.clone()))
+                {
+                    // Local constructed in the same function: `x = Concrete(); x.method()`
+                    type_id.clone()
+                } else {
+                    // Resolve receiver to a node (variable)
+                    let receiver_node_sym =
+                        Self::resolve_to_node_symbol(receiver_sym, &node_symbols, &enclosing_map);
+                    let Some(receiver_sym) = receiver_node_sym else {
                         still_unresolved.push((reference, source_idx));
                         continue;
-                    }
-                };
-                let Some(type_id) = var_type else {
-                    still_unresolved.push((reference, source_idx));
-                    continue;
+                    };
+                    let Some(receiver_idx) = graph.get_node_by_symbol(&receiver_sym) else {
+                        still_unresolved.push((reference, source_idx));
+                        continue;
+                    };
+                    let var_type = match graph.graph.node_weight(receiver_idx) {
+                        Some(Node::Variable(v)) => v.var_type.clone(),
+                        _ => {
+                            still_unresolved.push((reference, source_idx));
+                            continue;
+                        }
+                    };
+                    let Some(type_id) = var_type else {
+                        still_unresolved.push((reference, source_idx));
+                        continue;
+                    };
+                    type_id
                 };
-                let key = (type_id, method_name.clone());
+                let key = (type_id, method_name);
                 if let Some(target_indices) = method_by_scope.get(&key)
                     && let Some(&target_idx) = target_indices.first()
                 {
@@ -502,6 +861,78 @@ impl GraphBuilder {
                 break;
             }
         }
+        let unresolved: Vec<UnresolvedRef> = unresolved_calls
+            .into_iter()
+            .map(|(reference, source_idx)| UnresolvedRef {
+                source: graph.symbol_of(source_idx).unwrap_or_default().to_string(),
+                file_path: reference.location.file_path,
+                line: reference.location.line,
+                target_symbol: reference.target_symbol,
+                method_name: reference.method_name,
+                receiver: reference.receiver,
+            })
+            .collect();
+        graph.unresolved = unresolved;
+
+        // Pass 3.5: Inherits edges: Subtype method → nearest base-class method of the same name.
+        // A base that does not define the method is skipped in favor of its own bases.
+        // Keys are sorted so edges are added in a deterministic order.
+        let mut scoped_methods: Vec<_> = method_by_scope.iter().collect();
+        scoped_methods.sort_by(|a, b| a.0.cmp(b.0));
+        let mut inherits_edges = Vec::new();
+        for ((scope, method_name), indices) in scoped_methods {
+            let Some(bases) = base_types.get(scope) else {
+                continue;
+            };
+            let mut queue: VecDeque<&SymbolId> = bases.iter().collect();
+            let mut seen: HashSet<&SymbolId> = HashSet::from([scope]);
+            while let Some(base_id) = queue.pop_front() {
+                if !seen.insert(base_id) {
+                    continue;
+                }
+                match method_by_scope.get(&(base_id.clone(), method_name.clone())) {
+                    Some(base_indices) => {
+                        for &from in indices {
+                            for &to in base_indices {
+                                inherits_edges.push((from, to));
+                            }
+                        }
+                    }
+                    None => queue.extend(base_types.get(base_id).into_iter().flatten()),
+                }
+            }
+        }
+        for (from, to) in inherits_edges {
+            if from != to {
+                graph.add_edge(from, to, EdgeKind::Inherits);
+            }
+        }
+
+        // Pass 4: Config globals nobody writes are constants, whatever the adapter said
+        if self.infer_config_constants {
+            let unwritten_config: Vec<NodeIndex> = graph
+                .graph
+                .node_indices()
+                .filter(|&idx| match graph.node(idx) {
+                    Node::Variable(v) => {
+                        v.variable_kind == VariableKind::Global
+                            && v.mutability == NodeMutability::Mutable
+                            && !v.core.is_external
+                            && is_config_name(&v.core.name)
+                            && graph
+                                .incoming_edges(idx, Some(EdgeKind::Write))
+                                .next()
+                                .is_none()
+                    }
+                    Node::Function(_) => false,
+                })
+                .collect();
+            for idx in unwritten_config {
+                if let Node::Variable(v) = &mut graph.graph[idx] {
+                    v.mutability = NodeMutability::Const;
+                }
+            }
+        }
 
         graph.type_registry = type_registry;
         Ok(graph)
@@ -556,6 +987,15 @@ impl GraphBuilder {
     }
 }
 
+/// `MAX_RETRIES`, `CONFIG`, `settings`: names conventionally used for configuration.
+fn is_config_name(name: &str) -> bool {
+    let all_caps = name.chars().any(|c| c.is_ascii_uppercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+    all_caps || name.eq_ignore_ascii_case("settings")
+}
+
 /// Convert semantic span to node SourceSpan
 fn convert_span(span: &SemanticSpan) -> SourceSpan {
     SourceSpan {
@@ -576,6 +1016,57 @@ fn convert_span_for_size(span: &SemanticSpan) -> crate::domain::node::SourceSpan
     }
 }
 
+/// Expand a single-line variable span (typically just the name) to the full declaration:
+/// from the start of its line to the end of the statement, following unbalanced brackets
+/// and trailing backslash continuations across lines. Brackets inside string literals and
+/// line comments are not counted. Multi-line spans are trusted as-is.
+fn expand_variable_span(span: &SemanticSpan, source_code: &str, language: &str) -> SemanticSpan {
+    if span.start_line != span.end_line {
+        return span.clone();
+    }
+    let lines: Vec<&str> = source_code.lines().collect();
+    let start = span.start_line as usize;
+    if start >= lines.len() {
+        return span.clone();
+    }
+
+    let comment_marker = comment_syntax(language).map(|syntax| syntax.line_marker);
+    let mut depth: i32 = 0;
+    let mut end = start;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        end = i;
+        let mut quote: Option<char> = None;
+        let mut escaped = false;
+        for (at, c) in line.char_indices() {
+            match quote {
+                Some(_) if escaped => escaped = false,
+                Some(_) if c == '\\' => escaped = true,
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if comment_marker.is_some_and(|marker| line[at..].starts_with(marker)) => {
+                    break;
+                }
+                None => match c {
+                    '"' | '\'' | '`' => quote = Some(c),
+                    '(' | '[' | '{' => depth += 1,
+                    ')' | ']' | '}' => depth -= 1,
+                    _ => {}
+                },
+            }
+        }
+        if depth <= 0 && !line.trim_end().ends_with('\\') {
+            break;
+        }
+    }
+
+    SemanticSpan {
+        start_line: span.start_line,
+        start_column: 0,
+        end_line: end as u32,
+        end_column: lines[end].len() as u32,
+    }
+}
+
 /// Build a synthetic source string for an external symbol (no project source file available).
 /// Combines the signature line with up to 5 doc lines. Kept for potential future use (e.g. display).
 #[allow(dead_code)]
@@ -596,22 +1087,210 @@ fn build_external_source(signature: &Option<String>, doc_texts: &[String]) -> St
     }
 }
 
-/// Max context_size for external symbols; signatures only, no implementation.
+/// Languages whose interfaces are satisfied structurally (no `implements` declaration).
+const STRUCTURALLY_TYPED_LANGUAGES: &[&str] = &["go"];
+
+/// Register a concrete type as implementor of an interface when its method set contains
+/// every method of the interface. Only applies to types from structurally typed languages.
+fn infer_structural_implementors(
+    semantic_data: &SemanticData,
+    method_by_scope: &HashMap<(SymbolId, String), Vec<NodeIndex>>,
+    type_registry: &mut TypeRegistry,
+) {
+    let mut interfaces: Vec<&SymbolId> = Vec::new();
+    let mut concrete_types: Vec<&SymbolId> = Vec::new();
+    for document in &semantic_data.documents {
+        if !STRUCTURALLY_TYPED_LANGUAGES.contains(&document.language.to_lowercase().as_str()) {
+            continue;
+        }
+        for def in &document.definitions {
+            if let SymbolDetails::Type(details) = &def.details {
+                if details.kind == crate::domain::semantic::TypeKind::Interface {
+                    interfaces.push(&def.symbol_id);
+                } else {
+                    concrete_types.push(&def.symbol_id);
+                }
+            }
+        }
+    }
+    if interfaces.is_empty() {
+        return;
+    }
+
+    let mut method_names: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for (scope, name) in method_by_scope.keys() {
+        method_names
+            .entry(scope.as_str())
+            .or_default()
+            .insert(name.as_str());
+    }
+
+    for interface_id in interfaces {
+        let Some(required) = method_names.get(interface_id.as_str()) else {
+            continue; // empty interface (`interface{}`) would match everything
+        };
+        for concrete_id in &concrete_types {
+            let satisfies = method_names
+                .get(concrete_id.as_str())
+                .is_some_and(|provided| required.is_subset(provided));
+            let already_known = type_registry
+                .get_implementors(interface_id)
+                .is_some_and(|known| known.contains(concrete_id));
+            if satisfies && !already_known {
+                type_registry.register_implementor(interface_id.clone(), (*concrete_id).clone());
+            }
+        }
+    }
+}
+
+/// Collapse external symbols sharing a symbol_id (e.g. merged indexes from different
+/// stdlib versions), keeping the entry with the richest metadata. With `normalize_versions`,
+/// ids differing only in their package version count as the same symbol.
+/// Returns the survivors in first-seen order, an alias map from each dropped id to the
+/// surviving id (when they differ), and one warning per conflicting symbol.
+fn dedup_external_symbols(
+    defs: &[SymbolDefinition],
+    normalize_versions: bool,
+) -> (
+    Vec<&SymbolDefinition>,
+    HashMap<SymbolId, SymbolId>,
+    Vec<String>,
+) {
+    let mut kept: Vec<&SymbolDefinition> = Vec::with_capacity(defs.len());
+    let mut position: HashMap<Cow<'_, str>, usize> = HashMap::new();
+    let mut dropped: Vec<(&str, usize)> = Vec::new();
+    let mut warnings = Vec::new();
+
+    for def in defs {
+        let key = if normalize_versions {
+            normalize_symbol_version(&def.symbol_id)
+        } else {
+            Cow::Borrowed(def.symbol_id.as_str())
+        };
+        match position.get(&key) {
+            None => {
+                position.insert(key, kept.len());
+                kept.push(def);
+            }
+            Some(&pos) => {
+                let current = kept[pos];
+                if metadata_richness(def) > metadata_richness(current) {
+                    kept[pos] = def;
+                    dropped.push((current.symbol_id.as_str(), pos));
+                } else {
+                    dropped.push((def.symbol_id.as_str(), pos));
+                }
+                warnings.push(format!(
+                    "Conflicting external symbol definitions for {}; keeping the one with richer metadata",
+                    def.symbol_id
+                ));
+            }
+        }
+    }
+
+    let aliases = dropped
+        .into_iter()
+        .filter(|(id, pos)| *id != kept[*pos].symbol_id)
+        .map(|(id, pos)| (id.to_string(), kept[pos].symbol_id.clone()))
+        .collect();
+
+    (kept, aliases, warnings)
+}
+
+/// Ordering key for external symbol metadata: signature information first, then docs.
+fn metadata_richness(def: &SymbolDefinition) -> (bool, usize) {
+    let has_signature = match &def.details {
+        SymbolDetails::Function(f) => !f.parameters.is_empty() || !f.return_types.is_empty(),
+        SymbolDetails::Variable(v) => v.var_type.is_some(),
+        SymbolDetails::Type(t) => !t.fields.is_empty() || !t.type_params.is_empty(),
+    };
+    let doc_len = def.documentation.iter().map(|d| d.trim().len()).sum();
+    (has_signature, doc_len)
+}
+
+/// TypeScript ambient declaration file (`foo.d.ts`, also `.d.mts`/`.d.cts`).
+fn is_declaration_file(relative_path: &str) -> bool {
+    let lower = relative_path.to_lowercase();
+    [".d.ts", ".d.mts", ".d.cts"]
+        .iter()
+        .any(|suffix| lower.ends_with(suffix))
+}
+
+/// Method name from a symbol's last descriptor: `pkg/Repo#save().` / `Repo#save(+1).` -> `save`.
+/// None unless the symbol ends with a method descriptor.
+fn method_name_from_symbol(symbol: &str) -> Option<String> {
+    let rest = symbol.strip_suffix(").")?;
+    let rest = &rest[..rest.rfind('(')?];
+    let name = if let Some(quoted) = rest.strip_suffix('`') {
+        &quoted[quoted.rfind('`')? + 1..]
+    } else {
+        let start = rest
+            .rfind(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+            .map_or(0, |i| i + 1);
+        &rest[start..]
+    };
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// The same namespace member with the other parent separator: `Foo/bar().` <-> `Foo.bar().`.
+///
+/// TypeScript namespaces (and `export =` modules) are encoded as namespace descriptors (`/`)
+/// at the definition but can be referenced through the namespace's value binding, a term
+/// descriptor (`.`). Returns None if the parent of the last descriptor is neither.
+fn typescript_namespace_alias(symbol: &str) -> Option<String> {
+    // Strip the last descriptor's suffix: `bar().` / `bar(+1).` (method), `bar.` (term), `Bar#` (type)
+    let mut rest = symbol;
+    if let Some(stripped) = rest.strip_suffix('#') {
+        rest = stripped;
+    } else {
+        rest = rest.strip_suffix('.')?;
+        if rest.ends_with(')') {
+            rest = &rest[..rest.rfind('(')?];
+        }
+    }
+    // Then its name: an identifier or a backtick-quoted name
+    let name_start = if let Some(quoted) = rest.strip_suffix('`') {
+        quoted.rfind('`')?
+    } else {
+        rest.rfind(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))? + 1
+    };
+    if name_start == rest.len() || name_start == 0 {
+        return None;
+    }
+    let separator = match &rest[name_start - 1..name_start] {
+        "/" => '.',
+        "." => '/',
+        _ => return None,
+    };
+    // `src/`a.ts`/bar().` has no namespace: the `/` belongs to the file path
+    if rest[..name_start - 1].ends_with('`') {
+        return None;
+    }
+    let mut alias = symbol.to_string();
+    alias.replace_range(name_start - 1..name_start, &separator.to_string());
+    Some(alias)
+}
+
+/// Default max context_size for external symbols; signatures only, no implementation.
 /// We assign a small fixed size to external symbols because we don't explore their bodies.
-const EXTERNAL_SYMBOL_MAX_TOKENS: u32 = 50;
+pub const EXTERNAL_SYMBOL_MAX_TOKENS: u32 = 50;
 
-/// Length at which to truncate external symbol signatures to avoid token explosion.
-const EXTERNAL_SIGNATURE_TRUNCATE_LEN: usize = 200;
+/// Default length (in characters) at which external symbol signatures are truncated to avoid
+/// token explosion.
+pub const EXTERNAL_SIGNATURE_TRUNCATE_LEN: usize = 200;
 
 /// Build a minimal synthetic source for external symbol context_size: signature only.
 /// Does not include doc or implementation; external library bodies are not useful for CF.
-/// Long signatures (e.g. FastAPI File/Form) are truncated to avoid token explosion.
-fn build_external_signature_only(signature: &Option<String>, def: &SymbolDefinition) -> String {
+/// Long signatures (e.g. FastAPI File/Form) are truncated to `truncate_len` characters.
+fn build_external_signature_only(
+    signature: &Option<String>,
+    def: &SymbolDefinition,
+    truncate_len: usize,
+) -> String {
     if let Some(sig) = signature {
-        let truncated = if sig.len() > EXTERNAL_SIGNATURE_TRUNCATE_LEN {
-            format!("{}...", &sig[..EXTERNAL_SIGNATURE_TRUNCATE_LEN])
-        } else {
-            sig.clone()
+        let truncated = match sig.char_indices().nth(truncate_len) {
+            Some((cut, _)) => format!("{}...", &sig[..cut]),
+            None => sig.clone(),
         };
         format!("{} {}", def.name, truncated)
     } else {
@@ -628,6 +1307,32 @@ fn infer_node_type_from_kind(kind: &SymbolKind) -> NodeType {
     }
 }
 
+/// Documentation-scoring view of `def`; the API surface fields come from its
+/// [`FunctionDetails`](crate::domain::semantic::FunctionDetails) (empty for non-functions).
+fn node_info(
+    def: &SymbolDefinition,
+    signature: Option<String>,
+    language: Option<String>,
+) -> NodeInfo {
+    let (param_names, has_return_type, is_abstract) = match &def.details {
+        SymbolDetails::Function(func) => (
+            func.parameters.iter().map(|p| p.name.clone()).collect(),
+            !func.return_types.is_empty(),
+            func.modifiers.is_abstract,
+        ),
+        _ => (Vec::new(), false, false),
+    };
+    NodeInfo {
+        node_type: infer_node_type_from_kind(&def.kind),
+        name: def.name.clone(),
+        signature,
+        language,
+        param_names,
+        has_return_type,
+        is_abstract,
+    }
+}
+
 /// Extract signature text for documentation scoring
 fn extract_signature(def: &SymbolDefinition) -> Option<String> {
     match &def.details {
@@ -726,6 +1431,8 @@ fn convert_mutability(mutability: &Mutability) -> NodeMutability {
 fn create_type_info(def: &SymbolDefinition, context_size: u32, doc_score: f32) -> TypeInfo {
     let mut type_kind = TypeKind::Class;
     let mut is_abstract = false;
+    let mut is_data_class = false;
+    let mut is_frozen = false;
     let mut type_param_count = 0;
     let mut type_var_info = None;
 
@@ -756,6 +1463,8 @@ fn create_type_info(def: &SymbolDefinition, context_size: u32, doc_score: f32) -
             _ => TypeKind::Class,
         };
         is_abstract = type_details.is_abstract;
+        is_data_class = type_details.is_data_class;
+        is_frozen = type_details.is_frozen;
         type_param_count = type_details.type_params.len() as u32;
     }
 
@@ -763,6 +1472,8 @@ fn create_type_info(def: &SymbolDefinition, context_size: u32, doc_score: f32) -
         definition: TypeDefAttribute {
             type_kind,
             is_abstract,
+            is_data_class,
+            is_frozen,
             type_param_count,
             type_var_info,
         },
@@ -771,6 +1482,403 @@ fn create_type_info(def: &SymbolDefinition, context_size: u32, doc_score: f32) -
     }
 }
 
+/// Read the declared return type name from a Rust (`-> Foo {`), TypeScript
+/// (`(): Foo =>` / `(): Foo {`) or Julia (`)::Foo`) signature. Generic wrappers and paths are
+/// reduced to the head identifier (`crate::m::Foo<T>` → `Foo`); `()`/`void`-like types yield None.
+/// Other languages get their return annotations from the adapter.
+fn infer_return_type_name(
+    language: &str,
+    span: &SemanticSpan,
+    source_code: &str,
+) -> Option<String> {
+    let text = span_text(span, source_code);
+    let return_type = match language {
+        "julia" => {
+            let (_, close) = parameter_list(&text)?;
+            let annotation = text[close + 1..].trim_start().strip_prefix("::")?;
+            cut_annotation(annotation, &["\n", "=", "where"])
+        }
+        _ => parse_function_signature_for_language(language, &text)?.return_type,
+    };
+    return_type.as_deref().and_then(type_head_name)
+}
+
+/// Head identifier of a type annotation (`crate::m::Foo<T>` → `Foo`); None for unit-like types.
+fn type_head_name(annotation: &str) -> Option<String> {
+    let head = annotation
+        .trim()
+        .trim_start_matches('&')
+        .trim_start_matches("mut ")
+        .trim_start_matches("impl ")
+        .split(['<', '[', '{', ' ', '|'])
+        .next()?;
+    let name = head.rsplit(['.', ':']).next()?.trim();
+    let is_unit = matches!(name, "" | "()" | "void" | "never" | "undefined" | "Self");
+    (!is_unit && name.chars().all(|c| c.is_alphanumeric() || c == '_')).then(|| name.to_string())
+}
+
+/// Source lines covered by `span`, joined with newlines.
+fn span_text(span: &SemanticSpan, source_code: &str) -> String {
+    source_code
+        .lines()
+        .skip(span.start_line as usize)
+        .take((span.end_line.saturating_sub(span.start_line) + 1) as usize)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn is_typescript_like(language: &str) -> bool {
+    matches!(
+        language,
+        "typescript" | "typescriptreact" | "tsx" | "javascript" | "javascriptreact" | "jsx"
+    )
+}
+
+/// Parameters and return annotation read from a function's source text.
+#[derive(Debug, Default)]
+struct ParsedSignature {
+    parameters: Vec<Parameter>,
+    /// Annotation text as written (e.g. `Promise<Foo>`).
+    return_type: Option<String>,
+}
+
+/// Parse a function signature from source for languages whose adapters may not deliver
+/// parameters. None for languages without a parser.
+fn parse_function_signature_for_language(language: &str, text: &str) -> Option<ParsedSignature> {
+    match language {
+        "rust" => Some(parse_function_signature_rust(text)),
+        "ruby" => Some(parse_function_signature_ruby(text)),
+        _ if is_typescript_like(language) => Some(parse_function_signature_typescript(text)),
+        _ => None,
+    }
+}
+
+/// Parse `(x: number, y?: string, ...rest: T[]): Promise<Foo>` (also arrow functions and
+/// methods). Optional (`?`) and defaulted parameters set `has_default`; the `this` pseudo
+/// parameter is skipped. Parameter types are kept as written.
+fn parse_function_signature_typescript(text: &str) -> ParsedSignature {
+    let Some((open, close)) = parameter_list(text) else {
+        return ParsedSignature::default();
+    };
+    let parameters = split_top_level(&text[open + 1..close], ',', "\"'`")
+        .into_iter()
+        .filter_map(parse_typescript_parameter)
+        .collect();
+    // `): Foo {` / `): Foo =>` / `): Foo;`
+    let return_type = text[close + 1..]
+        .trim_start()
+        .strip_prefix(':')
+        .and_then(|annotation| cut_annotation(annotation, &["=>", "{", ";"]));
+
+    ParsedSignature {
+        parameters,
+        return_type,
+    }
+}
+
+/// Parse `fn foo<T>(&self, x: i32, y: &mut Bar) -> Result<T, E> where ...`. Receivers are
+/// dropped; references, `mut` and lifetimes are stripped from parameter types.
+fn parse_function_signature_rust(text: &str) -> ParsedSignature {
+    let Some((open, close)) = parameter_list(text) else {
+        return ParsedSignature::default();
+    };
+    let parameters = split_top_level(&text[open + 1..close], ',', "\"")
+        .into_iter()
+        .filter_map(parse_rust_parameter)
+        .collect();
+    let return_type = text[close + 1..]
+        .trim_start()
+        .strip_prefix("->")
+        .and_then(|annotation| cut_annotation(annotation, &["{", ";", "where"]));
+
+    ParsedSignature {
+        parameters,
+        return_type,
+    }
+}
+
+/// Parse `def name(a, b = 1, *args, key:, opt: 2, **kw, &blk)`, with or without parentheses.
+/// Ruby has no annotations, so parameters are untyped and there is no return type. Defaulted
+/// positional and keyword parameters and the block set `has_default`; splats set `is_variadic`.
+fn parse_function_signature_ruby(text: &str) -> ParsedSignature {
+    let Some(def) = text.find("def ") else {
+        return ParsedSignature::default();
+    };
+    // Method name, possibly `self.`-qualified or an operator (`==`, `[]=`)
+    let header = text[def + 4..].trim_start();
+    let rest = header
+        .trim_start_matches(|c: char| !c.is_whitespace() && c != '(' && c != ';')
+        .trim_start_matches([' ', '\t']);
+    let params_text = if rest.starts_with('(') {
+        match parameter_list(rest) {
+            Some((open, close)) => &rest[open + 1..close],
+            None => return ParsedSignature::default(),
+        }
+    } else {
+        // `def name a, b` ends at the line or statement end
+        rest.split(['\n', ';']).next().unwrap_or_default()
+    };
+    let parameters = split_top_level(params_text, ',', "\"'")
+        .into_iter()
+        .filter_map(parse_ruby_parameter)
+        .collect();
+
+    ParsedSignature {
+        parameters,
+        return_type: None,
+    }
+}
+
+/// One Ruby parameter: `a`, `a = 1`, `key:`, `key: 1`, `*args`, `**opts`, `&blk` or `...`.
+fn parse_ruby_parameter(text: &str) -> Option<Parameter> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    if text == "..." {
+        return Some(Parameter {
+            name: text.to_string(),
+            is_variadic: true,
+            ..Default::default()
+        });
+    }
+    if let Some(block) = text.strip_prefix('&') {
+        // The block is optional for callers
+        return Some(Parameter {
+            name: block.trim().to_string(),
+            has_default: true,
+            ..Default::default()
+        });
+    }
+    if let Some(splat) = text.strip_prefix("**").or_else(|| text.strip_prefix('*')) {
+        return Some(Parameter {
+            name: splat.trim().to_string(),
+            is_variadic: true,
+            ..Default::default()
+        });
+    }
+    let is_identifier =
+        |s: &str| !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_');
+    // Keyword argument: `key:` (required) or `key: value`
+    if let Some((name, value)) = text.split_once(':')
+        && is_identifier(name)
+        && !value.starts_with(':')
+    {
+        return Some(Parameter {
+            name: name.to_string(),
+            has_default: !value.trim().is_empty(),
+            ..Default::default()
+        });
+    }
+    let parts = split_top_level(text, '=', "\"'");
+    Some(Parameter {
+        name: parts[0].trim().to_string(),
+        has_default: parts.len() > 1,
+        ..Default::default()
+    })
+}
+
+/// One TypeScript parameter: `name`, `name?: T`, `name: T = value`, `...name: T[]`, or a
+/// constructor parameter property (`private readonly name: T`).
+fn parse_typescript_parameter(text: &str) -> Option<Parameter> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let parts = split_top_level(text, '=', "\"'`");
+    let has_default_value = parts.len() > 1;
+    let declaration = split_top_level(parts[0], ':', "\"'`");
+    let param_type = declaration.get(1).map(|t| t.trim());
+
+    let mut name = declaration[0].trim();
+    while let Some(rest) = [
+        "public ",
+        "private ",
+        "protected ",
+        "readonly ",
+        "override ",
+    ]
+    .iter()
+    .find_map(|modifier| name.strip_prefix(modifier))
+    {
+        name = rest.trim_start();
+    }
+    let (name, is_variadic) = match name.strip_prefix("...") {
+        Some(rest) => (rest.trim(), true),
+        None => (name, false),
+    };
+    let (name, is_optional) = match name.strip_suffix('?') {
+        Some(rest) => (rest.trim(), true),
+        None => (name, false),
+    };
+    if name == "this" {
+        return None;
+    }
+
+    let param_type = param_type.filter(|t| !t.is_empty()).map(|t| {
+        t.strip_suffix("[]")
+            .filter(|_| is_variadic)
+            .unwrap_or(t)
+            .to_string()
+    });
+    let is_high_freedom_type = param_type
+        .as_deref()
+        .is_some_and(|t| matches!(t, "any" | "unknown" | "object" | "Object" | "Function"));
+    Some(Parameter {
+        name: name.to_string(),
+        param_type,
+        is_high_freedom_type,
+        has_default: has_default_value || is_optional,
+        is_variadic,
+    })
+}
+
+/// One Rust parameter `pattern: Type`; None for receivers (`self`, `&'a mut self`,
+/// `self: Box<Self>`).
+fn parse_rust_parameter(text: &str) -> Option<Parameter> {
+    let text = text.trim();
+    // The pattern ends at the first `:` that is not part of a `::` path
+    let bytes = text.as_bytes();
+    let colon = (0..bytes.len()).find(|&i| {
+        bytes[i] == b':' && bytes.get(i + 1) != Some(&b':') && (i == 0 || bytes[i - 1] != b':')
+    });
+    let (pattern, param_type) = match colon {
+        Some(i) => (&text[..i], Some(strip_rust_reference(&text[i + 1..]))),
+        None => (text, None),
+    };
+    let name = strip_rust_reference(pattern);
+    if name.is_empty() || name == "self" {
+        return None;
+    }
+    Some(Parameter {
+        name: name.to_string(),
+        param_type: param_type.filter(|t| !t.is_empty()).map(str::to_string),
+        ..Default::default()
+    })
+}
+
+/// Strip leading `&`, lifetimes and `mut` (`&'a mut Bar` → `Bar`).
+fn strip_rust_reference(text: &str) -> &str {
+    let mut text = text.trim();
+    loop {
+        if let Some(rest) = text.strip_prefix('&') {
+            text = rest.trim_start();
+        } else if let Some(rest) = text.strip_prefix('\'') {
+            text = rest
+                .trim_start_matches(|c: char| c.is_alphanumeric() || c == '_')
+                .trim_start();
+        } else if let Some(rest) = text.strip_prefix("mut ") {
+            text = rest.trim_start();
+        } else {
+            return text;
+        }
+    }
+}
+
+/// Byte range of the parameter list: the first `(` outside generic or attribute brackets and
+/// its matching `)`.
+fn parameter_list(text: &str) -> Option<(usize, usize)> {
+    let mut depth = 0;
+    let mut open = None;
+    let mut prev = ' ';
+    for (i, c) in text.char_indices() {
+        let is_arrow = c == '>' && (prev == '-' || prev == '=');
+        prev = c;
+        match (c, open) {
+            ('<' | '[', None) => depth += 1,
+            ('>' | ']', None) if !is_arrow => depth -= 1,
+            ('(', None) if depth == 0 => {
+                open = Some(i);
+                depth = 1;
+            }
+            ('(', Some(_)) => depth += 1,
+            (')', Some(start)) => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some((start, i));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Annotation text up to the first of `markers` outside brackets, trimmed; None if empty.
+/// Word markers (`where`) only match as whole words.
+fn cut_annotation(annotation: &str, markers: &[&str]) -> Option<String> {
+    let mut depth = 0;
+    let mut end = annotation.len();
+    let mut prev = ' ';
+    for (i, c) in annotation.char_indices() {
+        let at_marker = markers.iter().any(|m| {
+            annotation[i..].starts_with(m)
+                && !(c.is_alphabetic() && (prev.is_alphanumeric() || prev == '_'))
+        });
+        if depth == 0 && at_marker {
+            end = i;
+            break;
+        }
+        match c {
+            '(' | '[' | '<' => depth += 1,
+            '>' if prev == '=' || prev == '-' => {}
+            ')' | ']' | '>' => depth -= 1,
+            _ => {}
+        }
+        prev = c;
+    }
+    let annotation = annotation[..end].trim();
+    (!annotation.is_empty()).then(|| annotation.to_string())
+}
+
+/// Split `text` at `separator` outside (), [], {}, <> and the given string `quotes`. The `>`
+/// of `=>`/`->` never closes a generic bracket, and `=>` is never split at `=`.
+fn split_top_level<'a>(text: &'a str, separator: char, quotes: &str) -> Vec<&'a str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+    let mut prev = '\0';
+    for (i, c) in text.char_indices() {
+        let next_is_arrow = c == '=' && text[i..].starts_with("=>");
+        if let Some(q) = quote {
+            if c == q && prev != '\\' {
+                quote = None;
+            }
+        } else {
+            match c {
+                _ if quotes.contains(c) => quote = Some(c),
+                '(' | '[' | '{' | '<' => depth += 1,
+                '>' if prev == '=' || prev == '-' => {}
+                ')' | ']' | '}' | '>' => depth -= 1,
+                _ if c == separator && depth == 0 && !next_is_arrow => {
+                    parts.push(&text[start..i]);
+                    start = i + c.len_utf8();
+                }
+                _ => {}
+            }
+        }
+        prev = c;
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Resolve a bare type name to a type symbol: prefer a definition in the same file, else a
+/// unique definition anywhere in the project.
+fn resolve_type_by_name<'a>(
+    types_by_name: &HashMap<&str, Vec<(&str, &'a SymbolId)>>,
+    relative_path: &str,
+    name: &str,
+) -> Option<&'a SymbolId> {
+    let candidates = types_by_name.get(name)?;
+    candidates
+        .iter()
+        .find(|(path, _)| *path == relative_path)
+        .or_else(|| (candidates.len() == 1).then(|| &candidates[0]))
+        .map(|(_, id)| *id)
+}
+
 /// Extract only the signature portion of a method span (first line to colon/semicolon)
 /// For interface methods, we only want to count the signature, not any implementation body
 fn extract_signature_span(span: &SemanticSpan, source_code: &str) -> SourceSpan {
@@ -832,11 +1940,48 @@ mod tests {
             },
             enclosing_symbol: None,
             is_external: false,
+            is_generated: false,
             documentation: vec![],
             details: SymbolDetails::Function(FunctionDetails::default()),
         }
     }
 
+    #[test]
+    fn test_method_name_from_symbol_reads_last_method_descriptor() {
+        let name = |s| method_name_from_symbol(s);
+        assert_eq!(
+            name("scip-python python app 1.0 repo/Repo#save().").as_deref(),
+            Some("save")
+        );
+        assert_eq!(name("pkg/Repo#save(+1).").as_deref(), Some("save"));
+        assert_eq!(name("pkg/Repo#`<init>`().").as_deref(), Some("<init>"));
+        assert_eq!(name("Repo#save()."), Some("save".to_string()));
+        assert_eq!(name("pkg/Repo#"), None);
+        assert_eq!(name("pkg/Repo#field."), None);
+    }
+
+    #[test]
+    fn test_typescript_namespace_alias_swaps_member_parent_separator() {
+        let ns = "scip-typescript npm app 1.0 src/`a.ts`/Foo/bar().";
+        let term = "scip-typescript npm app 1.0 src/`a.ts`/Foo.bar().";
+        assert_eq!(typescript_namespace_alias(ns).as_deref(), Some(term));
+        assert_eq!(typescript_namespace_alias(term).as_deref(), Some(ns));
+        assert_eq!(
+            typescript_namespace_alias("scip-typescript npm app 1.0 src/`a.ts`/Foo/Bar#")
+                .as_deref(),
+            Some("scip-typescript npm app 1.0 src/`a.ts`/Foo.Bar#")
+        );
+        // File-level function, method of a class: nothing to swap
+        assert_eq!(
+            typescript_namespace_alias("scip-typescript npm app 1.0 src/`a.ts`/bar()."),
+            None
+        );
+        assert_eq!(
+            typescript_namespace_alias("scip-typescript npm app 1.0 src/`a.ts`/Foo#bar()."),
+            None
+        );
+    }
+
     #[test]
     fn test_convert_visibility() {
         assert!(matches!(
@@ -857,6 +2002,225 @@ mod tests {
         assert!(!info.definition.is_abstract);
     }
 
+    #[test]
+    fn test_infer_return_type_name_rust_and_typescript() {
+        let span = |start_line, end_line| SemanticSpan {
+            start_line,
+            start_column: 0,
+            end_line,
+            end_column: 0,
+        };
+
+        let rust = "fn make(\n    x: i32,\n) -> crate::model::Foo {\n    Foo { x }\n}\n";
+        assert_eq!(
+            infer_return_type_name("rust", &span(0, 4), rust),
+            Some("Foo".to_string())
+        );
+        let generic = "pub fn load(p: &Path) -> Result<Config, Error> where E: Debug {}";
+        assert_eq!(
+            infer_return_type_name("rust", &span(0, 0), "fn f() -> Somewhere {}"),
+            Some("Somewhere".to_string())
+        );
+        assert_eq!(
+            infer_return_type_name("rust", &span(0, 0), generic),
+            Some("Result".to_string())
+        );
+        assert_eq!(
+            infer_return_type_name("rust", &span(0, 0), "fn run() {}"),
+            None
+        );
+
+        let ts = "const make = (x: number): Foo => {\n  return new Foo(x);\n};\n";
+        assert_eq!(
+            infer_return_type_name("typescript", &span(0, 2), ts),
+            Some("Foo".to_string())
+        );
+        let method = "  async fetch(id: string): Promise<User> {";
+        assert_eq!(
+            infer_return_type_name("typescript", &span(0, 0), method),
+            Some("Promise".to_string())
+        );
+        assert_eq!(
+            infer_return_type_name("typescript", &span(0, 0), "function f(): void {}"),
+            None
+        );
+        // Python return types come from the adapter
+        assert_eq!(
+            infer_return_type_name("python", &span(0, 0), "def f() -> Foo:"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_function_signature_typescript() {
+        let sig = parse_function_signature_typescript(
+            "async load(x: number, y?: string, opts: Map<string, number> = new Map(), \
+             ...rest: Item[]): Promise<Foo> {\n  return x;\n}",
+        );
+        let params: Vec<_> = sig
+            .parameters
+            .iter()
+            .map(|p| {
+                (
+                    p.name.as_str(),
+                    p.param_type.as_deref(),
+                    p.has_default,
+                    p.is_variadic,
+                )
+            })
+            .collect();
+        assert_eq!(
+            params,
+            [
+                ("x", Some("number"), false, false),
+                ("y", Some("string"), true, false),
+                ("opts", Some("Map<string, number>"), true, false),
+                ("rest", Some("Item"), false, true),
+            ]
+        );
+        assert_eq!(sig.return_type.as_deref(), Some("Promise<Foo>"));
+
+        // Arrow function with a callback parameter, `this` pseudo parameter, untyped JS
+        let arrow = parse_function_signature_typescript(
+            "const run = (this: Ctx, cb: (e: Event) => void, n = 1): Result => {",
+        );
+        assert_eq!(arrow.parameters.len(), 2);
+        assert_eq!(
+            arrow.parameters[0].param_type.as_deref(),
+            Some("(e: Event) => void")
+        );
+        assert!(arrow.parameters[1].has_default);
+        assert_eq!(arrow.parameters[1].param_type, None);
+        assert_eq!(arrow.return_type.as_deref(), Some("Result"));
+
+        let ctor = parse_function_signature_typescript(
+            "constructor(private readonly repo: Repo, data: any) {}",
+        );
+        assert_eq!(ctor.parameters[0].name, "repo");
+        assert!(ctor.parameters[1].is_high_freedom_type);
+        assert_eq!(ctor.return_type, None);
+
+        assert!(parse_function_signature_for_language("python", "def f(x: int):").is_none());
+        assert!(
+            parse_function_signature_for_language("javascript", "function f(a, b) {}").is_some()
+        );
+    }
+
+    #[test]
+    fn test_parse_function_signature_rust() {
+        let sig = parse_function_signature_rust(
+            "pub fn apply<'a, F: Fn(u8) -> u8>(&'a mut self, x: i32, y: &mut Bar, \
+             pair: (Vec<u8>, HashMap<String, u32>), name: &'a str, path: &std::path::Path) \
+             -> Result<T, E> where F: Send {\n    todo!()\n}",
+        );
+        let params: Vec<_> = sig
+            .parameters
+            .iter()
+            .map(|p| (p.name.as_str(), p.param_type.as_deref()))
+            .collect();
+        assert_eq!(
+            params,
+            [
+                ("x", Some("i32")),
+                ("y", Some("Bar")),
+                ("pair", Some("(Vec<u8>, HashMap<String, u32>)")),
+                ("name", Some("str")),
+                ("path", Some("std::path::Path")),
+            ]
+        );
+        assert_eq!(sig.return_type.as_deref(), Some("Result<T, E>"));
+
+        let method = parse_function_signature_rust("fn len(&self) -> usize;");
+        assert!(method.parameters.is_empty());
+        assert_eq!(method.return_type.as_deref(), Some("usize"));
+
+        let boxed = parse_function_signature_rust(
+            "fn run(self: Box<Self>, mut cb: impl FnMut() -> bool) {}",
+        );
+        assert_eq!(boxed.parameters.len(), 1);
+        assert_eq!(boxed.parameters[0].name, "cb");
+        assert_eq!(
+            boxed.parameters[0].param_type.as_deref(),
+            Some("impl FnMut() -> bool")
+        );
+        assert_eq!(boxed.return_type, None);
+    }
+
+    #[test]
+    fn test_parse_function_signature_ruby() {
+        let sig = parse_function_signature_ruby(
+            "def self.charge(amount, currency = :usd, *items, key:, retries: 3, **opts, &blk)\n  \
+             process(amount)\nend",
+        );
+        let params: Vec<_> = sig
+            .parameters
+            .iter()
+            .map(|p| {
+                (
+                    p.name.as_str(),
+                    p.param_type.as_deref(),
+                    p.has_default,
+                    p.is_variadic,
+                )
+            })
+            .collect();
+        assert_eq!(
+            params,
+            [
+                ("amount", None, false, false),
+                ("currency", None, true, false),
+                ("items", None, false, true),
+                ("key", None, false, false),
+                ("retries", None, true, false),
+                ("opts", None, false, true),
+                ("blk", None, true, false),
+            ]
+        );
+        assert_eq!(sig.return_type, None);
+
+        // Without parentheses the list ends at the line; calls in the body are not parameters
+        let bare = parse_function_signature_ruby("def update name, age\n  save(name)\nend");
+        let names: Vec<_> = bare.parameters.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["name", "age"]);
+        let none = parse_function_signature_ruby("def reset!\n  clear(all: true)\nend");
+        assert!(none.parameters.is_empty());
+
+        let operator = parse_function_signature_ruby("def ==(other)");
+        assert_eq!(operator.parameters[0].name, "other");
+
+        assert!(parse_function_signature_for_language("ruby", "def f(x); end").is_some());
+    }
+
+    #[test]
+    fn test_infer_return_type_name_julia() {
+        let span = |start_line, end_line| SemanticSpan {
+            start_line,
+            start_column: 0,
+            end_line,
+            end_column: 0,
+        };
+
+        let julia = "function check(x::Int, y::String)::Bool\n    x > length(y)\nend\n";
+        assert_eq!(
+            infer_return_type_name("julia", &span(0, 2), julia),
+            Some("Bool".to_string())
+        );
+        let short = "load(p::String)::Base.Vector{Config} = read(p)";
+        assert_eq!(
+            infer_return_type_name("julia", &span(0, 0), short),
+            Some("Vector".to_string())
+        );
+        let parametric = "function first(v::Vector{T})::T where {T}\n    v[1]\nend";
+        assert_eq!(
+            infer_return_type_name("julia", &span(0, 2), parametric),
+            Some("T".to_string())
+        );
+        assert_eq!(
+            infer_return_type_name("julia", &span(0, 2), "function run(x)\n    x\nend"),
+            None
+        );
+    }
+
     #[test]
     fn test_extract_signature_span_python() {
         let source = "    def method(self, x: int) -> str:\n        return str(x)\n        pass\n";
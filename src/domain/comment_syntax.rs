@@ -0,0 +1,40 @@
+/// Comment syntax of a language.
+#[derive(Debug)]
+pub struct CommentSyntax {
+    /// Marker starting a comment that runs to the end of the line
+    pub line_marker: &'static str,
+    /// `/* ... */` block comments
+    pub block_comments: bool,
+    /// Characters that open and close string literals
+    pub quotes: &'static [char],
+}
+
+pub const HASH_COMMENTS: CommentSyntax = CommentSyntax {
+    line_marker: "#",
+    block_comments: false,
+    quotes: &['"', '\''],
+};
+
+pub const C_FAMILY_COMMENTS: CommentSyntax = CommentSyntax {
+    line_marker: "//",
+    block_comments: true,
+    quotes: &['"', '\'', '`'],
+};
+
+/// Rust uses `'` for lifetimes (`&'a str`), so only `"` delimits strings.
+pub const RUST_COMMENTS: CommentSyntax = CommentSyntax {
+    line_marker: "//",
+    block_comments: true,
+    quotes: &['"'],
+};
+
+/// Comment syntax by SemanticData language name; None when unknown.
+pub fn comment_syntax(language: &str) -> Option<&'static CommentSyntax> {
+    match language {
+        "python" | "ruby" | "shell" | "bash" | "r" | "julia" | "perl" => Some(&HASH_COMMENTS),
+        "rust" => Some(&RUST_COMMENTS),
+        "typescript" | "javascript" | "tsx" | "jsx" | "java" | "go" | "c" | "cpp" | "csharp"
+        | "kotlin" | "swift" | "scala" | "dart" => Some(&C_FAMILY_COMMENTS),
+        _ => None,
+    }
+}
@@ -12,4 +12,11 @@ pub enum EdgeKind {
     OverriddenBy,
     /// Decorated → Decorator (understanding decorated requires decorator)
     Annotates,
+    /// Subtype method → the base-class method it overrides (`super().foo()`, inherited behavior)
+    Inherits,
+    /// Function → constructor of an exception type it declares (`throws`, `Raises:`)
+    Raises,
+    /// Function → constructor (or fields) of a project type one of its parameters is annotated
+    /// with; only built on request (`GraphBuilder::with_param_type_edges`)
+    Uses,
 }
@@ -1,7 +1,9 @@
 use crate::domain::edge::EdgeKind;
 use crate::domain::node::Node;
 use crate::domain::type_registry::TypeRegistry;
+use petgraph::algo::tarjan_scc;
 use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::{EdgeFiltered, EdgeRef};
 use std::collections::HashMap;
 
 /// Symbol identifier (globally unique symbol string)
@@ -15,8 +17,41 @@ pub struct ContextGraph {
     /// Mapping from symbol to node index
     pub symbol_to_node: HashMap<SymbolId, NodeIndex>,
 
+    /// Reverse of [Self::symbol_to_node]: node index to its symbol (kept in sync by [Self::add_node])
+    pub node_to_symbol: HashMap<NodeIndex, SymbolId>,
+
     /// Type registry - stores type definitions outside the graph
     pub type_registry: TypeRegistry,
+
+    /// Non-fatal issues found while building the graph (e.g. conflicting external symbols)
+    pub warnings: Vec<String>,
+
+    /// Calls the builder could not wire to any node, even after type-driven recovery
+    pub unresolved: Vec<UnresolvedRef>,
+}
+
+/// A call reference left without an edge: its callee's context is missing from every CF that
+/// reaches `source`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedRef {
+    /// Node the call was made from
+    pub source: SymbolId,
+    /// Relative path and 0-based line of the call site
+    pub file_path: String,
+    pub line: u32,
+    pub target_symbol: Option<SymbolId>,
+    pub method_name: Option<String>,
+    pub receiver: Option<SymbolId>,
+}
+
+impl UnresolvedRef {
+    /// What was being called: the method name, else the target symbol.
+    pub fn callee(&self) -> &str {
+        self.method_name
+            .as_deref()
+            .or(self.target_symbol.as_deref())
+            .unwrap_or("<unknown>")
+    }
 }
 
 impl Default for ContextGraph {
@@ -30,13 +65,20 @@ impl ContextGraph {
         Self {
             graph: DiGraph::new(),
             symbol_to_node: HashMap::new(),
+            node_to_symbol: HashMap::new(),
             type_registry: TypeRegistry::new(),
+            warnings: Vec::new(),
+            unresolved: Vec::new(),
         }
     }
 
     pub fn add_node(&mut self, symbol: SymbolId, node: Node) -> NodeIndex {
         let idx = self.graph.add_node(node);
-        self.symbol_to_node.insert(symbol, idx);
+        if let Some(previous) = self.symbol_to_node.insert(symbol.clone(), idx) {
+            // Duplicate symbol: the earlier node is no longer addressable by symbol.
+            self.node_to_symbol.remove(&previous);
+        }
+        self.node_to_symbol.insert(idx, symbol);
         idx
     }
 
@@ -48,6 +90,11 @@ impl ContextGraph {
         self.symbol_to_node.get(symbol).copied()
     }
 
+    /// Symbol of the node at `idx` (O(1) reverse lookup).
+    pub fn symbol_of(&self, idx: NodeIndex) -> Option<&str> {
+        self.node_to_symbol.get(&idx).map(String::as_str)
+    }
+
     pub fn node(&self, idx: NodeIndex) -> &Node {
         &self.graph[idx]
     }
@@ -101,6 +148,52 @@ impl ContextGraph {
         members.sort_by(|a, b| a.0.cmp(&b.0));
         members
     }
+
+    /// Innermost node of `file_path` whose span contains `line` (0-based): the smallest
+    /// enclosing span, so a method wins over its class-level neighbours.
+    pub fn innermost_node_at(&self, file_path: &str, line: u32) -> Option<NodeIndex> {
+        self.graph
+            .node_indices()
+            .filter(|&idx| {
+                let core = self.node(idx).core();
+                core.file_path == file_path
+                    && core.span.start_line <= line
+                    && line <= core.span.end_line
+            })
+            .min_by_key(|&idx| {
+                let span = &self.node(idx).core().span;
+                (
+                    span.end_line - span.start_line,
+                    std::cmp::Reverse(span.start_line),
+                )
+            })
+    }
+
+    /// Call cycles: strongly connected components of the Call-edge subgraph with more than
+    /// one node (functions that transitively call each other), largest first.
+    pub fn call_cycles(&self) -> Vec<Vec<NodeIndex>> {
+        let calls = EdgeFiltered::from_fn(&self.graph, |edge| *edge.weight() == EdgeKind::Call);
+        let mut cycles: Vec<Vec<NodeIndex>> = tarjan_scc(&calls)
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .collect();
+        cycles.sort_by_key(|component| std::cmp::Reverse(component.len()));
+        cycles
+    }
+
+    /// Functions that call themselves directly (Call self-loops), which [`Self::call_cycles`]
+    /// leaves out as single-node components.
+    pub fn self_calls(&self) -> Vec<NodeIndex> {
+        let mut nodes: Vec<NodeIndex> = self
+            .graph
+            .edge_references()
+            .filter(|edge| edge.source() == edge.target() && *edge.weight() == EdgeKind::Call)
+            .map(|edge| edge.source())
+            .collect();
+        nodes.sort();
+        nodes.dedup();
+        nodes
+    }
 }
 
 #[cfg(test)]
@@ -168,6 +261,59 @@ mod tests {
         assert!(matches!(out[0].1, EdgeKind::Call));
     }
 
+    #[test]
+    fn test_innermost_node_at_prefers_smallest_span() {
+        let with_span = |id, name: &str, start_line, end_line| {
+            let mut node = test_node(id, name, 10);
+            if let Node::Function(f) = &mut node {
+                f.core.span.start_line = start_line;
+                f.core.span.end_line = end_line;
+            }
+            node
+        };
+        let mut graph = ContextGraph::new();
+        let outer = graph.add_node("sym::outer".into(), with_span(0, "outer", 0, 20));
+        let inner = graph.add_node("sym::inner".into(), with_span(1, "inner", 5, 8));
+
+        assert_eq!(graph.innermost_node_at("test.py", 6), Some(inner));
+        assert_eq!(graph.innermost_node_at("test.py", 12), Some(outer));
+        assert_eq!(graph.innermost_node_at("test.py", 21), None);
+        assert_eq!(graph.innermost_node_at("other.py", 6), None);
+    }
+
+    #[test]
+    fn test_call_cycles_ignore_non_call_edges() {
+        let mut graph = ContextGraph::new();
+        let a = graph.add_node("sym::a".into(), test_node(0, "a", 10));
+        let b = graph.add_node("sym::b".into(), test_node(1, "b", 10));
+        let c = graph.add_node("sym::c".into(), test_node(2, "c", 10));
+        let d = graph.add_node("sym::d".into(), test_node(3, "d", 10));
+        graph.add_edge(a, b, EdgeKind::Call);
+        graph.add_edge(b, c, EdgeKind::Call);
+        graph.add_edge(c, a, EdgeKind::Call);
+        graph.add_edge(c, d, EdgeKind::Call);
+        graph.add_edge(d, c, EdgeKind::OverriddenBy);
+
+        let cycles = graph.call_cycles();
+        assert_eq!(cycles.len(), 1);
+        let mut cycle = cycles[0].clone();
+        cycle.sort();
+        assert_eq!(cycle, vec![a, b, c]);
+    }
+
+    #[test]
+    fn test_self_calls_are_reported_apart_from_cycles() {
+        let mut graph = ContextGraph::new();
+        let a = graph.add_node("sym::a".into(), test_node(0, "a", 10));
+        let b = graph.add_node("sym::b".into(), test_node(1, "b", 10));
+        graph.add_edge(a, a, EdgeKind::Call);
+        graph.add_edge(a, b, EdgeKind::Call);
+        graph.add_edge(b, b, EdgeKind::Read);
+
+        assert!(graph.call_cycles().is_empty());
+        assert_eq!(graph.self_calls(), vec![a]);
+    }
+
     #[test]
     fn test_get_node_by_symbol() {
         let mut graph = ContextGraph::new();
@@ -219,6 +365,8 @@ mod tests {
         assert_eq!(graph.graph.node_count(), 2);
         assert_eq!(graph.get_node_by_symbol("sym::dup"), Some(i2));
         assert_eq!(graph.node(i2).core().context_size, 20);
+        assert_eq!(graph.symbol_of(i2), Some("sym::dup"));
+        assert_eq!(graph.symbol_of(_i1), None);
     }
 
     #[test]
@@ -276,6 +424,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_node_to_symbol_consistent_with_symbol_to_node() {
+        let mut graph = ContextGraph::new();
+        for (i, s) in ["sym::a", "sym::b", "sym::a", "sym::c"].iter().enumerate() {
+            graph.add_node((*s).into(), test_node(i as u32, s, 1));
+        }
+        assert_eq!(graph.node_to_symbol.len(), graph.symbol_to_node.len());
+        for (sym, &idx) in &graph.symbol_to_node {
+            assert_eq!(graph.symbol_of(idx), Some(sym.as_str()));
+        }
+        for (&idx, sym) in &graph.node_to_symbol {
+            assert_eq!(graph.get_node_by_symbol(sym), Some(idx));
+        }
+    }
+
     #[test]
     fn test_neighbors_only_outgoing() {
         let mut graph = ContextGraph::new();
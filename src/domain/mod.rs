@@ -1,7 +1,9 @@
 pub mod builder;
+pub mod comment_syntax;
 pub mod edge;
 pub mod graph;
 pub mod node;
+pub mod path_glob;
 pub mod policy;
 pub mod ports;
 pub mod semantic;
@@ -22,6 +22,8 @@ pub struct NodeCore {
     pub name: String,
     pub scope: Option<ScopeId>,
     pub context_size: u32, // Abstract context size (computed by SizeFunction)
+    /// Size of the signature (+ docs) alone, when smaller than the full `context_size`.
+    pub signature_size: Option<u32>,
     pub span: SourceSpan,
     pub doc_score: f32, // Documentation quality score [0.0, 1.0]
     pub is_external: bool,
@@ -45,12 +47,18 @@ impl NodeCore {
             name,
             scope,
             context_size,
+            signature_size: None,
             span,
             doc_score,
             is_external,
             file_path,
         }
     }
+
+    /// Size a reader pays when this node is a boundary and only its signature is read.
+    pub fn signature_or_context_size(&self) -> u32 {
+        self.signature_size.unwrap_or(self.context_size)
+    }
 }
 
 /// Visibility level
@@ -0,0 +1,95 @@
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Gitignore-style path pattern matched against project-relative paths.
+///
+/// - `*` and `?` match within one path segment, `**` across segments (`migrations/**`).
+/// - A pattern without `/` matches a file or directory name at any depth (`*_pb2.py`, `vendor`).
+/// - A pattern with `/` is anchored at the project root.
+/// - A match on a directory covers everything below it.
+#[derive(Debug, Clone)]
+pub struct PathGlob {
+    pattern: String,
+    regex: Regex,
+}
+
+impl PathGlob {
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        let trimmed = pattern.trim_start_matches("./").trim_start_matches('/');
+        let mut re = String::from(if pattern.contains('/') { "^" } else { "(^|/)" });
+        let mut chars = trimmed.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        re.push_str("(.*/)?");
+                    } else {
+                        re.push_str(".*");
+                    }
+                }
+                '*' => re.push_str("[^/]*"),
+                '?' => re.push_str("[^/]"),
+                _ => re.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        re.push_str("(/.*)?$");
+        Ok(Self {
+            pattern: pattern.to_string(),
+            regex: Regex::new(&re)?,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// True if `path` (relative to the project root, `/`-separated) is covered by the pattern.
+    pub fn is_match(&self, path: &str) -> bool {
+        self.regex.is_match(path.trim_start_matches("./"))
+    }
+}
+
+impl Serialize for PathGlob {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.pattern.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PathGlob {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        PathGlob::new(&pattern).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unanchored_pattern_matches_names_at_any_depth() {
+        let generated = PathGlob::new("*_pb2.py").unwrap();
+        assert!(generated.is_match("api/v1/user_pb2.py"));
+        assert!(generated.is_match("user_pb2.py"));
+        assert!(!generated.is_match("api/user_pb2.pyi"));
+
+        let vendor = PathGlob::new("vendor").unwrap();
+        assert!(vendor.is_match("vendor/lib/x.py"));
+        assert!(vendor.is_match("pkg/vendor/x.py"));
+        assert!(!vendor.is_match("pkg/vendored.py"));
+    }
+
+    #[test]
+    fn test_anchored_pattern_and_double_star() {
+        let migrations = PathGlob::new("migrations/**").unwrap();
+        assert!(migrations.is_match("migrations/0001_init.py"));
+        assert!(!migrations.is_match("app/migrations/0001_init.py"));
+
+        let nested = PathGlob::new("**/migrations/*.py").unwrap();
+        assert!(nested.is_match("app/migrations/0001_init.py"));
+        assert!(nested.is_match("migrations/0001_init.py"));
+        assert!(!nested.is_match("app/migrations.py"));
+    }
+}
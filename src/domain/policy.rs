@@ -1,7 +1,12 @@
 use crate::domain::edge::EdgeKind;
 use crate::domain::graph::ContextGraph;
-use crate::domain::node::Node;
-use crate::domain::type_registry::TypeRegistry;
+use crate::domain::node::{FunctionNode, Node};
+use crate::domain::semantic::SymbolDefinition;
+use crate::domain::type_registry::{TypeKind, TypeRegistry};
+use petgraph::graph::NodeIndex;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 /// Node type for documentation scoring
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -13,19 +18,152 @@ pub enum NodeType {
 /// Pruning decision
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PruningDecision {
-    Boundary,    // Stop traversal here; node is a valid abstraction
-    Transparent, // Continue traversal through this node
+    Boundary(BoundaryReason), // Stop traversal here; node is a valid abstraction
+    Transparent,              // Continue traversal through this node
+}
+
+impl PruningDecision {
+    pub fn is_boundary(&self) -> bool {
+        matches!(self, PruningDecision::Boundary(_))
+    }
+}
+
+/// Why [`evaluate_forward`] stopped at a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundaryReason {
+    /// Decided by a [`CustomPruningRule`].
+    CustomRule,
+    /// The source is a stub (package/module/synthetic, context_size 0); nothing is expanded from it.
+    StubNode,
+    /// The target is outside the project.
+    External,
+    /// The target is smaller than `min_traversal_size`.
+    Trivial,
+    /// Implementation reached over an OverriddenBy edge while `follow_overrides` is off.
+    OverrideNotFollowed,
+    /// Constructor of an exception type the source declares it raises.
+    RaisedType,
+    /// Constructor or field of a parameter type that is abstract or documented.
+    DocumentedParamType,
+    /// Read of a const/immutable variable, or of a field of a frozen type.
+    ImmutableVariable,
+    /// Generated constructor of a pure data type.
+    DataClassConstructor,
+    /// DI-wired function with a complete signature.
+    DiWired,
+    /// Interface/abstract method with a complete, documented signature.
+    InterfaceMethod,
+    /// Function returning an abstract type with a documented contract.
+    AbstractFactory,
+    /// Internal function with a complete signature and sufficient documentation (academic).
+    TypedDocumented,
+}
+
+impl BoundaryReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BoundaryReason::CustomRule => "CustomRule",
+            BoundaryReason::StubNode => "StubNode",
+            BoundaryReason::External => "External",
+            BoundaryReason::Trivial => "Trivial",
+            BoundaryReason::OverrideNotFollowed => "OverrideNotFollowed",
+            BoundaryReason::RaisedType => "RaisedType",
+            BoundaryReason::DocumentedParamType => "DocumentedParamType",
+            BoundaryReason::ImmutableVariable => "ImmutableVariable",
+            BoundaryReason::DataClassConstructor => "DataClassConstructor",
+            BoundaryReason::DiWired => "DiWired",
+            BoundaryReason::InterfaceMethod => "InterfaceMethod",
+            BoundaryReason::AbstractFactory => "AbstractFactory",
+            BoundaryReason::TypedDocumented => "TypedDocumented",
+        }
+    }
 }
 
 /// Pruning parameters for the CF solver.
-/// Only [doc_threshold] is configurable; "document completeness" is defined by doc_score (from doc_scorer).
-#[derive(Debug, Clone)]
+/// "Document completeness" is defined by doc_score (from doc_scorer) compared against
+/// [func_doc_threshold] or, for interface methods, [interface_doc_threshold].
+///
+/// Deserializable so a policy can be versioned as a file; omitted fields fall back to [`Default`].
+/// The legacy `doc_threshold` key sets both thresholds unless they are given explicitly.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self", default, deny_unknown_fields)]
 pub struct PruningParams {
-    /// Documentation score threshold: doc_score >= this value is "sufficient documentation".
-    pub doc_threshold: f32,
-    /// If true (Academic): internal function is Boundary when sig complete and doc_score >= doc_threshold.
+    /// Documentation score threshold for regular functions: doc_score >= this value is
+    /// "sufficient documentation".
+    pub func_doc_threshold: f32,
+    /// Documentation score threshold for interface/abstract methods, whose docs carry the whole
+    /// contract and may warrant a stricter bar than concrete functions.
+    pub interface_doc_threshold: f32,
+    /// If true (Academic): internal function is Boundary when sig complete and doc_score >= func_doc_threshold.
     /// If false (Strict): only abstract factory is Boundary for internal functions.
     pub treat_typed_documented_function_as_boundary: bool,
+    /// If true (Balanced), the typed + documented rule above only applies to functions without
+    /// high-freedom parameters (`dict`, `list`, `str`, ...); others stay Transparent.
+    pub typed_documented_boundary_requires_specific_params: bool,
+    /// Depth penalty: each reached node contributes `context_size * (1 + multiplier * depth)`.
+    /// 0.0 (default) leaves sizes unweighted.
+    pub depth_size_multiplier: f32,
+    /// OverriddenBy edges (interface/parent method → implementation): if true, implementations are
+    /// pruned by the usual function rules (an undocumented one is expanded); if false, each
+    /// implementation is a Boundary.
+    pub follow_overrides: bool,
+    /// Nodes smaller than this many tokens (getters, `__repr__`, generated `__eq__`) are counted
+    /// but treated as leaves (Boundary). 0 (default) disables the heuristic.
+    pub min_traversal_size: u32,
+    /// If true, fields of frozen types (frozen dataclass, record) are Boundary on Read even when
+    /// the field itself was not marked immutable: the value cannot change after construction.
+    /// Off in all presets.
+    pub treat_frozen_type_fields_as_immutable: bool,
+    /// If true, each reached function also adds the `context_size` of its parameter and return
+    /// types (from the type registry) as boundary contributions, once per type per computation.
+    pub include_signature_types: bool,
+    /// If false, constructors are always Transparent so their initialization logic is read;
+    /// if true (default), they are classified like any other function.
+    pub treat_constructor_as_boundary: bool,
+    /// Skip test-code callers during call-in exploration, so test helpers that call production
+    /// functions do not leak into production CF. Needs a classifier on the solver
+    /// ([`CfSolver::with_test_classifier`](crate::domain::solver::CfSolver::with_test_classifier)).
+    pub exclude_test_callers: bool,
+    /// If true, a node reached as a Boundary contributes only its signature size (signature +
+    /// docs, as computed for external symbols) instead of its full body. Signature sizes are
+    /// only measured by a builder configured
+    /// [`with_signature_sizes`](crate::domain::builder::GraphBuilder::with_signature_sizes);
+    /// without them the full size is counted.
+    pub boundary_counts_signature_only: bool,
+    /// Fraction of a boundary's size left out of the CF: a boundary contributes
+    /// `size * (1 - discount)`, since only its contract is read. 0.0 (default) counts it fully;
+    /// values are clamped to `[0, 1]`.
+    pub boundary_size_discount: f32,
+    /// Call-in exploration skips utilities: functions with more than one caller and fewer than
+    /// this many tokens per caller (`context_size / callers`). 0 disables the exception.
+    pub utility_tokens_per_caller_threshold: usize,
+    /// If false (default), constructors never explore their callers: instantiation sites are
+    /// many and the constructor's purpose is self-evident.
+    pub explore_constructor_callers: bool,
+    /// Maximum hops from the start nodes: a node at this depth is still counted, but none of
+    /// its edges (forward, call-in or shared-state) are followed. `None` (default) is unbounded.
+    pub max_depth: Option<u32>,
+    /// Edges whose target symbol matches any of these patterns (logging, metrics, telemetry)
+    /// are skipped entirely: the target is neither counted nor a boundary.
+    /// Stored as pattern strings in a policy file.
+    #[serde(with = "regex_patterns")]
+    pub exclude_edge_targets: Vec<Regex>,
+    /// User rules consulted before the built-in logic; the first `Some` decision wins.
+    /// Not part of a policy file (code only).
+    #[serde(skip)]
+    pub custom_rules: Vec<Arc<dyn CustomPruningRule>>,
+}
+
+/// Plugin point for project-specific boundary logic (e.g. "everything under `legacy/` is
+/// transparent"). Return `None` to defer to the next rule / built-in classification.
+pub trait CustomPruningRule: Send + Sync + std::fmt::Debug {
+    fn evaluate(
+        &self,
+        source: &Node,
+        target: &Node,
+        edge_kind: &EdgeKind,
+        graph: &ContextGraph,
+    ) -> Option<PruningDecision>;
 }
 
 impl Default for PruningParams {
@@ -34,27 +172,160 @@ impl Default for PruningParams {
     }
 }
 
+impl Serialize for PruningParams {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PruningParams::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PruningParams {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut value = serde_json::Value::deserialize(deserializer)?;
+        if let Some(obj) = value.as_object_mut()
+            && let Some(threshold) = obj.remove("doc_threshold")
+        {
+            for key in ["func_doc_threshold", "interface_doc_threshold"] {
+                obj.entry(key).or_insert_with(|| threshold.clone());
+            }
+        }
+        PruningParams::deserialize(value).map_err(serde::de::Error::custom)
+    }
+}
+
 impl PruningParams {
     /// Academic mode: internal function IS a boundary if typed + documented.
     pub fn academic(doc_threshold: f32) -> Self {
         Self {
-            doc_threshold,
+            func_doc_threshold: doc_threshold,
+            interface_doc_threshold: doc_threshold,
             treat_typed_documented_function_as_boundary: true,
+            typed_documented_boundary_requires_specific_params: false,
+            depth_size_multiplier: 0.0,
+            follow_overrides: true,
+            min_traversal_size: 0,
+            treat_frozen_type_fields_as_immutable: false,
+            include_signature_types: false,
+            treat_constructor_as_boundary: true,
+            exclude_test_callers: false,
+            boundary_counts_signature_only: false,
+            boundary_size_discount: 0.0,
+            utility_tokens_per_caller_threshold: UTILITY_TOKENS_PER_CALLER_THRESHOLD,
+            explore_constructor_callers: false,
+            max_depth: None,
+            exclude_edge_targets: Vec::new(),
+            custom_rules: Vec::new(),
+        }
+    }
+
+    /// Balanced mode: like academic, but a typed + documented function with a high-freedom
+    /// parameter is TRANSPARENT (its contract depends on what the loose argument holds).
+    pub fn balanced(doc_threshold: f32) -> Self {
+        Self {
+            typed_documented_boundary_requires_specific_params: true,
+            ..Self::academic(doc_threshold)
         }
     }
 
     /// Strict mode: internal function is TRANSPARENT unless it's an abstract factory.
     pub fn strict(doc_threshold: f32) -> Self {
         Self {
-            doc_threshold,
+            func_doc_threshold: doc_threshold,
+            interface_doc_threshold: doc_threshold,
             treat_typed_documented_function_as_boundary: false,
+            typed_documented_boundary_requires_specific_params: false,
+            depth_size_multiplier: 0.0,
+            follow_overrides: true,
+            min_traversal_size: 0,
+            treat_frozen_type_fields_as_immutable: false,
+            include_signature_types: false,
+            treat_constructor_as_boundary: true,
+            exclude_test_callers: false,
+            boundary_counts_signature_only: false,
+            boundary_size_discount: 0.0,
+            utility_tokens_per_caller_threshold: UTILITY_TOKENS_PER_CALLER_THRESHOLD,
+            explore_constructor_callers: false,
+            max_depth: None,
+            exclude_edge_targets: Vec::new(),
+            custom_rules: Vec::new(),
+        }
+    }
+
+    /// Documentation threshold that applies to `f`: interface methods use
+    /// `interface_doc_threshold`, every other function `func_doc_threshold`.
+    pub fn doc_threshold_for(&self, f: &FunctionNode) -> f32 {
+        if f.is_interface_method {
+            self.interface_doc_threshold
+        } else {
+            self.func_doc_threshold
         }
     }
+
+    /// Size `node` contributes when reached at `depth`; boundaries count only their signature
+    /// under `boundary_counts_signature_only`, reduced by `boundary_size_discount`.
+    pub fn counted_size(&self, node: &Node, is_boundary: bool, depth: u32) -> u32 {
+        let core = node.core();
+        let size = if is_boundary && self.boundary_counts_signature_only {
+            core.signature_or_context_size()
+        } else {
+            core.context_size
+        };
+        let size = if is_boundary && self.boundary_size_discount > 0.0 {
+            let kept = 1.0 - self.boundary_size_discount.clamp(0.0, 1.0);
+            (size as f32 * kept).round() as u32
+        } else {
+            size
+        };
+        self.weighted_size(size, depth)
+    }
+
+    /// True when a node reached at `depth` must not be expanded further.
+    pub fn is_at_max_depth(&self, depth: u32) -> bool {
+        self.max_depth.is_some_and(|max| depth >= max)
+    }
+
+    /// Size contributed by a node of `context_size` reached at `depth` (0 = start node).
+    pub fn weighted_size(&self, context_size: u32, depth: u32) -> u32 {
+        if self.depth_size_multiplier == 0.0 {
+            return context_size;
+        }
+        let factor = 1.0 + self.depth_size_multiplier * depth as f32;
+        (context_size as f32 * factor).round() as u32
+    }
+
+    /// True if edges into `target` should be ignored (see [`Self::exclude_edge_targets`]).
+    pub fn is_excluded_target(&self, graph: &ContextGraph, target: NodeIndex) -> bool {
+        if self.exclude_edge_targets.is_empty() {
+            return false;
+        }
+        graph.symbol_of(target).is_some_and(|symbol| {
+            self.exclude_edge_targets
+                .iter()
+                .any(|re| re.is_match(symbol))
+        })
+    }
 }
 
-/// Threshold for tokens per caller. If a function has fewer tokens per caller than this,
-/// it's considered a utility and we don't explore its callers during call-in exploration.
-const UTILITY_TOKENS_PER_CALLER_THRESHOLD: usize = 10;
+mod regex_patterns {
+    use regex::Regex;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(patterns: &[Regex], serializer: S) -> Result<S::Ok, S::Error> {
+        let strings: Vec<&str> = patterns.iter().map(Regex::as_str).collect();
+        strings.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Regex>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|s| Regex::new(s).map_err(serde::de::Error::custom))
+            .collect()
+    }
+}
+
+/// Default threshold for tokens per caller. If a function has fewer tokens per caller than
+/// this, it's considered a utility and we don't explore its callers during call-in exploration
+/// (see [`PruningParams::utility_tokens_per_caller_threshold`]).
+pub const UTILITY_TOKENS_PER_CALLER_THRESHOLD: usize = 10;
 
 // -----------------------------------------------------------------------------
 // Core algorithm (domain layer)
@@ -91,10 +362,34 @@ pub fn is_abstract_factory(
     false
 }
 
+/// Returns true if the function is a member of a pure data type (dataclass/record).
+fn is_data_class_member(func_node: &FunctionNode, type_registry: &TypeRegistry) -> bool {
+    func_node
+        .core
+        .scope
+        .as_deref()
+        .and_then(|scope| type_registry.get(scope))
+        .is_some_and(|type_info| type_info.definition.is_data_class)
+}
+
+/// Returns true if the variable is a field of a frozen type (frozen dataclass/record).
+fn is_frozen_type_field(
+    var_node: &crate::domain::node::VariableNode,
+    type_registry: &TypeRegistry,
+) -> bool {
+    var_node.variable_kind == crate::domain::node::VariableKind::ClassField
+        && var_node
+            .core
+            .scope
+            .as_deref()
+            .and_then(|scope| type_registry.get(scope))
+            .is_some_and(|type_info| type_info.definition.is_frozen)
+}
+
 /// Whether to explore callers of the current function (call-in exploration).
 /// Used when traversing: if true, follow incoming Call edges from this function.
 pub fn should_explore_callers(
-    func_node: &crate::domain::node::FunctionNode,
+    func_node: &FunctionNode,
     func_idx: petgraph::graph::NodeIndex,
     incoming_edge: Option<&EdgeKind>,
     params: &PruningParams,
@@ -108,7 +403,7 @@ pub fn should_explore_callers(
     // Constructors (e.g. __init__) are called from many instantiation sites.
     // Call-in exploration would add all callers, inflating CF without adding
     // semantic value — the constructor's purpose is self-evident.
-    if func_node.is_constructor {
+    if func_node.is_constructor && !params.explore_constructor_callers {
         return false;
     }
 
@@ -119,7 +414,7 @@ pub fn should_explore_callers(
     // Exploring all callers would inflate CF without aiding understanding.
     if caller_count > 1 {
         let tokens_per_caller = func_node.core.context_size as usize / caller_count;
-        if tokens_per_caller < UTILITY_TOKENS_PER_CALLER_THRESHOLD {
+        if tokens_per_caller < params.utility_tokens_per_caller_threshold {
             return false;
         }
     }
@@ -142,10 +437,8 @@ pub fn should_explore_callers(
                     is_side_effect_free = false;
                     break;
                 }
-                EdgeKind::Call => {
-                    if visited_pure_check.insert(target_idx) {
-                        queue.push_back(target_idx);
-                    }
+                EdgeKind::Call if visited_pure_check.insert(target_idx) => {
+                    queue.push_back(target_idx);
                 }
                 _ => {}
             }
@@ -172,7 +465,7 @@ pub fn should_explore_callers(
 
     if has_high_freedom_params {
         // High freedom params require documentation to establish contract
-        if func_node.core.doc_score < params.doc_threshold {
+        if func_node.core.doc_score < params.doc_threshold_for(func_node) {
             return true;
         }
     }
@@ -192,18 +485,71 @@ pub fn evaluate_forward(
     edge_kind: &EdgeKind,
     graph: &ContextGraph,
 ) -> PruningDecision {
+    // 0. Custom rules take precedence over everything below
+    if let Some(decision) = params
+        .custom_rules
+        .iter()
+        .find_map(|rule| rule.evaluate(source, target, edge_kind, graph))
+    {
+        return decision;
+    }
+
     // 1. Do not expand from stub nodes (context_size 0: package/module/synthetic).
     // Otherwise reverse traversal (CallIn) into such a node would pull in the whole package.
     if source.core().context_size == 0 {
-        return PruningDecision::Boundary;
+        return PruningDecision::Boundary(BoundaryReason::StubNode);
     }
 
     // 2. External dependencies are always boundaries
     if target.core().is_external {
-        return PruningDecision::Boundary;
+        return PruningDecision::Boundary(BoundaryReason::External);
+    }
+
+    // 2b. Trivial nodes: count them, but there is nothing worth following behind them
+    if target.core().context_size < params.min_traversal_size {
+        return PruningDecision::Boundary(BoundaryReason::Trivial);
+    }
+
+    // 3. Implementations reached from a parent method: when not followed, each is counted as a
+    // leaf; when followed, the implementation is judged by the node-type rules below like any call.
+    if *edge_kind == EdgeKind::OverriddenBy && !params.follow_overrides {
+        return PruningDecision::Boundary(BoundaryReason::OverrideNotFollowed);
+    }
+
+    // 3a. Declared exceptions: callers need the error type, not how it is built
+    if *edge_kind == EdgeKind::Raises {
+        return PruningDecision::Boundary(BoundaryReason::RaisedType);
+    }
+
+    // 3b. Parameter types: an abstract or documented type is understood from its contract;
+    // otherwise its definition (constructor, fields) has to be read.
+    if *edge_kind == EdgeKind::Uses {
+        let documented_type = target
+            .core()
+            .scope
+            .as_deref()
+            .and_then(|scope| graph.type_registry.get(scope))
+            .is_some_and(|type_info| {
+                type_info.definition.is_abstract
+                    || type_info.definition.type_kind == TypeKind::Interface
+                    || type_info.doc_score >= params.func_doc_threshold
+            });
+        return if documented_type {
+            PruningDecision::Boundary(BoundaryReason::DocumentedParamType)
+        } else {
+            PruningDecision::Transparent
+        };
+    }
+
+    // 3c. Base-class methods reached from a subtype: a concrete base is part of the subtype's
+    // behavior and always read; an abstract base is judged like any interface method below.
+    if *edge_kind == EdgeKind::Inherits
+        && !matches!(target, Node::Function(f) if f.is_interface_method)
+    {
+        return PruningDecision::Transparent;
     }
 
-    // 3. Node type dispatch
+    // 4. Node type dispatch
     match target {
         Node::Variable(v) => {
             // For Read edges: immutable values are boundaries (behavior is fully determined)
@@ -211,38 +557,56 @@ pub fn evaluate_forward(
             // For Write edges: always transparent (writing to any variable is an action)
             match edge_kind {
                 EdgeKind::Write => PruningDecision::Transparent,
+                _ if params.treat_frozen_type_fields_as_immutable
+                    && is_frozen_type_field(v, &graph.type_registry) =>
+                {
+                    PruningDecision::Boundary(BoundaryReason::ImmutableVariable)
+                }
                 _ => match v.mutability {
                     crate::domain::node::Mutability::Const
-                    | crate::domain::node::Mutability::Immutable => PruningDecision::Boundary,
+                    | crate::domain::node::Mutability::Immutable => {
+                        PruningDecision::Boundary(BoundaryReason::ImmutableVariable)
+                    }
                     crate::domain::node::Mutability::Mutable => PruningDecision::Transparent,
                 },
             }
         }
         Node::Function(f) => {
+            // Generated constructor of a pure data type (dataclass/record): it only assigns
+            // fields, so there is nothing to learn by traversing into it.
+            if f.is_constructor && is_data_class_member(f, &graph.type_registry) {
+                return PruningDecision::Boundary(BoundaryReason::DataClassConstructor);
+            }
+            if f.is_constructor && !params.treat_constructor_as_boundary {
+                return PruningDecision::Transparent;
+            }
+
             let sig_complete = f.is_signature_complete_with_registry(&graph.type_registry);
 
             // DI-wired function with complete signature: boundary (no doc requirement)
             if f.is_di_wired && sig_complete {
-                return PruningDecision::Boundary;
+                return PruningDecision::Boundary(BoundaryReason::DiWired);
             }
 
             // Interface/abstract methods: boundary if signature complete and documented
             if f.is_interface_method {
-                if sig_complete && f.core.doc_score >= params.doc_threshold {
-                    return PruningDecision::Boundary;
+                if sig_complete && f.core.doc_score >= params.interface_doc_threshold {
+                    return PruningDecision::Boundary(BoundaryReason::InterfaceMethod);
                 }
                 // Undocumented interface method is a leaky abstraction
                 return PruningDecision::Transparent;
             }
 
-            if is_abstract_factory(target, &graph.type_registry, params.doc_threshold) {
-                return PruningDecision::Boundary;
+            if is_abstract_factory(target, &graph.type_registry, params.func_doc_threshold) {
+                return PruningDecision::Boundary(BoundaryReason::AbstractFactory);
             }
             if params.treat_typed_documented_function_as_boundary
                 && sig_complete
-                && f.core.doc_score >= params.doc_threshold
+                && f.core.doc_score >= params.func_doc_threshold
+                && !(params.typed_documented_boundary_requires_specific_params
+                    && f.parameters.iter().any(|p| p.is_high_freedom_type))
             {
-                return PruningDecision::Boundary;
+                return PruningDecision::Boundary(BoundaryReason::TypedDocumented);
             }
             PruningDecision::Transparent
         }
@@ -268,6 +632,18 @@ pub trait SizeFunction: Send + Sync {
     /// Compute the context size for a given source code span,
     /// potentially excluding documentation to avoid "punishing" well-documented code.
     fn compute(&self, source: &str, span: &SourceSpan, doc_texts: &[String]) -> u32;
+
+    /// Like [`Self::compute`], for a span in a file of the given language (e.g. "python",
+    /// "rust"); lets implementations apply language-specific rules such as comment syntax.
+    fn compute_in_language(
+        &self,
+        _language: &str,
+        source: &str,
+        span: &SourceSpan,
+        doc_texts: &[String],
+    ) -> u32 {
+        self.compute(source, span, doc_texts)
+    }
 }
 
 /// Documentation scorer trait - evaluates documentation quality
@@ -276,6 +652,17 @@ pub trait DocumentationScorer: Send + Sync {
     /// - 0.0: No documentation or meaningless documentation
     /// - 1.0: Complete, clear documentation
     fn score(&self, node_info: &NodeInfo, doc_text: Option<&str>) -> f32;
+
+    /// Like [`Self::score`], with access to the full symbol definition (parameters, return
+    /// types, modifiers) so richer scorers can e.g. check parameter coverage.
+    fn score_node(
+        &self,
+        _def: &SymbolDefinition,
+        node_info: &NodeInfo,
+        doc_text: Option<&str>,
+    ) -> f32 {
+        self.score(node_info, doc_text)
+    }
 }
 
 /// Node information for documentation scoring
@@ -284,6 +671,13 @@ pub struct NodeInfo {
     pub name: String,
     pub signature: Option<String>, // Function signature
     pub language: Option<String>,  // Programming language
+    /// Declared parameter names, in order (functions; receivers excluded). Lets scorers check
+    /// which parameters the docs cover without re-parsing `signature`.
+    pub param_names: Vec<String>,
+    /// The function declares a return type.
+    pub has_return_type: bool,
+    /// Abstract/interface method: the docs are its whole contract.
+    pub is_abstract: bool,
 }
 
 #[cfg(test)]
@@ -327,13 +721,142 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_boundary_reason_reports_which_rule_stopped() {
+        let graph = ContextGraph::new();
+        let source = test_node(0.0);
+        let params = PruningParams::academic(0.5);
+        let reason = |target: &Node, params: &PruningParams| match evaluate_forward(
+            params,
+            &source,
+            target,
+            &EdgeKind::Call,
+            &graph,
+        ) {
+            PruningDecision::Boundary(reason) => Some(reason),
+            PruningDecision::Transparent => None,
+        };
+
+        let mut target = test_node(0.8);
+        assert_eq!(
+            reason(&target, &params),
+            Some(BoundaryReason::TypedDocumented)
+        );
+
+        // External wins over the function's own properties
+        target.core_mut().is_external = true;
+        assert_eq!(reason(&target, &params), Some(BoundaryReason::External));
+
+        let undocumented = test_node(0.0);
+        assert_eq!(reason(&undocumented, &params), None);
+        let trivial = PruningParams {
+            min_traversal_size: 20,
+            ..PruningParams::academic(0.5)
+        };
+        assert_eq!(
+            reason(&undocumented, &trivial),
+            Some(BoundaryReason::Trivial)
+        );
+    }
+
+    #[test]
+    fn test_pruning_params_deserialize_partial() {
+        let params: PruningParams =
+            serde_json::from_str(r#"{"doc_threshold": 0.7, "depth_size_multiplier": 0.25}"#)
+                .unwrap();
+        assert_eq!(params.func_doc_threshold, 0.7);
+        assert_eq!(params.interface_doc_threshold, 0.7);
+        assert_eq!(params.depth_size_multiplier, 0.25);
+        // Omitted fields keep the default preset
+        assert!(params.treat_typed_documented_function_as_boundary);
+
+        // An explicit threshold wins over the legacy key
+        let params: PruningParams =
+            serde_json::from_str(r#"{"doc_threshold": 0.7, "interface_doc_threshold": 0.9}"#)
+                .unwrap();
+        assert_eq!(params.func_doc_threshold, 0.7);
+        assert_eq!(params.interface_doc_threshold, 0.9);
+
+        assert!(serde_json::from_str::<PruningParams>(r#"{"doc_treshold": 0.7}"#).is_err());
+    }
+
+    #[test]
+    fn test_custom_rule_overrides_builtin_boundary() {
+        #[derive(Debug)]
+        struct ForceTransparent(&'static str);
+        impl CustomPruningRule for ForceTransparent {
+            fn evaluate(
+                &self,
+                _source: &Node,
+                target: &Node,
+                _edge_kind: &EdgeKind,
+                graph: &ContextGraph,
+            ) -> Option<PruningDecision> {
+                let idx = graph.get_node_by_symbol(self.0)?;
+                (graph.node(idx).core().id == target.core().id)
+                    .then_some(PruningDecision::Transparent)
+            }
+        }
+
+        let mut graph = ContextGraph::new();
+        let source_idx = graph.add_node("sym::source".into(), test_node(0.0));
+        let target_idx = graph.add_node("sym::legacy".into(), test_node(0.8));
+        if let Node::Function(f) = graph.graph.node_weight_mut(target_idx).unwrap() {
+            f.core.id = 1;
+        }
+        let source = graph.node(source_idx).clone();
+        let target = graph.node(target_idx).clone();
+
+        // Typed + documented: Boundary under the built-in academic logic
+        let academic = PruningParams::academic(0.5);
+        assert_eq!(
+            evaluate_forward(&academic, &source, &target, &EdgeKind::Call, &graph),
+            PruningDecision::Boundary(BoundaryReason::TypedDocumented)
+        );
+
+        let with_rule = PruningParams {
+            custom_rules: vec![Arc::new(ForceTransparent("sym::legacy"))],
+            ..PruningParams::academic(0.5)
+        };
+        assert_eq!(
+            evaluate_forward(&with_rule, &source, &target, &EdgeKind::Call, &graph),
+            PruningDecision::Transparent
+        );
+        // Rule does not match the source node: built-in logic applies
+        let other = PruningParams {
+            custom_rules: vec![Arc::new(ForceTransparent("sym::source"))],
+            ..PruningParams::academic(0.5)
+        };
+        assert_eq!(
+            evaluate_forward(&other, &source, &target, &EdgeKind::Call, &graph),
+            PruningDecision::Boundary(BoundaryReason::TypedDocumented)
+        );
+    }
+
     #[test]
     fn test_default_pruning_params() {
         let p = PruningParams::default();
-        assert!((p.doc_threshold - 0.5).abs() < 1e-5);
+        assert!((p.func_doc_threshold - 0.5).abs() < 1e-5);
+        assert!((p.interface_doc_threshold - 0.5).abs() < 1e-5);
         assert!(p.treat_typed_documented_function_as_boundary);
     }
 
+    #[test]
+    fn test_boundary_size_discount_applies_to_boundaries_only() {
+        let node = test_node(0.0);
+        let params = PruningParams {
+            boundary_size_discount: 0.25,
+            ..PruningParams::default()
+        };
+        assert_eq!(params.counted_size(&node, true, 0), 8);
+        assert_eq!(params.counted_size(&node, false, 0), 10);
+        let out_of_range = PruningParams {
+            boundary_size_discount: 3.0,
+            ..PruningParams::default()
+        };
+        assert_eq!(out_of_range.counted_size(&node, true, 0), 0);
+    }
+
     #[test]
     fn test_academic_vs_strict() {
         let graph = ContextGraph::new();
@@ -342,12 +865,13 @@ mod tests {
         let edge = EdgeKind::Call;
         let academic = PruningParams::default();
         let strict = PruningParams {
-            doc_threshold: 0.5,
+            func_doc_threshold: 0.5,
             treat_typed_documented_function_as_boundary: false,
+            ..PruningParams::default()
         };
         assert!(matches!(
             evaluate(&academic, &source, &target, &edge, &graph),
-            PruningDecision::Boundary
+            PruningDecision::Boundary(BoundaryReason::TypedDocumented)
         ));
         assert!(matches!(
             evaluate(&strict, &source, &target, &edge, &graph),
@@ -355,6 +879,61 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_followed_override_is_judged_like_any_function() {
+        let graph = ContextGraph::new();
+        let source = test_node(0.0);
+        let edge = EdgeKind::OverriddenBy;
+        let params = PruningParams::default();
+        assert!(matches!(
+            evaluate(&params, &source, &test_node(0.8), &edge, &graph),
+            PruningDecision::Boundary(BoundaryReason::TypedDocumented)
+        ));
+        assert!(matches!(
+            evaluate(&params, &source, &test_node(0.0), &edge, &graph),
+            PruningDecision::Transparent
+        ));
+        let not_followed = PruningParams {
+            follow_overrides: false,
+            ..PruningParams::default()
+        };
+        assert!(matches!(
+            evaluate(&not_followed, &source, &test_node(0.0), &edge, &graph),
+            PruningDecision::Boundary(BoundaryReason::OverrideNotFollowed)
+        ));
+    }
+
+    #[test]
+    fn test_interface_doc_threshold_applies_only_to_interface_methods() {
+        let graph = ContextGraph::new();
+        let source = test_node(0.0);
+        let concrete = test_node(0.6);
+        let mut interface = test_node(0.6);
+        if let Node::Function(f) = &mut interface {
+            f.is_interface_method = true;
+        }
+        let params = PruningParams {
+            interface_doc_threshold: 0.9,
+            ..PruningParams::academic(0.5)
+        };
+        assert_eq!(
+            evaluate(&params, &source, &concrete, &EdgeKind::Call, &graph),
+            PruningDecision::Boundary(BoundaryReason::TypedDocumented)
+        );
+        assert_eq!(
+            evaluate(&params, &source, &interface, &EdgeKind::Call, &graph),
+            PruningDecision::Transparent
+        );
+        let lenient = PruningParams {
+            interface_doc_threshold: 0.5,
+            ..params
+        };
+        assert_eq!(
+            evaluate(&lenient, &source, &interface, &EdgeKind::Call, &graph),
+            PruningDecision::Boundary(BoundaryReason::InterfaceMethod)
+        );
+    }
+
     // Helper to create variable nodes for testing
     fn test_variable_node(mutability: crate::domain::node::Mutability) -> Node {
         let core = NodeCore::new(
@@ -391,10 +970,58 @@ mod tests {
         // Immutable variable should be a boundary on Read
         assert!(matches!(
             evaluate(&params, &source, &target, &edge, &graph),
-            PruningDecision::Boundary
+            PruningDecision::Boundary(BoundaryReason::ImmutableVariable)
         ));
     }
 
+    #[test]
+    fn test_frozen_type_field_is_boundary_on_read_unless_disabled() {
+        use crate::domain::type_registry::TypeKind;
+
+        let mut graph = ContextGraph::new();
+        graph.type_registry.register(
+            "Config#".to_string(),
+            TypeInfo {
+                definition: TypeDefAttribute {
+                    type_kind: TypeKind::Class,
+                    is_abstract: false,
+                    is_data_class: true,
+                    is_frozen: true,
+                    type_param_count: 0,
+                    type_var_info: None,
+                },
+                context_size: 0,
+                doc_score: 0.0,
+            },
+        );
+        let source = test_node(0.0);
+        let Node::Variable(mut field) =
+            test_variable_node(crate::domain::node::Mutability::Mutable)
+        else {
+            unreachable!()
+        };
+        field.core.scope = Some("Config#".to_string());
+        field.variable_kind = crate::domain::node::VariableKind::ClassField;
+        let target = Node::Variable(field);
+
+        let mut params = PruningParams::default();
+        assert_eq!(
+            evaluate(&params, &source, &target, &EdgeKind::Read, &graph),
+            PruningDecision::Transparent,
+            "opt-in: presets leave frozen type fields mutable"
+        );
+
+        params.treat_frozen_type_fields_as_immutable = true;
+        assert_eq!(
+            evaluate(&params, &source, &target, &EdgeKind::Read, &graph),
+            PruningDecision::Boundary(BoundaryReason::ImmutableVariable)
+        );
+        assert_eq!(
+            evaluate(&params, &source, &target, &EdgeKind::Write, &graph),
+            PruningDecision::Transparent
+        );
+    }
+
     #[test]
     fn test_variable_const_is_boundary_on_read() {
         let graph = ContextGraph::new();
@@ -406,7 +1033,7 @@ mod tests {
         // Const variable should be a boundary on Read
         assert!(matches!(
             evaluate(&params, &source, &target, &edge, &graph),
-            PruningDecision::Boundary
+            PruningDecision::Boundary(BoundaryReason::ImmutableVariable)
         ));
     }
 
@@ -446,7 +1073,7 @@ mod tests {
         // Do not expand from stub nodes (context_size 0)
         assert!(matches!(
             evaluate(&params, &source, &target, &edge, &graph),
-            PruningDecision::Boundary
+            PruningDecision::Boundary(BoundaryReason::StubNode)
         ));
     }
 
@@ -534,6 +1161,8 @@ mod tests {
                 definition: TypeDefAttribute {
                     type_kind: crate::domain::type_registry::TypeKind::TypeVar,
                     is_abstract: false,
+                    is_data_class: false,
+                    is_frozen: false,
                     type_param_count: 0,
                     type_var_info,
                 },
@@ -585,7 +1214,7 @@ mod tests {
 
         assert!(matches!(
             evaluate(&params, &source, &target, &edge, &graph),
-            PruningDecision::Boundary
+            PruningDecision::Boundary(BoundaryReason::TypedDocumented)
         ));
     }
 
@@ -608,7 +1237,7 @@ mod tests {
 
         assert!(matches!(
             evaluate(&params, &source, &target, &edge, &graph),
-            PruningDecision::Boundary
+            PruningDecision::Boundary(BoundaryReason::TypedDocumented)
         ));
     }
 
@@ -627,4 +1256,46 @@ mod tests {
             PruningDecision::Transparent
         ));
     }
+
+    #[test]
+    fn test_uses_edge_stops_at_documented_or_abstract_param_types() {
+        let mut graph = ContextGraph::new();
+        let type_info = |doc_score: f32, is_abstract: bool| TypeInfo {
+            definition: TypeDefAttribute {
+                type_kind: TypeKind::Class,
+                is_abstract,
+                is_data_class: false,
+                is_frozen: false,
+                type_param_count: 0,
+                type_var_info: None,
+            },
+            context_size: 10,
+            doc_score,
+        };
+        graph
+            .type_registry
+            .register("Plain#".into(), type_info(0.0, false));
+        graph
+            .type_registry
+            .register("Documented#".into(), type_info(0.9, false));
+        graph
+            .type_registry
+            .register("Base#".into(), type_info(0.0, true));
+
+        let source = test_node(0.0);
+        let params = PruningParams::academic(0.5);
+        let decide = |scope: &str| {
+            let mut constructor = test_node(0.0);
+            constructor.core_mut().scope = Some(scope.to_string());
+            evaluate_forward(&params, &source, &constructor, &EdgeKind::Uses, &graph)
+        };
+
+        assert_eq!(decide("Plain#"), PruningDecision::Transparent);
+        for scope in ["Documented#", "Base#"] {
+            assert_eq!(
+                decide(scope),
+                PruningDecision::Boundary(BoundaryReason::DocumentedParamType)
+            );
+        }
+    }
 }
@@ -14,3 +14,8 @@ pub trait SourceReader: Send + Sync {
     /// Read specific lines from a file (0-indexed; start_line and end_line inclusive)
     fn read_lines(&self, path: &str, start_line: usize, end_line: usize) -> Result<Vec<String>>;
 }
+
+/// Test code classification port (language conventions live in adapters)
+pub trait TestCodeClassifier: Send + Sync {
+    fn is_test_code(&self, symbol: &str, file_path: &str) -> bool;
+}
@@ -33,6 +33,7 @@
 //! - **Static access**: `Class.static_method()` → depends on language representation
 
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 /// Symbol identifier - Globally unique within project
@@ -45,6 +46,23 @@ use std::collections::HashMap;
 /// - For builtin types (int, str, etc.): use language-standard names
 pub type SymbolId = String;
 
+/// Symbol identity with the package version blanked out, for matching the same symbol
+/// across indexes built at different package versions.
+///
+/// SCIP-style ids (`<scheme> <manager> <package> <version> <descriptors>`) get their
+/// version replaced by `.`; `local` ids and other formats are returned unchanged.
+pub fn normalize_symbol_version(symbol_id: &str) -> Cow<'_, str> {
+    let parts: Vec<&str> = symbol_id.splitn(5, ' ').collect();
+    match parts.as_slice() {
+        [scheme, manager, package, version, descriptors]
+            if *scheme != "local" && *version != "." =>
+        {
+            Cow::Owned(format!("{scheme} {manager} {package} . {descriptors}"))
+        }
+        _ => Cow::Borrowed(symbol_id),
+    }
+}
+
 /// Type reference - Points to a Type symbol or builtin type
 ///
 /// **Adapter Contract**:
@@ -201,6 +219,17 @@ pub struct SymbolDefinition {
     #[serde(default)]
     pub is_external: bool,
 
+    /// Whether this definition is generated code (protobuf stubs, ORM models, ...)
+    ///
+    /// **Adapter Contract**:
+    /// - `true` when the indexer marks the definition as generated (e.g. the SCIP
+    ///   `SymbolRole::Generated` bit on its definition occurrence)
+    /// - Generated definitions are treated like external symbols (signature-only size,
+    ///   CF boundary) so boilerplate doesn't dominate CF
+    /// - If omitted: defaults to `false`
+    #[serde(default)]
+    pub is_generated: bool,
+
     /// Documentation strings (for doc_score calculation)
     ///
     /// **Adapter Contract**:
@@ -281,6 +310,15 @@ pub struct FunctionDetails {
     /// - For unbounded generics, `bounds` is empty vec
     pub type_params: Vec<TypeParam>,
 
+    /// Declared exception types (`throws` clause, documented `Raises:`)
+    ///
+    /// **Adapter Contract**:
+    /// - Java/Kotlin: `throws` clause; Python: types listed in a docstring `Raises:` section
+    /// - Empty vec if the language or index carries no such declaration
+    /// - **NO inference** from `raise`/`throw` statements in the body
+    #[serde(default)]
+    pub throws: Vec<TypeRef>,
+
     /// Function modifiers and attributes
     pub modifiers: FunctionModifiers,
 }
@@ -525,6 +563,23 @@ pub struct TypeDetails {
     /// - Affects: boundary decisions (final types are complete, no subclass expansion)
     pub is_final: bool,
 
+    /// Whether this is a pure data type whose members are generated boilerplate
+    ///
+    /// **Adapter Contract**:
+    /// - `true` for: Python `@dataclass` / `NamedTuple`, Java `record`, Kotlin `data class`
+    /// - Generated constructors of such types are CF boundaries (no behavior to traverse)
+    #[serde(default)]
+    pub is_data_class: bool,
+
+    /// Whether instances are immutable once constructed
+    ///
+    /// **Adapter Contract**:
+    /// - `true` for: Python `@dataclass(frozen=True)` / `NamedTuple`, Java `record`
+    /// - Not implied by `is_final` (a final class may still have mutable fields)
+    /// - Fields of frozen types are treated as immutable for Read pruning
+    #[serde(default)]
+    pub is_frozen: bool,
+
     pub visibility: Visibility,
 
     /// Generic type parameters
@@ -566,6 +621,8 @@ impl Default for TypeDetails {
             kind: TypeKind::Class,
             is_abstract: false,
             is_final: false,
+            is_data_class: false,
+            is_frozen: false,
             visibility: Visibility::Public,
             type_params: Vec::new(),
             fields: Vec::new(),
@@ -692,6 +749,8 @@ pub struct SymbolReference {
     /// **Adapter Contract**:
     /// - For method calls: the name of the method being called (e.g. "foo", "__init__")
     /// - Used by builder for type-driven call edge recovery when target_symbol is None
+    /// - May be left `None` when `target_symbol` ends with a method descriptor (`Foo#bar().`):
+    ///   the builder then takes the name from the symbol
     #[serde(default)]
     pub method_name: Option<String>,
 
@@ -4,13 +4,15 @@ use crate::domain::node::{Node, NodeId};
 use crate::domain::policy::{
     PruningDecision, PruningParams, evaluate_forward, should_explore_callers,
 };
+use crate::domain::ports::TestCodeClassifier;
 use petgraph::graph::NodeIndex;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// How the current node was reached (for edge-aware pruning and reverse exploration).
-#[derive(Debug, Clone)]
-enum ReachedVia {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReachedVia {
     Start,
     Forward(EdgeKind),
     /// Reached by following incoming Call edges (call-in exploration).
@@ -25,6 +27,12 @@ pub struct TraversalStep {
     pub node_id: NodeId,
     pub incoming_edge_kind: Option<EdgeKind>,
     pub decision: Option<PruningDecision>,
+    /// How the node was reached (forward edge, call-in, shared-state write, or start).
+    pub reached_via: ReachedVia,
+    /// Node whose expansion reached this one (None for start nodes).
+    pub parent: Option<NodeId>,
+    /// BFS layer (0 = start node).
+    pub depth: u32,
 }
 
 /// CF computation result
@@ -36,6 +44,28 @@ pub struct CfResult {
     /// Traversal steps in BFS order: for each node, the edge kind and decision that led to it (None for start nodes).
     pub traversal_steps: Vec<TraversalStep>,
     pub total_context_size: u32,
+    /// Unweighted sum where boundaries count only their signature size (the body of a valid
+    /// abstraction need not be read) and transparent nodes their full context_size.
+    pub weighted_context_size: u32,
+    /// Distinct `(file, line)` pairs covered by the spans of reachable project nodes:
+    /// CF in "lines you'd actually read", without double-counting nested definitions.
+    pub unique_source_lines: usize,
+    /// Traversal was aborted via the solver's cancellation flag; the result is partial.
+    pub cancelled: bool,
+}
+
+impl CfResult {
+    /// Reached nodes where traversal stopped (counted, but not expanded).
+    pub fn boundary_count(&self) -> usize {
+        self.traversal_steps
+            .iter()
+            .filter(|step| {
+                step.decision
+                    .as_ref()
+                    .is_some_and(PruningDecision::is_boundary)
+            })
+            .count()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -62,33 +92,295 @@ pub struct ReachabilityResult {
     pub witness_paths: Vec<Vec<NodeId>>,
 }
 
+/// Pending BFS visit in [CfSolver::traverse].
+struct QueueEntry {
+    node: NodeIndex,
+    depth: u32,
+    reached_via: ReachedVia,
+    decision: Option<PruningDecision>,
+    parent: Option<NodeIndex>,
+}
+
 #[derive(Debug, Clone)]
 struct TraversalState {
     visited: HashSet<NodeIndex>,
     ordered: Vec<NodeIndex>,
     layers: Vec<Vec<NodeIndex>>,
     traversal_steps: Vec<TraversalStep>,
+    /// What each node in `ordered` added to `total_context_size`: its counted size plus the
+    /// signature types it was first to reach.
+    added_sizes: Vec<u32>,
     total_context_size: u32,
     predecessors: HashMap<NodeIndex, NodeIndex>,
+    cancelled: bool,
+}
+
+/// Reusable scratch state for [`CfSolver::compute_cf_total_with_scratch`].
+///
+/// Visited marks are stored as generation stamps, so clearing between
+/// computations is O(1) instead of re-allocating a `node_count` buffer.
+#[derive(Debug, Default)]
+pub struct CfScratch {
+    stamps: Vec<u32>,
+    generation: u32,
+    queue: VecDeque<(NodeIndex, u32, ReachedVia)>,
+    seen_types: HashSet<String>,
+}
+
+impl CfScratch {
+    fn reset(&mut self, node_count: usize) {
+        if self.stamps.len() != node_count {
+            self.stamps = vec![0; node_count];
+            self.generation = 0;
+        }
+        self.generation = self.generation.wrapping_add(1);
+        if self.generation == 0 {
+            // Stamps wrapped around: stale marks could alias the new generation.
+            self.stamps.fill(0);
+            self.generation = 1;
+        }
+        self.queue.clear();
+        self.seen_types.clear();
+    }
+
+    fn in_bounds(&self, idx: NodeIndex) -> bool {
+        idx.index() < self.stamps.len()
+    }
+
+    fn is_visited(&self, idx: NodeIndex) -> bool {
+        self.stamps
+            .get(idx.index())
+            .is_some_and(|&stamp| stamp == self.generation)
+    }
+
+    /// Mark `idx` visited; returns true if it was not visited before.
+    fn visit(&mut self, idx: NodeIndex) -> bool {
+        match self.stamps.get_mut(idx.index()) {
+            Some(stamp) if *stamp != self.generation => {
+                *stamp = self.generation;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A base method reached from a subtype over Inherits is read for the behavior it gives that
+/// subtype: its OverriddenBy edges lead to sibling overrides, which are not part of that context.
+fn is_sibling_override(reached_via: &ReachedVia, edge_kind: &EdgeKind) -> bool {
+    *edge_kind == EdgeKind::OverriddenBy
+        && matches!(reached_via, ReachedVia::Forward(EdgeKind::Inherits))
+}
+
+/// File a reader has to open to read `idx`; None for external and 0-size stub nodes.
+fn counted_file(graph: &ContextGraph, idx: NodeIndex) -> Option<&str> {
+    let core = graph.node(idx).core();
+    (!core.is_external && core.context_size > 0).then_some(core.file_path.as_str())
+}
+
+/// Emit one `trace` event per forward pruning decision (`trace` feature), so a run with
+/// `RUST_LOG=context_footprint::domain::solver=trace` shows why the traversal stopped where it did.
+#[cfg(feature = "trace")]
+fn trace_decision(
+    graph: &ContextGraph,
+    source: NodeIndex,
+    target: NodeIndex,
+    edge_kind: &EdgeKind,
+    decision: &PruningDecision,
+) {
+    let source = graph.symbol_of(source).unwrap_or_default();
+    let target = graph.symbol_of(target).unwrap_or_default();
+    match decision {
+        PruningDecision::Boundary(reason) => {
+            tracing::trace!(source, target, ?edge_kind, ?reason, "boundary");
+        }
+        PruningDecision::Transparent => {
+            tracing::trace!(source, target, ?edge_kind, "transparent");
+        }
+    }
+}
+
+#[cfg(not(feature = "trace"))]
+#[inline(always)]
+fn trace_decision(
+    _graph: &ContextGraph,
+    _source: NodeIndex,
+    _target: NodeIndex,
+    _edge_kind: &EdgeKind,
+    _decision: &PruningDecision,
+) {
+}
+
+/// Count distinct source lines spanned by `nodes` (external and 0-size stub nodes have no
+/// project source and are skipped). Span lines are 0-based with end_line inclusive.
+fn unique_source_lines(graph: &ContextGraph, nodes: &HashSet<NodeIndex>) -> usize {
+    let mut lines_by_file: HashMap<&str, Vec<(u32, u32)>> = HashMap::new();
+    for &idx in nodes {
+        let core = graph.node(idx).core();
+        if core.is_external || core.context_size == 0 {
+            continue;
+        }
+        lines_by_file
+            .entry(core.file_path.as_str())
+            .or_default()
+            .push((
+                core.span.start_line,
+                core.span.end_line.max(core.span.start_line),
+            ));
+    }
+
+    // Merge overlapping line ranges per file
+    let mut total = 0;
+    for ranges in lines_by_file.values_mut() {
+        ranges.sort_unstable();
+        let mut current: Option<(u32, u32)> = None;
+        for &(start, end) in ranges.iter() {
+            current = match current {
+                Some((s, e)) if start <= e + 1 => Some((s, e.max(end))),
+                Some((s, e)) => {
+                    total += (e - s + 1) as usize;
+                    Some((start, end))
+                }
+                None => Some((start, end)),
+            };
+        }
+        if let Some((s, e)) = current {
+            total += (e - s + 1) as usize;
+        }
+    }
+    total
 }
 
 /// CF Solver - computes Context-Footprint for a given node.
 ///
-/// Holds graph and pruning params (doc_threshold + mode).
+/// Holds graph and pruning params (doc thresholds + mode).
 pub struct CfSolver {
     graph: Arc<ContextGraph>,
     params: PruningParams,
+    cancel: Option<Arc<AtomicBool>>,
+    test_classifier: Option<Arc<dyn TestCodeClassifier>>,
 }
 
 impl CfSolver {
     pub fn new(graph: Arc<ContextGraph>, params: PruningParams) -> Self {
-        Self { graph, params }
+        Self {
+            graph,
+            params,
+            cancel: None,
+            test_classifier: None,
+        }
+    }
+
+    /// Classifier consulted when `exclude_test_callers` is set.
+    pub fn with_test_classifier(mut self, classifier: Arc<dyn TestCodeClassifier>) -> Self {
+        self.test_classifier = Some(classifier);
+        self
+    }
+
+    /// Caller that call-in exploration must not pull in (test code under `exclude_test_callers`).
+    fn is_excluded_caller(&self, idx: NodeIndex) -> bool {
+        if !self.params.exclude_test_callers {
+            return false;
+        }
+        self.test_classifier.as_ref().is_some_and(|classifier| {
+            let graph = self.graph.as_ref();
+            classifier.is_test_code(
+                graph.symbol_of(idx).unwrap_or(""),
+                &graph.node(idx).core().file_path,
+            )
+        })
+    }
+
+    /// Abort traversals once `flag` is set (checked before each node expansion).
+    /// Cancelled computations return what was reached so far.
+    pub fn with_cancellation(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(flag);
+        self
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed))
     }
 
     /// Compute CF for a given set of starting nodes (full result with layers, etc.).
     pub fn compute_cf(&self, starts: &[NodeIndex], max_tokens: Option<u32>) -> CfResult {
+        self.compute_cf_with_file_limit(starts, max_tokens, None)
+    }
+
+    /// [`compute_cf`](Self::compute_cf) that also stops reaching into new files once
+    /// `max_files` distinct project files are covered (start nodes always count).
+    pub fn compute_cf_with_file_limit(
+        &self,
+        starts: &[NodeIndex],
+        max_tokens: Option<u32>,
+        max_files: Option<usize>,
+    ) -> CfResult {
+        self.cf_result(self.traverse(starts, max_tokens, max_files))
+    }
+
+    /// Additional CF of `starts` for a reader who has already read `already_read`: the
+    /// traversal is unchanged (dependencies behind read nodes still count), but read nodes are
+    /// dropped from the result and contribute no size. `max_tokens` bounds the traversal as in
+    /// [`compute_cf`](Self::compute_cf), before read nodes are dropped.
+    pub fn compute_cf_excluding(
+        &self,
+        starts: &[NodeIndex],
+        already_read: &HashSet<NodeId>,
+        max_tokens: Option<u32>,
+    ) -> CfResult {
         let graph = self.graph.as_ref();
-        let traversal = self.traverse(starts, max_tokens);
+        let mut traversal = self.traverse(starts, max_tokens, None);
+        let is_read = |idx: &NodeIndex| already_read.contains(&graph.node(*idx).core().id);
+
+        // ordered, traversal_steps and added_sizes are pushed together, one entry per reached node
+        let mut kept_order = Vec::with_capacity(traversal.ordered.len());
+        let mut kept_steps = Vec::with_capacity(traversal.traversal_steps.len());
+        let mut kept_sizes = Vec::with_capacity(traversal.added_sizes.len());
+        for ((idx, step), added) in traversal
+            .ordered
+            .into_iter()
+            .zip(traversal.traversal_steps)
+            .zip(traversal.added_sizes)
+        {
+            if is_read(&idx) {
+                traversal.total_context_size = traversal.total_context_size.saturating_sub(added);
+            } else {
+                kept_order.push(idx);
+                kept_steps.push(step);
+                kept_sizes.push(added);
+            }
+        }
+        traversal.ordered = kept_order;
+        traversal.traversal_steps = kept_steps;
+        traversal.added_sizes = kept_sizes;
+        traversal.visited.retain(|idx| !is_read(idx));
+        for layer in &mut traversal.layers {
+            layer.retain(|idx| !is_read(idx));
+        }
+        self.cf_result(traversal)
+    }
+
+    fn cf_result(&self, traversal: TraversalState) -> CfResult {
+        let graph = self.graph.as_ref();
+        let weighted_context_size = traversal
+            .ordered
+            .iter()
+            .zip(&traversal.traversal_steps)
+            .map(|(idx, step)| {
+                let core = graph.node(*idx).core();
+                if step
+                    .decision
+                    .as_ref()
+                    .is_some_and(PruningDecision::is_boundary)
+                {
+                    core.signature_or_context_size()
+                } else {
+                    core.context_size
+                }
+            })
+            .fold(0u32, u32::saturating_add);
         CfResult {
             reachable_set: traversal
                 .visited
@@ -105,8 +397,11 @@ impl CfSolver {
                 .iter()
                 .map(|layer| layer.iter().map(|idx| graph.node(*idx).core().id).collect())
                 .collect(),
+            unique_source_lines: unique_source_lines(graph, &traversal.visited),
             traversal_steps: traversal.traversal_steps,
             total_context_size: traversal.total_context_size,
+            weighted_context_size,
+            cancelled: traversal.cancelled,
         }
     }
 
@@ -117,7 +412,7 @@ impl CfSolver {
         options: ReachabilityOptions,
     ) -> ReachabilityResult {
         let graph = self.graph.as_ref();
-        let traversal = self.traverse(starts, None);
+        let traversal = self.traverse(starts, None, None);
         let visited_set: HashSet<NodeId> = traversal
             .visited
             .iter()
@@ -154,61 +449,78 @@ impl CfSolver {
     /// Compute CF total context size for a single start node.
     /// Does not return traversal order / layers; ignores max_tokens.
     pub fn compute_cf_total(&self, start: NodeIndex) -> u32 {
+        let mut scratch = CfScratch::default();
+        self.compute_cf_total_with_scratch(start, &mut scratch)
+    }
+
+    /// Same as [`compute_cf_total`](Self::compute_cf_total), but reuses the
+    /// visited buffer and queue in `scratch` instead of allocating per call.
+    /// Intended for passes that compute CF for many nodes of the same graph.
+    pub fn compute_cf_total_with_scratch(&self, start: NodeIndex, scratch: &mut CfScratch) -> u32 {
         let graph = self.graph.as_ref();
         let params = &self.params;
-        let node_count = graph.graph.node_count();
-        let mut visited = vec![false; node_count];
-        let mut reachable: Vec<NodeIndex> = Vec::new();
+        scratch.reset(graph.graph.node_count());
         let mut total_size: u32 = 0;
 
-        let mut queue: VecDeque<(NodeIndex, ReachedVia)> = VecDeque::new();
-
         let add_node = |idx: NodeIndex,
-                        visited: &mut [bool],
-                        reachable: &mut Vec<NodeIndex>,
+                        depth: u32,
+                        is_boundary: bool,
+                        scratch: &mut CfScratch,
                         total_size: &mut u32| {
-            let pos = idx.index();
-            if pos >= visited.len() {
-                return;
-            }
-            if !visited[pos] {
-                visited[pos] = true;
-                *total_size = total_size.saturating_add(graph.node(idx).core().context_size);
-                reachable.push(idx);
+            if scratch.visit(idx) {
+                let node = graph.node(idx);
+                let size = params
+                    .counted_size(node, is_boundary, depth)
+                    .saturating_add(self.signature_types_size(
+                        node,
+                        depth,
+                        &mut scratch.seen_types,
+                    ));
+                *total_size = total_size.saturating_add(size);
             }
         };
 
-        add_node(start, &mut visited, &mut reachable, &mut total_size);
-        queue.push_back((start, ReachedVia::Start));
+        add_node(start, 0, false, scratch, &mut total_size);
+        scratch.queue.push_back((start, 0, ReachedVia::Start));
 
-        while let Some((current, reached_via)) = queue.pop_front() {
+        while let Some((current, depth, reached_via)) = scratch.queue.pop_front() {
+            if self.is_cancelled() {
+                break;
+            }
             // === Stop exploring from reverse traversal nodes ===
             // If we reached this node just to understand how it calls something
             // or mutates shared state, we only need its immediate context. We do not explore further from it.
             if matches!(
                 reached_via,
                 ReachedVia::CallIn | ReachedVia::SharedStateWrite
-            ) {
+            ) || params.is_at_max_depth(depth)
+            {
                 continue;
             }
 
             let current_node = graph.node(current);
 
             for (neighbor, edge_kind) in graph.outgoing_edges(current) {
-                let neighbor_pos = neighbor.index();
-                if neighbor_pos < visited.len() && visited[neighbor_pos] {
+                if scratch.is_visited(neighbor)
+                    || params.is_excluded_target(graph, neighbor)
+                    || is_sibling_override(&reached_via, edge_kind)
+                {
                     continue;
                 }
 
                 let neighbor_node = graph.node(neighbor);
                 let decision =
                     evaluate_forward(params, current_node, neighbor_node, edge_kind, graph);
+                trace_decision(graph, current, neighbor, edge_kind, &decision);
 
-                if matches!(decision, PruningDecision::Transparent) {
-                    add_node(neighbor, &mut visited, &mut reachable, &mut total_size);
-                    queue.push_back((neighbor, ReachedVia::Forward(edge_kind.clone())));
-                } else {
-                    add_node(neighbor, &mut visited, &mut reachable, &mut total_size);
+                let is_boundary = decision.is_boundary();
+                add_node(neighbor, depth + 1, is_boundary, scratch, &mut total_size);
+                if !is_boundary {
+                    scratch.queue.push_back((
+                        neighbor,
+                        depth + 1,
+                        ReachedVia::Forward(edge_kind.clone()),
+                    ));
                 }
             }
 
@@ -219,10 +531,14 @@ impl CfSolver {
                 };
                 if should_explore_callers(f, current, incoming_edge, params, graph) {
                     for (caller_idx, _) in graph.incoming_edges(current, Some(EdgeKind::Call)) {
-                        let caller_pos = caller_idx.index();
-                        if caller_pos < visited.len() && !visited[caller_pos] {
-                            add_node(caller_idx, &mut visited, &mut reachable, &mut total_size);
-                            queue.push_back((caller_idx, ReachedVia::CallIn));
+                        if scratch.in_bounds(caller_idx)
+                            && !scratch.is_visited(caller_idx)
+                            && !self.is_excluded_caller(caller_idx)
+                        {
+                            add_node(caller_idx, depth + 1, false, scratch, &mut total_size);
+                            scratch
+                                .queue
+                                .push_back((caller_idx, depth + 1, ReachedVia::CallIn));
                         }
                     }
                 }
@@ -233,10 +549,13 @@ impl CfSolver {
                 && matches!(reached_via, ReachedVia::Forward(EdgeKind::Read))
             {
                 for (writer_idx, _) in graph.incoming_edges(current, Some(EdgeKind::Write)) {
-                    let writer_pos = writer_idx.index();
-                    if writer_pos < visited.len() && !visited[writer_pos] {
-                        add_node(writer_idx, &mut visited, &mut reachable, &mut total_size);
-                        queue.push_back((writer_idx, ReachedVia::SharedStateWrite));
+                    if scratch.in_bounds(writer_idx) && !scratch.is_visited(writer_idx) {
+                        add_node(writer_idx, depth + 1, false, scratch, &mut total_size);
+                        scratch.queue.push_back((
+                            writer_idx,
+                            depth + 1,
+                            ReachedVia::SharedStateWrite,
+                        ));
                     }
                 }
             }
@@ -245,38 +564,81 @@ impl CfSolver {
         total_size
     }
 
-    fn traverse(&self, starts: &[NodeIndex], max_tokens: Option<u32>) -> TraversalState {
+    fn traverse(
+        &self,
+        starts: &[NodeIndex],
+        max_tokens: Option<u32>,
+        max_files: Option<usize>,
+    ) -> TraversalState {
+        #[cfg(feature = "trace")]
+        let _span = tracing::trace_span!(
+            "traverse",
+            starts = ?starts
+                .iter()
+                .filter_map(|&idx| self.graph.symbol_of(idx))
+                .collect::<Vec<_>>()
+        )
+        .entered();
         let graph = self.graph.as_ref();
         let params = &self.params;
-        let mut idx_to_symbol: HashMap<NodeIndex, &str> =
-            HashMap::with_capacity(graph.symbol_to_node.len());
-        for (sym, &idx) in &graph.symbol_to_node {
-            idx_to_symbol.insert(idx, sym.as_str());
-        }
+        let mut files: HashSet<&str> = HashSet::new();
+        let opens_file_over_budget = |files: &HashSet<&str>, idx: NodeIndex| {
+            max_files.is_some_and(|limit| {
+                counted_file(graph, idx).is_some_and(|f| !files.contains(f) && files.len() >= limit)
+            })
+        };
 
         let start_set: HashSet<NodeIndex> = starts.iter().copied().collect();
         let mut visited = HashSet::new();
         let mut ordered = Vec::new();
         let mut traversal_steps = Vec::new();
+        let mut added_sizes = Vec::new();
         let mut layers: Vec<Vec<NodeIndex>> = Vec::new();
         let mut predecessors = HashMap::new();
-        let mut queue: VecDeque<(NodeIndex, u32, ReachedVia, Option<PruningDecision>)> =
-            VecDeque::new();
-        let mut total_size = 0;
+        let mut queue: VecDeque<QueueEntry> = VecDeque::new();
+        let mut total_size: u32 = 0;
+        let mut cancelled = false;
+        let mut seen_types = HashSet::new();
 
         for &start in starts {
-            queue.push_back((start, 0, ReachedVia::Start, None));
+            queue.push_back(QueueEntry {
+                node: start,
+                depth: 0,
+                reached_via: ReachedVia::Start,
+                decision: None,
+                parent: None,
+            });
         }
 
-        while let Some((current, depth, reached_via, incoming_decision)) = queue.pop_front() {
+        while let Some(QueueEntry {
+            node: current,
+            depth,
+            reached_via,
+            decision: incoming_decision,
+            parent,
+        }) = queue.pop_front()
+        {
+            if self.is_cancelled() {
+                cancelled = true;
+                break;
+            }
+
             let current_node = graph.node(current);
             let current_id = current_node.core().id;
 
-            if !visited.insert(current) {
+            if visited.contains(&current)
+                || (!start_set.contains(&current) && opens_file_over_budget(&files, current))
+            {
                 continue;
             }
-
-            total_size += current_node.core().context_size;
+            visited.insert(current);
+            files.extend(counted_file(graph, current));
+
+            let added = params
+                .counted_size(current_node, false, depth)
+                .saturating_add(self.signature_types_size(current_node, depth, &mut seen_types));
+            total_size = total_size.saturating_add(added);
+            added_sizes.push(added);
             let step_edge_kind = match &reached_via {
                 ReachedVia::Forward(ek) => Some(ek.clone()),
                 _ => None,
@@ -286,6 +648,9 @@ impl CfSolver {
                 node_id: current_id,
                 incoming_edge_kind: step_edge_kind,
                 decision: incoming_decision,
+                reached_via: reached_via.clone(),
+                parent: parent.map(|p| graph.node(p).core().id),
+                depth,
             });
 
             while layers.len() <= depth as usize {
@@ -302,36 +667,45 @@ impl CfSolver {
             if matches!(
                 reached_via,
                 ReachedVia::CallIn | ReachedVia::SharedStateWrite
-            ) {
+            ) || params.is_at_max_depth(depth)
+            {
                 continue;
             }
 
             let mut out_edges: Vec<_> = graph.outgoing_edges(current).collect();
             out_edges.sort_by(|(a_idx, _), (b_idx, _)| {
-                let a_sym = idx_to_symbol.get(a_idx).copied().unwrap_or("");
-                let b_sym = idx_to_symbol.get(b_idx).copied().unwrap_or("");
+                let a_sym = graph.symbol_of(*a_idx).unwrap_or("");
+                let b_sym = graph.symbol_of(*b_idx).unwrap_or("");
                 a_sym.cmp(b_sym)
             });
 
             for (neighbor, edge_kind) in out_edges {
+                if params.is_excluded_target(graph, neighbor)
+                    || is_sibling_override(&reached_via, edge_kind)
+                {
+                    continue;
+                }
                 let neighbor_node = graph.node(neighbor);
                 let decision =
                     evaluate_forward(params, current_node, neighbor_node, edge_kind, graph);
+                trace_decision(graph, current, neighbor, edge_kind, &decision);
 
                 if matches!(decision, PruningDecision::Transparent) {
                     if !start_set.contains(&neighbor) {
                         predecessors.entry(neighbor).or_insert(current);
                     }
-                    queue.push_back((
-                        neighbor,
-                        depth + 1,
-                        ReachedVia::Forward(edge_kind.clone()),
-                        Some(decision),
-                    ));
-                } else if !visited.contains(&neighbor) {
-                    let boundary_size = neighbor_node.core().context_size;
+                    queue.push_back(QueueEntry {
+                        node: neighbor,
+                        depth: depth + 1,
+                        reached_via: ReachedVia::Forward(edge_kind.clone()),
+                        decision: Some(decision),
+                        parent: Some(current),
+                    });
+                } else if !visited.contains(&neighbor) && !opens_file_over_budget(&files, neighbor)
+                {
+                    let boundary_size = params.counted_size(neighbor_node, true, depth + 1);
                     if let Some(limit) = max_tokens
-                        && total_size + boundary_size > limit
+                        && total_size.saturating_add(boundary_size) > limit
                     {
                         break;
                     }
@@ -340,12 +714,22 @@ impl CfSolver {
                         predecessors.entry(neighbor).or_insert(current);
                     }
                     if visited.insert(neighbor) {
-                        total_size += boundary_size;
+                        files.extend(counted_file(graph, neighbor));
+                        let added = boundary_size.saturating_add(self.signature_types_size(
+                            neighbor_node,
+                            depth + 1,
+                            &mut seen_types,
+                        ));
+                        total_size = total_size.saturating_add(added);
+                        added_sizes.push(added);
                         ordered.push(neighbor);
                         traversal_steps.push(TraversalStep {
                             node_id: neighbor_node.core().id,
                             incoming_edge_kind: Some(edge_kind.clone()),
                             decision: Some(decision),
+                            reached_via: ReachedVia::Forward(edge_kind.clone()),
+                            parent: Some(current_id),
+                            depth: depth + 1,
                         });
 
                         let boundary_depth = depth + 1;
@@ -367,17 +751,23 @@ impl CfSolver {
                         .incoming_edges(current, Some(EdgeKind::Call))
                         .collect();
                     callers.sort_by(|(a_idx, _), (b_idx, _)| {
-                        let a_sym = idx_to_symbol.get(a_idx).copied().unwrap_or("");
-                        let b_sym = idx_to_symbol.get(b_idx).copied().unwrap_or("");
+                        let a_sym = graph.symbol_of(*a_idx).unwrap_or("");
+                        let b_sym = graph.symbol_of(*b_idx).unwrap_or("");
                         a_sym.cmp(b_sym)
                     });
 
                     for (caller_idx, _) in callers {
-                        if !visited.contains(&caller_idx) {
+                        if !visited.contains(&caller_idx) && !self.is_excluded_caller(caller_idx) {
                             if !start_set.contains(&caller_idx) {
                                 predecessors.entry(caller_idx).or_insert(current);
                             }
-                            queue.push_back((caller_idx, depth + 1, ReachedVia::CallIn, None));
+                            queue.push_back(QueueEntry {
+                                node: caller_idx,
+                                depth: depth + 1,
+                                reached_via: ReachedVia::CallIn,
+                                decision: None,
+                                parent: Some(current),
+                            });
                         }
                     }
                 }
@@ -391,8 +781,8 @@ impl CfSolver {
                     .incoming_edges(current, Some(EdgeKind::Write))
                     .collect();
                 writers.sort_by(|(a_idx, _), (b_idx, _)| {
-                    let a_sym = idx_to_symbol.get(a_idx).copied().unwrap_or("");
-                    let b_sym = idx_to_symbol.get(b_idx).copied().unwrap_or("");
+                    let a_sym = graph.symbol_of(*a_idx).unwrap_or("");
+                    let b_sym = graph.symbol_of(*b_idx).unwrap_or("");
                     a_sym.cmp(b_sym)
                 });
 
@@ -401,12 +791,13 @@ impl CfSolver {
                         if !start_set.contains(&writer_idx) {
                             predecessors.entry(writer_idx).or_insert(current);
                         }
-                        queue.push_back((
-                            writer_idx,
-                            depth + 1,
-                            ReachedVia::SharedStateWrite,
-                            None,
-                        ));
+                        queue.push_back(QueueEntry {
+                            node: writer_idx,
+                            depth: depth + 1,
+                            reached_via: ReachedVia::SharedStateWrite,
+                            decision: None,
+                            parent: Some(current),
+                        });
                     }
                 }
             }
@@ -423,11 +814,64 @@ impl CfSolver {
             ordered,
             layers,
             traversal_steps,
+            added_sizes,
             total_context_size: total_size,
             predecessors,
+            cancelled,
         }
     }
 
+    /// Size of `node`'s parameter and return types not yet in `seen_types`, counted as
+    /// boundaries one layer below `depth`. 0 unless `include_signature_types` is set.
+    fn signature_types_size(
+        &self,
+        node: &Node,
+        depth: u32,
+        seen_types: &mut HashSet<String>,
+    ) -> u32 {
+        if !self.params.include_signature_types {
+            return 0;
+        }
+        let Node::Function(f) = node else {
+            return 0;
+        };
+        let registry = &self.graph.type_registry;
+        f.parameters
+            .iter()
+            .filter_map(|p| p.param_type.as_ref())
+            .chain(&f.return_types)
+            .filter_map(|type_id| {
+                let info = registry.get(type_id)?;
+                seen_types
+                    .insert(type_id.clone())
+                    .then(|| self.params.weighted_size(info.context_size, depth + 1))
+            })
+            .fold(0u32, u32::saturating_add)
+    }
+
+    /// Traversal steps leading from `start` to `target`, start first, following the parent
+    /// recorded for each step. BFS order makes this a shortest chain under CF semantics
+    /// (call-in and shared-state hops included). `None` when `target` is not reached.
+    pub fn path_to(&self, start: NodeIndex, target: NodeIndex) -> Option<Vec<TraversalStep>> {
+        let target_id = self.graph.node(target).core().id;
+        let traversal = self.traverse(&[start], None, None);
+        let mut steps: HashMap<NodeId, TraversalStep> = traversal
+            .traversal_steps
+            .into_iter()
+            .map(|step| (step.node_id, step))
+            .collect();
+
+        let mut path = Vec::new();
+        let mut cursor = Some(target_id);
+        while let Some(id) = cursor {
+            let step = steps.remove(&id)?;
+            cursor = step.parent;
+            path.push(step);
+        }
+        path.reverse();
+        Some(path)
+    }
+
     fn reconstruct_path(
         &self,
         target: NodeIndex,
@@ -550,6 +994,43 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_balanced_expands_documented_function_with_high_freedom_param() {
+        // a -> b -> c; b is typed + documented, with a `dict` parameter when `high_freedom`
+        let build = |high_freedom: bool| {
+            let mut graph = ContextGraph::new();
+            let a = graph.add_node("sym::a".into(), test_node(0, "a", 10));
+            let Node::Function(mut b) = test_node_boundary(1, "b", 10) else {
+                unreachable!()
+            };
+            if high_freedom {
+                b.parameters[0].param_type = Some("dict#".to_string());
+                b.parameters[0].is_high_freedom_type = true;
+            }
+            let b = graph.add_node("sym::b".into(), Node::Function(b));
+            let c = graph.add_node("sym::c".into(), test_node(2, "c", 10));
+            graph.add_edge(a, b, EdgeKind::Call);
+            graph.add_edge(b, c, EdgeKind::Call);
+            (Arc::new(graph), a)
+        };
+
+        let (graph, a) = build(true);
+        let academic = CfSolver::new(graph.clone(), PruningParams::academic(0.5));
+        let result = academic.compute_cf(&[a], None);
+        assert_eq!(result.reachable_set.len(), 2);
+        assert_eq!(result.boundary_count(), 1);
+
+        let balanced = CfSolver::new(graph, PruningParams::balanced(0.5));
+        let result = balanced.compute_cf(&[a], None);
+        assert_eq!(result.reachable_set.len(), 3);
+        assert!(result.reachable_set.contains(&2));
+
+        // Without the high-freedom parameter balanced agrees with academic
+        let (graph, a) = build(false);
+        let balanced = CfSolver::new(graph, PruningParams::balanced(0.5));
+        assert_eq!(balanced.compute_cf(&[a], None).reachable_set.len(), 2);
+    }
+
     #[test]
     fn test_single_node_cf() {
         let mut graph = ContextGraph::new();
@@ -575,6 +1056,42 @@ mod tests {
         assert_eq!(result.total_context_size, 10 + 20 + 30);
     }
 
+    #[test]
+    fn test_depth_size_multiplier_penalizes_deep_chains() {
+        // Deep: d0 -> d1 -> d2 -> d3; shallow: s0 -> {s1, s2, s3}. Equal raw size (40).
+        let mut graph = ContextGraph::new();
+        let d: Vec<_> = (0..4)
+            .map(|i| graph.add_node(format!("sym::d{i}"), test_node(i, "d", 10)))
+            .collect();
+        for w in d.windows(2) {
+            graph.add_edge(w[0], w[1], EdgeKind::Call);
+        }
+        let s: Vec<_> = (0..4)
+            .map(|i| graph.add_node(format!("sym::s{i}"), test_node(4 + i, "s", 10)))
+            .collect();
+        for &leaf in &s[1..] {
+            graph.add_edge(s[0], leaf, EdgeKind::Call);
+        }
+        let graph = Arc::new(graph);
+
+        let unweighted = CfSolver::new(graph.clone(), PruningParams::strict(0.5));
+        assert_eq!(unweighted.compute_cf(&[d[0]], None).total_context_size, 40);
+        assert_eq!(unweighted.compute_cf(&[s[0]], None).total_context_size, 40);
+
+        let params = PruningParams {
+            depth_size_multiplier: 1.0,
+            ..PruningParams::strict(0.5)
+        };
+        let weighted = CfSolver::new(graph, params);
+        let deep = weighted.compute_cf(&[d[0]], None).total_context_size;
+        let shallow = weighted.compute_cf(&[s[0]], None).total_context_size;
+        assert_eq!(deep, 10 + 20 + 30 + 40);
+        assert_eq!(shallow, 10 + 20 * 3);
+        assert!(deep > shallow);
+        assert_eq!(weighted.compute_cf_total(d[0]), deep);
+        assert_eq!(weighted.compute_cf_total(s[0]), shallow);
+    }
+
     #[test]
     fn test_diamond_dependency() {
         let mut graph = ContextGraph::new();
@@ -592,6 +1109,50 @@ mod tests {
         assert_eq!(result.total_context_size, 10 + 20 + 30 + 40);
     }
 
+    #[test]
+    fn test_compute_cf_excluding_subtracts_already_read_nodes() {
+        let mut graph = ContextGraph::new();
+        let a = graph.add_node("sym::a".into(), test_node(0, "a", 10));
+        let b = graph.add_node("sym::b".into(), test_node(1, "b", 20));
+        let shared = graph.add_node("sym::shared".into(), test_node(2, "shared", 30));
+        let deep = graph.add_node("sym::deep".into(), test_node(3, "deep", 40));
+        graph.add_edge(a, shared, EdgeKind::Call);
+        graph.add_edge(b, shared, EdgeKind::Call);
+        graph.add_edge(shared, deep, EdgeKind::Call);
+        let solver = CfSolver::new(Arc::new(graph), PruningParams::strict(0.5));
+
+        let full = solver.compute_cf(&[a], None);
+        let already_read: HashSet<NodeId> = [2].into_iter().collect();
+        let rest = solver.compute_cf_excluding(&[a], &already_read, None);
+
+        assert_eq!(rest.total_context_size, full.total_context_size - 30);
+        // Dependencies behind the read node still count
+        assert_eq!(rest.reachable_set, [0, 3].into_iter().collect());
+        assert_eq!(rest.reachable_nodes_ordered, vec![0, 3]);
+        assert!(rest.traversal_steps.iter().all(|s| s.node_id != 2));
+        assert!(
+            rest.reachable_nodes_by_layer
+                .iter()
+                .flatten()
+                .all(|&id| id != 2)
+        );
+        assert_eq!(
+            solver
+                .compute_cf_excluding(&[a], &HashSet::new(), None)
+                .total_context_size,
+            full.total_context_size
+        );
+        // The token budget bounds the traversal like compute_cf
+        let bounded = solver.compute_cf(&[a], Some(35));
+        assert!(bounded.total_context_size < full.total_context_size);
+        assert_eq!(
+            solver
+                .compute_cf_excluding(&[a], &HashSet::new(), Some(35))
+                .reachable_set,
+            bounded.reachable_set
+        );
+    }
+
     #[test]
     fn test_cycle_detection() {
         let mut graph = ContextGraph::new();
@@ -621,6 +1182,33 @@ mod tests {
         assert_eq!(result.total_context_size, 10 + 20); // a and b both count
     }
 
+    #[test]
+    fn test_boundary_counts_signature_only_uses_signature_size() {
+        let mut graph = ContextGraph::new();
+        let a = graph.add_node("sym::a".into(), test_node(0, "a", 10));
+        let mut boundary = test_node_boundary(1, "b", 500);
+        boundary.core_mut().signature_size = Some(15);
+        let b = graph.add_node("sym::b".into(), boundary);
+        graph.add_edge(a, b, EdgeKind::Call);
+        let graph = Arc::new(graph);
+
+        let full = CfSolver::new(Arc::clone(&graph), PruningParams::academic(0.5));
+        let result = full.compute_cf(&[a], None);
+        assert_eq!(result.total_context_size, 10 + 500);
+        // The weighted sum is reported alongside the raw one without changing the policy
+        assert_eq!(result.weighted_context_size, 10 + 15);
+
+        let params = PruningParams {
+            boundary_counts_signature_only: true,
+            ..PruningParams::academic(0.5)
+        };
+        let solver = CfSolver::new(graph, params);
+        assert_eq!(solver.compute_cf(&[a], None).total_context_size, 10 + 15);
+        assert_eq!(solver.compute_cf_total(a), 10 + 15);
+        // A start node is read in full even when it would be a boundary elsewhere.
+        assert_eq!(solver.compute_cf(&[b], None).total_context_size, 500);
+    }
+
     #[test]
     fn test_transparent_node_continues() {
         let mut graph = ContextGraph::new();
@@ -680,6 +1268,49 @@ mod tests {
         assert_eq!(result.witness_paths, vec![vec![1, 0]]);
     }
 
+    #[test]
+    fn test_path_to_follows_recorded_parents_including_call_in() {
+        // caller -> callee -> helper; `other` is unrelated. The callee takes an untyped
+        // parameter and writes shared state, so call-in exploration reaches its caller.
+        let mut graph = ContextGraph::new();
+        let caller = graph.add_node("sym::caller".into(), test_node(0, "caller", 10));
+        let Node::Function(mut callee) = test_node(1, "callee", 20) else {
+            unreachable!()
+        };
+        callee.parameters.push(crate::domain::node::Parameter {
+            name: "x".to_string(),
+            param_type: None,
+            is_high_freedom_type: false,
+        });
+        let callee = graph.add_node("sym::callee".into(), Node::Function(callee));
+        let helper = graph.add_node("sym::helper".into(), test_node(2, "helper", 5));
+        let other = graph.add_node("sym::other".into(), test_node(3, "other", 5));
+        let state = graph.add_node(
+            "sym::state".into(),
+            test_var_node(4, "state", crate::domain::node::Mutability::Mutable),
+        );
+        graph.add_edge(caller, callee, EdgeKind::Call);
+        graph.add_edge(callee, helper, EdgeKind::Call);
+        graph.add_edge(callee, state, EdgeKind::Write);
+        let solver = CfSolver::new(Arc::new(graph), PruningParams::strict(0.5));
+
+        let path = solver.path_to(caller, helper).unwrap();
+        let ids: Vec<_> = path.iter().map(|step| step.node_id).collect();
+        assert_eq!(ids, vec![0, 1, 2]);
+        assert_eq!(path[0].reached_via, ReachedVia::Start);
+        assert_eq!(path[2].reached_via, ReachedVia::Forward(EdgeKind::Call));
+        assert_eq!(path[2].parent, Some(1));
+
+        // Its caller is pulled in through call-in exploration
+        let path = solver.path_to(callee, caller).unwrap();
+        let ids: Vec<_> = path.iter().map(|step| step.node_id).collect();
+        assert_eq!(ids, vec![1, 0]);
+        assert_eq!(path[1].reached_via, ReachedVia::CallIn);
+        assert_eq!(path[1].parent, Some(1));
+
+        assert!(solver.path_to(caller, other).is_none());
+    }
+
     #[test]
     fn test_reachable_uses_union_of_multiple_starts_and_targets() {
         let mut graph = ContextGraph::new();
@@ -764,6 +1395,108 @@ mod tests {
         assert_eq!(result.total_context_size, 10 + 25 + 1);
     }
 
+    #[test]
+    fn test_utility_threshold_and_constructor_call_in_are_tunable() {
+        // Two callers of a 30-token impure callee: 15 tokens per caller
+        let build = |is_constructor: bool| {
+            let mut graph = ContextGraph::new();
+            let Node::Function(mut callee) = test_node(0, "callee", 30) else {
+                unreachable!()
+            };
+            callee.is_constructor = is_constructor;
+            let callee = graph.add_node("sym::callee".into(), Node::Function(callee));
+            let a = graph.add_node("sym::a".into(), test_node(1, "a", 5));
+            let b = graph.add_node("sym::b".into(), test_node(2, "b", 5));
+            let var = graph.add_node(
+                "sym::var".into(),
+                test_var_node(3, "var", crate::domain::node::Mutability::Mutable),
+            );
+            graph.add_edge(a, callee, EdgeKind::Call);
+            graph.add_edge(b, callee, EdgeKind::Call);
+            graph.add_edge(callee, var, EdgeKind::Write);
+            (Arc::new(graph), callee)
+        };
+
+        let (graph, callee) = build(false);
+        for (threshold, explores_callers) in [(0, true), (10, true), (15, true), (16, false)] {
+            let params = PruningParams {
+                utility_tokens_per_caller_threshold: threshold,
+                ..PruningParams::strict(0.5)
+            };
+            let result = CfSolver::new(Arc::clone(&graph), params).compute_cf(&[callee], None);
+            assert_eq!(
+                result.reachable_set.contains(&1),
+                explores_callers,
+                "threshold {threshold}"
+            );
+        }
+
+        let (graph, constructor) = build(true);
+        let default = CfSolver::new(Arc::clone(&graph), PruningParams::strict(0.5));
+        assert_eq!(
+            default.compute_cf(&[constructor], None).reachable_set.len(),
+            2
+        );
+        let params = PruningParams {
+            explore_constructor_callers: true,
+            ..PruningParams::strict(0.5)
+        };
+        let solver = CfSolver::new(graph, params);
+        assert_eq!(
+            solver.compute_cf(&[constructor], None).reachable_set.len(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_exclude_test_callers_skips_test_code_during_call_in() {
+        #[derive(Debug)]
+        struct TestsDir;
+        impl TestCodeClassifier for TestsDir {
+            fn is_test_code(&self, _symbol: &str, file_path: &str) -> bool {
+                file_path.starts_with("tests/")
+            }
+        }
+
+        // prod_caller and test_helper both call callee, which writes shared state (call-in)
+        let mut graph = ContextGraph::new();
+        let callee = graph.add_node("sym::callee".into(), test_node(0, "callee", 100));
+        let prod = graph.add_node("sym::prod_caller".into(), test_node(1, "prod_caller", 25));
+        let Node::Function(mut helper) = test_node(2, "make_fixture", 40) else {
+            unreachable!()
+        };
+        helper.core.file_path = "tests/fixtures.py".to_string();
+        let helper = graph.add_node("sym::make_fixture".into(), Node::Function(helper));
+        let var = graph.add_node(
+            "sym::var".into(),
+            test_var_node(3, "var", crate::domain::node::Mutability::Mutable),
+        );
+        graph.add_edge(prod, callee, EdgeKind::Call);
+        graph.add_edge(helper, callee, EdgeKind::Call);
+        graph.add_edge(callee, var, EdgeKind::Write);
+        let graph = Arc::new(graph);
+
+        let default = CfSolver::new(Arc::clone(&graph), PruningParams::strict(0.5))
+            .with_test_classifier(Arc::new(TestsDir));
+        assert!(
+            default
+                .compute_cf(&[callee], None)
+                .reachable_set
+                .contains(&2)
+        );
+
+        let params = PruningParams {
+            exclude_test_callers: true,
+            ..PruningParams::strict(0.5)
+        };
+        let solver = CfSolver::new(graph, params).with_test_classifier(Arc::new(TestsDir));
+        let result = solver.compute_cf(&[callee], None);
+        assert!(result.reachable_set.contains(&1));
+        assert!(!result.reachable_set.contains(&2));
+        assert_eq!(result.total_context_size, 100 + 25 + 1);
+        assert_eq!(solver.compute_cf_total(callee), 100 + 25 + 1);
+    }
+
     #[test]
     fn test_different_policies_different_results() {
         let mut graph = ContextGraph::new();
@@ -884,6 +1617,416 @@ mod tests {
         assert_eq!(result.reachable_nodes_by_layer[2][0], 3);
     }
 
+    #[test]
+    fn test_cancellation_aborts_mid_traversal() {
+        use crate::domain::policy::CustomPruningRule;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        /// Trips the flag when the traversal reaches `n10`, simulating a client disconnect.
+        #[derive(Debug)]
+        struct TripAt(Arc<AtomicBool>);
+        impl CustomPruningRule for TripAt {
+            fn evaluate(
+                &self,
+                _source: &Node,
+                target: &Node,
+                _edge_kind: &EdgeKind,
+                _graph: &ContextGraph,
+            ) -> Option<PruningDecision> {
+                if target.core().name == "n10" {
+                    self.0.store(true, Ordering::Relaxed);
+                }
+                None
+            }
+        }
+
+        let mut graph = ContextGraph::new();
+        let nodes: Vec<_> = (0..1000)
+            .map(|i| graph.add_node(format!("sym::n{i}"), test_node(i, &format!("n{i}"), 1)))
+            .collect();
+        for pair in nodes.windows(2) {
+            graph.add_edge(pair[0], pair[1], EdgeKind::Call);
+        }
+        let graph = Arc::new(graph);
+
+        let uncancelled = CfSolver::new(Arc::clone(&graph), PruningParams::strict(0.5));
+        assert_eq!(
+            uncancelled.compute_cf(&[nodes[0]], None).total_context_size,
+            1000
+        );
+
+        let flag = Arc::new(AtomicBool::new(false));
+        let params = PruningParams {
+            custom_rules: vec![Arc::new(TripAt(Arc::clone(&flag)))],
+            ..PruningParams::strict(0.5)
+        };
+        let solver = CfSolver::new(Arc::clone(&graph), params).with_cancellation(Arc::clone(&flag));
+        let result = solver.compute_cf(&[nodes[0]], None);
+        assert!(result.cancelled);
+        assert!(result.reachable_set.len() <= 11);
+
+        flag.store(false, Ordering::Relaxed);
+        let total = solver.compute_cf_total(nodes[0]);
+        assert!(flag.load(Ordering::Relaxed));
+        assert!(total <= 11);
+    }
+
+    #[test]
+    fn test_unique_source_lines_counts_overlap_once() {
+        let mut graph = ContextGraph::new();
+        // Class-level node spanning lines 0..=9 and a nested method on lines 2..=4,
+        // plus a node in another file on lines 0..=1.
+        let span_node = |id, name: &str, file: &str, start, end| {
+            let mut node = test_node(id, name, 10);
+            if let Node::Function(f) = &mut node {
+                f.core.file_path = file.to_string();
+                f.core.span.start_line = start;
+                f.core.span.end_line = end;
+            }
+            node
+        };
+        let outer = graph.add_node("sym::outer".into(), span_node(0, "outer", "a.py", 0, 9));
+        let inner = graph.add_node("sym::inner".into(), span_node(1, "inner", "a.py", 2, 4));
+        let other = graph.add_node("sym::other".into(), span_node(2, "other", "b.py", 0, 1));
+        graph.add_edge(outer, inner, EdgeKind::Call);
+        graph.add_edge(inner, other, EdgeKind::Call);
+
+        let solver = CfSolver::new(Arc::new(graph), PruningParams::strict(0.5));
+        let result = solver.compute_cf(&[outer], None);
+        assert_eq!(result.reachable_set.len(), 3);
+        // 10 lines in a.py (inner is inside outer) + 2 lines in b.py
+        assert_eq!(result.unique_source_lines, 12);
+    }
+
+    #[test]
+    fn test_min_traversal_size_treats_tiny_nodes_as_leaves() {
+        // a -> tiny (2 tokens) -> c
+        let mut graph = ContextGraph::new();
+        let a = graph.add_node("sym::a".into(), test_node(0, "a", 10));
+        let tiny = graph.add_node("sym::tiny".into(), test_node(1, "tiny", 2));
+        let c = graph.add_node("sym::c".into(), test_node(2, "c", 30));
+        graph.add_edge(a, tiny, EdgeKind::Call);
+        graph.add_edge(tiny, c, EdgeKind::Call);
+        let graph = Arc::new(graph);
+
+        let default = CfSolver::new(Arc::clone(&graph), PruningParams::strict(0.5));
+        assert_eq!(default.compute_cf_total(a), 10 + 2 + 30);
+
+        let params = PruningParams {
+            min_traversal_size: 5,
+            ..PruningParams::strict(0.5)
+        };
+        let solver = CfSolver::new(Arc::clone(&graph), params);
+        let result = solver.compute_cf(&[a], None);
+        assert!(result.reachable_set.contains(&1));
+        assert!(!result.reachable_set.contains(&2));
+        assert_eq!(result.total_context_size, 10 + 2);
+        assert_eq!(solver.compute_cf_total(a), 10 + 2);
+    }
+
+    #[test]
+    fn test_exclude_edge_targets_hides_matching_calls() {
+        // handler -> logger.info, handler -> save
+        let mut graph = ContextGraph::new();
+        let handler = graph.add_node("sym::handler".into(), test_node(0, "handler", 10));
+        let log = graph.add_node("sym::logger.info".into(), test_node(1, "info", 20));
+        let save = graph.add_node("sym::save".into(), test_node(2, "save", 30));
+        graph.add_edge(handler, log, EdgeKind::Call);
+        graph.add_edge(handler, save, EdgeKind::Call);
+        let graph = Arc::new(graph);
+
+        let params = PruningParams {
+            exclude_edge_targets: vec![regex::Regex::new(r"logger\.").unwrap()],
+            ..PruningParams::strict(0.5)
+        };
+        let solver = CfSolver::new(Arc::clone(&graph), params);
+        let result = solver.compute_cf(&[handler], None);
+        assert!(!result.reachable_set.contains(&1));
+        assert!(result.reachable_set.contains(&2));
+        assert_eq!(result.total_context_size, 10 + 30);
+        assert_eq!(result.boundary_count(), 0);
+        assert_eq!(solver.compute_cf_total(handler), 10 + 30);
+    }
+
+    #[test]
+    fn test_include_signature_types_adds_parameter_type_size() {
+        use crate::domain::type_registry::{TypeDefAttribute, TypeInfo, TypeKind};
+
+        let mut graph = ContextGraph::new();
+        graph.type_registry.register(
+            "Order#".to_string(),
+            TypeInfo {
+                definition: TypeDefAttribute {
+                    type_kind: TypeKind::Class,
+                    is_abstract: false,
+                    is_data_class: false,
+                    is_frozen: false,
+                    type_param_count: 0,
+                    type_var_info: None,
+                },
+                context_size: 40,
+                doc_score: 0.0,
+            },
+        );
+        let Node::Function(mut f) = test_node(0, "ship", 10) else {
+            unreachable!()
+        };
+        f.parameters = vec![crate::domain::node::Parameter {
+            name: "order".to_string(),
+            param_type: Some("Order#".to_string()),
+            is_high_freedom_type: false,
+        }];
+        // The same type twice (parameter + return) is only counted once.
+        f.return_types = vec!["Order#".to_string()];
+        let ship = graph.add_node("sym::ship".into(), Node::Function(f));
+        let graph = Arc::new(graph);
+
+        let default = CfSolver::new(Arc::clone(&graph), PruningParams::strict(0.5));
+        assert_eq!(default.compute_cf(&[ship], None).total_context_size, 10);
+
+        let params = PruningParams {
+            include_signature_types: true,
+            ..PruningParams::strict(0.5)
+        };
+        let solver = CfSolver::new(graph, params);
+        let result = solver.compute_cf(&[ship], None);
+        assert_eq!(result.total_context_size, 10 + 40);
+        assert_eq!(result.reachable_set.len(), 1);
+        assert_eq!(solver.compute_cf_total(ship), 10 + 40);
+    }
+
+    #[test]
+    fn test_compute_cf_excluding_drops_signature_types_of_read_nodes() {
+        use crate::domain::type_registry::{TypeDefAttribute, TypeInfo, TypeKind};
+
+        let mut graph = ContextGraph::new();
+        graph.type_registry.register(
+            "Order#".to_string(),
+            TypeInfo {
+                definition: TypeDefAttribute {
+                    type_kind: TypeKind::Class,
+                    is_abstract: false,
+                    is_data_class: false,
+                    is_frozen: false,
+                    type_param_count: 0,
+                    type_var_info: None,
+                },
+                context_size: 40,
+                doc_score: 0.0,
+            },
+        );
+        let main = graph.add_node("sym::main".into(), test_node(0, "main", 10));
+        let Node::Function(mut f) = test_node(1, "ship", 20) else {
+            unreachable!()
+        };
+        f.parameters = vec![crate::domain::node::Parameter {
+            name: "order".to_string(),
+            param_type: Some("Order#".to_string()),
+            is_high_freedom_type: false,
+        }];
+        let ship = graph.add_node("sym::ship".into(), Node::Function(f));
+        graph.add_edge(main, ship, EdgeKind::Call);
+        let params = PruningParams {
+            include_signature_types: true,
+            ..PruningParams::strict(0.5)
+        };
+        let solver = CfSolver::new(Arc::new(graph), params);
+
+        assert_eq!(
+            solver.compute_cf(&[main], None).total_context_size,
+            10 + 20 + 40
+        );
+        let already_read: HashSet<NodeId> = [1].into_iter().collect();
+        let rest = solver.compute_cf_excluding(&[main], &already_read, None);
+        // ship's body and the Order type it brought in are both already read
+        assert_eq!(rest.total_context_size, 10);
+    }
+
+    #[test]
+    fn test_constructor_boundary_flag_controls_init_dependencies() {
+        // main -> Order.__init__ (typed + documented) -> load_defaults
+        let mut graph = ContextGraph::new();
+        let main = graph.add_node("sym::main".into(), test_node(0, "main", 10));
+        let Node::Function(mut init) = test_node_boundary(1, "__init__", 20) else {
+            unreachable!()
+        };
+        init.is_constructor = true;
+        let init = graph.add_node("sym::Order.__init__".into(), Node::Function(init));
+        let defaults = graph.add_node(
+            "sym::load_defaults".into(),
+            test_node(2, "load_defaults", 30),
+        );
+        graph.add_edge(main, init, EdgeKind::Call);
+        graph.add_edge(init, defaults, EdgeKind::Call);
+        let graph = Arc::new(graph);
+
+        let boundary = CfSolver::new(Arc::clone(&graph), PruningParams::academic(0.5));
+        let result = boundary.compute_cf(&[main], None);
+        assert!(result.reachable_set.contains(&1));
+        assert!(!result.reachable_set.contains(&2));
+
+        let params = PruningParams {
+            treat_constructor_as_boundary: false,
+            ..PruningParams::academic(0.5)
+        };
+        let transparent = CfSolver::new(graph, params);
+        let result = transparent.compute_cf(&[main], None);
+        assert!(result.reachable_set.contains(&2));
+        assert_eq!(result.total_context_size, 10 + 20 + 30);
+    }
+
+    #[test]
+    fn test_inherits_expands_concrete_bases_and_stops_at_documented_abstract_base() {
+        // Leaf.run -Inherits-> Mid.run -Inherits-> Base.run (abstract) -Call-> hook
+        let build = |base_doc_score: f32| {
+            let mut graph = ContextGraph::new();
+            let leaf = graph.add_node("sym::Leaf.run".into(), test_node(0, "run", 10));
+            // Typed + documented: a boundary over Call, but a concrete base is always read
+            let mid = graph.add_node("sym::Mid.run".into(), test_node_boundary(1, "run", 20));
+            let Node::Function(mut base) = test_node_boundary(2, "run", 5) else {
+                unreachable!()
+            };
+            base.is_interface_method = true;
+            base.core.doc_score = base_doc_score;
+            let base = graph.add_node("sym::Base.run".into(), Node::Function(base));
+            let hook = graph.add_node("sym::hook".into(), test_node(3, "hook", 40));
+            graph.add_edge(leaf, mid, EdgeKind::Inherits);
+            graph.add_edge(mid, base, EdgeKind::Inherits);
+            graph.add_edge(base, hook, EdgeKind::Call);
+            (graph, leaf)
+        };
+
+        let (graph, leaf) = build(0.8);
+        let solver = CfSolver::new(Arc::new(graph), PruningParams::academic(0.5));
+        let result = solver.compute_cf(&[leaf], None);
+        assert_eq!(result.total_context_size, 10 + 20 + 5);
+        assert!(!result.reachable_set.contains(&3));
+
+        // An undocumented abstract base is a leaky contract: traversal continues through it
+        let (graph, leaf) = build(0.0);
+        let solver = CfSolver::new(Arc::new(graph), PruningParams::academic(0.5));
+        let result = solver.compute_cf(&[leaf], None);
+        assert_eq!(result.total_context_size, 10 + 20 + 5 + 40);
+    }
+
+    #[test]
+    fn test_inherited_base_does_not_lead_to_sibling_overrides() {
+        // Leaf.run -Inherits-> Base.run -OverriddenBy-> {Leaf.run, Sibling.run}
+        let mut graph = ContextGraph::new();
+        let leaf = graph.add_node("sym::Leaf.run".into(), test_node(0, "run", 10));
+        let base = graph.add_node("sym::Base.run".into(), test_node(1, "run", 20));
+        let sibling = graph.add_node("sym::Sibling.run".into(), test_node(2, "run", 40));
+        graph.add_edge(leaf, base, EdgeKind::Inherits);
+        graph.add_edge(base, leaf, EdgeKind::OverriddenBy);
+        graph.add_edge(base, sibling, EdgeKind::OverriddenBy);
+        let graph = Arc::new(graph);
+
+        let solver = CfSolver::new(graph.clone(), PruningParams::academic(0.5));
+        let result = solver.compute_cf(&[leaf], None);
+        assert!(!result.reachable_set.contains(&2));
+        assert_eq!(result.total_context_size, 10 + 20);
+
+        // Starting from the base itself, its overrides are still part of its context
+        let result = solver.compute_cf(&[base], None);
+        assert!(result.reachable_set.contains(&2));
+    }
+
+    #[test]
+    fn test_max_files_limits_distinct_reached_files() {
+        // a.py:a -> b.py:b -> c.py:c -> d.py:d, plus a -> a.py:a2 (same file as the start)
+        let mut graph = ContextGraph::new();
+        let mut idx = Vec::new();
+        for (id, name) in ["a", "b", "c", "d"].iter().enumerate() {
+            let Node::Function(mut f) = test_node(id as u32, name, 10) else {
+                unreachable!()
+            };
+            f.core.file_path = format!("{name}.py");
+            idx.push(graph.add_node(format!("sym::{name}"), Node::Function(f)));
+        }
+        let Node::Function(mut a2) = test_node(4, "a2", 10) else {
+            unreachable!()
+        };
+        a2.core.file_path = "a.py".to_string();
+        let a2 = graph.add_node("sym::a2".into(), Node::Function(a2));
+        for pair in idx.windows(2) {
+            graph.add_edge(pair[0], pair[1], EdgeKind::Call);
+        }
+        graph.add_edge(idx[0], a2, EdgeKind::Call);
+        let solver = CfSolver::new(Arc::new(graph), PruningParams::strict(0.5));
+
+        assert_eq!(solver.compute_cf(&[idx[0]], None).reachable_set.len(), 5);
+
+        let result = solver.compute_cf_with_file_limit(&[idx[0]], None, Some(2));
+        let mut reached: Vec<_> = result.reachable_set.into_iter().collect();
+        reached.sort_unstable();
+        assert_eq!(reached, vec![0, 1, 4]);
+        assert_eq!(result.total_context_size, 30);
+    }
+
+    #[test]
+    fn test_max_depth_counts_capped_node_without_expanding_it() {
+        // a -> b -> c -> d, all untyped so every hop expands without a cap
+        let mut graph = ContextGraph::new();
+        let idx: Vec<_> = ["a", "b", "c", "d"]
+            .iter()
+            .enumerate()
+            .map(|(id, name)| {
+                graph.add_node(format!("sym::{name}"), test_node(id as u32, name, 10))
+            })
+            .collect();
+        for pair in idx.windows(2) {
+            graph.add_edge(pair[0], pair[1], EdgeKind::Call);
+        }
+        let graph = Arc::new(graph);
+
+        let unbounded = CfSolver::new(graph.clone(), PruningParams::strict(0.5));
+        assert_eq!(unbounded.compute_cf(&[idx[0]], None).reachable_set.len(), 4);
+
+        let params = PruningParams {
+            max_depth: Some(1),
+            ..PruningParams::strict(0.5)
+        };
+        let solver = CfSolver::new(graph, params);
+        let result = solver.compute_cf(&[idx[0]], None);
+        let mut reached: Vec<_> = result.reachable_set.into_iter().collect();
+        reached.sort_unstable();
+        assert_eq!(reached, vec![0, 1]);
+        assert_eq!(result.total_context_size, 20);
+        assert_eq!(solver.compute_cf_total(idx[0]), 20);
+    }
+
+    #[test]
+    fn test_scratch_total_matches_per_call_allocation() {
+        let mut graph = ContextGraph::new();
+        // A -> B -> C -> D, A -> D, plus a cycle E <-> F.
+        let a = graph.add_node("sym::a".into(), test_node(0, "a", 10));
+        let b = graph.add_node("sym::b".into(), test_node(1, "b", 20));
+        let c = graph.add_node("sym::c".into(), test_node_boundary(2, "c", 30));
+        let d = graph.add_node("sym::d".into(), test_node(3, "d", 40));
+        let e = graph.add_node("sym::e".into(), test_node(4, "e", 5));
+        let f = graph.add_node("sym::f".into(), test_node(5, "f", 6));
+
+        graph.add_edge(a, b, EdgeKind::Call);
+        graph.add_edge(b, c, EdgeKind::Call);
+        graph.add_edge(c, d, EdgeKind::Call);
+        graph.add_edge(a, d, EdgeKind::Call);
+        graph.add_edge(e, f, EdgeKind::Call);
+        graph.add_edge(f, e, EdgeKind::Call);
+
+        let graph_arc = Arc::new(graph);
+        let solver = CfSolver::new(Arc::clone(&graph_arc), PruningParams::academic(0.5));
+
+        // Two passes over the same scratch: stale marks from earlier starts must not leak.
+        let mut scratch = CfScratch::default();
+        for _ in 0..2 {
+            for idx in graph_arc.graph.node_indices() {
+                let expected = solver.compute_cf_total(idx);
+                let got = solver.compute_cf_total_with_scratch(idx, &mut scratch);
+                assert_eq!(got, expected);
+            }
+        }
+    }
+
     #[test]
     fn test_cached_total_matches_compute_cf_for_each_node() {
         let mut graph = ContextGraph::new();
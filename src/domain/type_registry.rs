@@ -37,6 +37,10 @@ impl TypeVarInfo {
 pub struct TypeDefAttribute {
     pub type_kind: TypeKind,
     pub is_abstract: bool,
+    /// Pure data type (dataclass/record): generated members carry no behavior.
+    pub is_data_class: bool,
+    /// Instances are immutable once constructed (frozen dataclass, record).
+    pub is_frozen: bool,
     pub type_param_count: u32,
     pub type_var_info: Option<TypeVarInfo>,
 }
@@ -130,6 +134,8 @@ mod tests {
             definition: TypeDefAttribute {
                 type_kind: TypeKind::Class,
                 is_abstract: false,
+                is_data_class: false,
+                is_frozen: false,
                 type_param_count: 0,
                 type_var_info: None,
             },
@@ -1,10 +1,14 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Parser, Subcommand, ValueEnum};
-use context_footprint::app::engine::ContextEngine;
+use context_footprint::app::dto::{NeighborDirection, PolicyKind};
+use context_footprint::app::engine::{
+    ContextEngine, DocScorerKind, LoadOptions, PolicyFile, SizeFunctionKind,
+};
 use context_footprint::cli;
+use context_footprint::domain::path_glob::PathGlob;
 use context_footprint::server;
 use std::net::SocketAddr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "cftool")]
@@ -13,14 +17,203 @@ struct Cli {
     /// Path to SemanticData JSON file
     semantic_data_path: PathBuf,
 
+    /// Report format; `json` prints the command's response object. Defaults to `text`
+    /// (`json` for `reachable`)
+    #[arg(long, value_enum, global = true)]
+    format: Option<OutputFormat>,
+
+    /// Built-in pruning policy preset used by every command
+    #[arg(long, value_enum, global = true, conflicts_with = "policy_file")]
+    policy: Option<PolicyArg>,
+
+    /// JSON or `.toml` file with custom pruning params (overrides the built-in policy presets)
+    #[arg(long, global = true)]
+    policy_file: Option<PathBuf>,
+
+    /// Documentation score a regular function needs to count as a boundary (overrides the policy)
+    #[arg(long, value_name = "SCORE", global = true)]
+    func_doc_threshold: Option<f32>,
+
+    /// Documentation score an interface/abstract method needs to count as a boundary
+    /// (overrides the policy)
+    #[arg(long, value_name = "SCORE", global = true)]
+    interface_doc_threshold: Option<f32>,
+
+    /// Treat fields of frozen types (frozen dataclass, record) as immutable: boundaries on read
+    /// (overrides the policy)
+    #[arg(long, global = true)]
+    frozen_fields_immutable: bool,
+
+    /// Exclude trailing inline comments (`x = 1  # note`) from context sizes
+    #[arg(long, global = true)]
+    strip_comments: bool,
+
+    /// Treat module-level ALL_CAPS / `settings` globals that are never written as constants
+    #[arg(long, global = true)]
+    infer_config_constants: bool,
+
+    /// Cache token counts in this file so unchanged code is not re-tokenized on the next run
+    #[arg(long, value_name = "PATH", global = true)]
+    token_cache: Option<PathBuf>,
+
+    /// How node sizes are measured; `lines` is a much faster approximation for huge codebases
+    #[arg(long, value_enum, default_value_t = SizeFnArg::Tiktoken, global = true)]
+    size_fn: SizeFnArg,
+
+    /// Characters per token for `--size-fn chars`
+    #[arg(
+        long,
+        default_value_t = 4.0,
+        global = true,
+        allow_negative_numbers = true,
+        value_parser = parse_chars_per_token
+    )]
+    chars_per_token: f32,
+
+    /// How documentation completeness is scored for boundary decisions
+    #[arg(long, value_enum, default_value_t = DocScorerArg::Heuristic, global = true)]
+    doc_scorer: DocScorerArg,
+
+    /// Ignore calls/references to symbols matching this regex (e.g. `logger\.`); repeatable
+    #[arg(long = "ignore-calls", value_name = "PATTERN", global = true)]
+    ignore_calls: Vec<String>,
+
+    /// Leave files matching this glob (e.g. `*_pb2.py`, `migrations/**`) out of the graph
+    /// entirely; repeatable, added to the policy file's `exclude` list
+    #[arg(long = "exclude-glob", value_name = "GLOB", global = true)]
+    exclude_glob: Vec<String>,
+
+    /// Read source files from this `.tar`, `.tar.gz`/`.tgz` or `.zip` (paths relative to the
+    /// project root) instead of a checkout at the SemanticData `project_root`
+    #[cfg(feature = "archive")]
+    #[arg(
+        long,
+        value_name = "ARCHIVE",
+        global = true,
+        conflicts_with = "no_source"
+    )]
+    source_archive: Option<PathBuf>,
+
+    /// Cap on the context size of external library symbols, in tokens [default: 50]
+    #[arg(long, value_name = "TOKENS", global = true)]
+    external_max_tokens: Option<u32>,
+
+    /// Truncate external symbol signatures after this many characters [default: 200]
+    #[arg(long, value_name = "CHARS", global = true)]
+    external_signature_len: Option<usize>,
+
+    /// Count the definitions of project types used as parameter types (constructor or fields)
+    /// toward a function's CF, unless the type is abstract or documented
+    #[arg(long, global = true)]
+    include_param_types: bool,
+
+    /// Treat external symbols that differ only in package version as one symbol, so indexes
+    /// built against different dependency versions merge
+    #[arg(long, global = true)]
+    normalize_symbol_versions: bool,
+
+    /// Log each solver pruning decision (node, edge kind, boundary reason) to stderr; needs a
+    /// build with the `trace` feature. `RUST_LOG` takes precedence when set
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    /// Print calls that could not be resolved to a node (per file and top callees) to stderr
+    #[arg(long, global = true)]
+    report_unresolved: bool,
+
+    /// Build even when source files are missing (e.g. an index without its checkout): their
+    /// nodes are sized 0 and the graph structure is kept
+    #[arg(long, global = true)]
+    allow_missing_sources: bool,
+
+    /// Analyze the index alone, without reading any source file: sizes are 0 and context views
+    /// show no code, but graph structure and node counts are kept
+    #[arg(long, global = true)]
+    no_source: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
-enum ReachableFormat {
-    Json,
+enum OutputFormat {
     Text,
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum PolicyArg {
+    /// Typed + documented functions are boundaries (default)
+    Academic,
+    /// Like academic, unless the function takes a high-freedom parameter (dict, str, ...)
+    Balanced,
+    /// Only abstract factories are boundaries among internal functions
+    Strict,
+}
+
+impl From<PolicyArg> for PolicyKind {
+    fn from(arg: PolicyArg) -> Self {
+        match arg {
+            PolicyArg::Academic => PolicyKind::Academic,
+            PolicyArg::Balanced => PolicyKind::Balanced,
+            PolicyArg::Strict => PolicyKind::Strict,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum SizeFnArg {
+    /// Approximate token count (default)
+    Tiktoken,
+    /// Non-blank, non-comment source lines
+    Lines,
+    /// Code characters divided by `--chars-per-token`
+    Chars,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum DocScorerArg {
+    /// Length, keywords and parameter mentions (default)
+    Heuristic,
+    /// Share of the API surface (summary, each parameter, return value) the docs describe
+    Coverage,
+}
+
+impl From<DocScorerArg> for DocScorerKind {
+    fn from(arg: DocScorerArg) -> Self {
+        match arg {
+            DocScorerArg::Heuristic => DocScorerKind::Heuristic,
+            DocScorerArg::Coverage => DocScorerKind::Coverage,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum DirectionArg {
+    /// Edges from the symbol: what it calls, reads, writes
+    Out,
+    /// Edges into the symbol: its callers, readers, writers
+    In,
+    Both,
+}
+
+impl From<DirectionArg> for NeighborDirection {
+    fn from(arg: DirectionArg) -> Self {
+        match arg {
+            DirectionArg::Out => NeighborDirection::Out,
+            DirectionArg::In => NeighborDirection::In,
+            DirectionArg::Both => NeighborDirection::Both,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum ExportFormat {
+    Mermaid,
+    /// GEXF for Gephi; without a symbol, the whole graph
+    Gexf,
+    /// Graphviz DOT (pipe into `dot -Tsvg`)
+    Dot,
 }
 
 #[derive(Subcommand)]
@@ -28,11 +221,49 @@ enum Commands {
     /// Debug: build graph from SemanticData and print graph structure as JSON
     DebugGraphData {},
 
+    /// Write the loaded SemanticData as JSON (e.g. to convert an LSIF dump once and pass the
+    /// JSON to later runs)
+    Dump {
+        /// Output file
+        out: PathBuf,
+    },
+
     /// Compute CF for specific symbols (union)
     Compute {
-        /// Symbols to analyze
-        #[arg(required = true)]
+        /// Symbols to analyze; several give the CF of their union (e.g. all HTTP handlers)
+        #[arg(required = true, num_args = 1..)]
         symbols: Vec<String>,
+        /// File listing symbols already read (one per line; blank lines and `#` comments
+        /// ignored): report only the additional context
+        #[arg(long)]
+        already_read: Option<PathBuf>,
+        /// Follow dependencies at most this many hops from the symbols
+        #[arg(long)]
+        max_depth: Option<u32>,
+        /// Also report the weighted CF, where boundaries count only their signature
+        #[arg(long)]
+        weighted: bool,
+        /// Print only aggregate numbers (no per-node listing)
+        #[arg(long)]
+        summary_only: bool,
+    },
+    /// Compute CF for the definition at a source location (innermost span containing the line)
+    ComputeAt {
+        /// File path, relative to the project root (or absolute under it)
+        file: String,
+        /// 1-based line number
+        line: u32,
+        /// Print only aggregate numbers (no per-node listing)
+        #[arg(long)]
+        summary_only: bool,
+    },
+    /// Compute the CF of a whole file: the union of everything defined in it
+    File {
+        /// File path, relative to the project root (or absolute under it)
+        relative_path: String,
+        /// Print only aggregate numbers (no per-node listing)
+        #[arg(long)]
+        summary_only: bool,
     },
     /// Check whether any target is reachable under CF traversal semantics
     Reachable {
@@ -42,9 +273,6 @@ enum Commands {
         /// One or more target symbols
         #[arg(long, required = true, num_args = 1..)]
         to: Vec<String>,
-        /// Output format
-        #[arg(long, value_enum, default_value_t = ReachableFormat::Json)]
-        format: ReachableFormat,
         /// Include witness paths for hit targets
         #[arg(long)]
         witness_paths: bool,
@@ -52,28 +280,203 @@ enum Commands {
         #[arg(long, default_value_t = 1)]
         max_paths: usize,
     },
+    /// Explain why a symbol is in another's context: the edge chain from start to target
+    #[command(alias = "why-reachable")]
+    Path {
+        /// Starting symbol
+        #[arg(long)]
+        from: String,
+        /// Symbol in the starting symbol's context
+        #[arg(long)]
+        to: String,
+    },
+    /// List the nodes one edge away from a symbol, grouped by edge kind (no CF computation)
+    Neighbors {
+        /// Symbol to inspect
+        symbol: String,
+        /// Which edges to follow
+        #[arg(long, value_enum, default_value_t = DirectionArg::Both)]
+        direction: DirectionArg,
+    },
     /// Show CF distribution statistics across all nodes
     Stats {
         /// Include test code (test_* functions and tests/ directory)
         #[arg(short, long)]
         include_tests: bool,
+        /// Also report the CF distribution of variables (globals and fields)
+        #[arg(long)]
+        include_variables: bool,
+        /// Print `distribution,statistic,tokens` CSV rows (one per percentile plus summary)
+        #[arg(long)]
+        csv: bool,
+        /// Omit the CSV header row
+        #[arg(long, requires = "csv")]
+        no_header: bool,
+        /// Report these percentiles (e.g. `50,90,95,99`) instead of 5% steps
+        #[arg(long, value_delimiter = ',')]
+        percentiles: Option<Vec<u32>>,
     },
-    /// List nodes with highest CF
-    Top {
-        /// Number of nodes to display
+    /// Show per-file union CF distribution and the heaviest files
+    FileStats {
+        /// Number of files to list
         #[arg(short, long, default_value = "10")]
         limit: usize,
-        /// Filter by node type (function, variable, or all)
+        /// Include test code (test_* functions and tests/ directory)
+        #[arg(short, long)]
+        include_tests: bool,
+    },
+    /// Summarize coupling health as a 0-100 score with a per-factor breakdown
+    Fitness {
+        /// Include test code (test_* functions and tests/ directory)
+        #[arg(short, long)]
+        include_tests: bool,
+        /// JSON file with factor weights and scales (omitted fields keep defaults)
+        #[arg(long)]
+        config: Option<PathBuf>,
+    },
+    /// Record every symbol's CF to a JSON snapshot (baseline for `check`)
+    Snapshot {
+        /// Snapshot file to write
+        #[arg(short, long)]
+        output: PathBuf,
+        /// Include test code (test_* functions and tests/ directory)
+        #[arg(short, long)]
+        include_tests: bool,
+    },
+    /// Compare current CF against a snapshot; exits non-zero on regressions
+    Check {
+        /// Snapshot written by `snapshot`
+        #[arg(long)]
+        baseline: PathBuf,
+        /// Allowed CF growth per symbol, in percent of the baseline value
+        #[arg(
+            long,
+            default_value = "0",
+            allow_negative_numbers = true,
+            value_parser = parse_tolerance
+        )]
+        tolerance: f32,
+        /// Include test code (test_* functions and tests/ directory)
+        #[arg(short, long)]
+        include_tests: bool,
+    },
+    /// Compare per-symbol CF against an older index of the same project (e.g. the PR base);
+    /// the positional SemanticData file is the new side
+    Diff {
+        /// SemanticData file of the old side; symbols are matched by symbol string
+        old: PathBuf,
+        /// Hide changes smaller than this many tokens
+        #[arg(long, default_value = "0")]
+        threshold: u32,
+        /// Include test code (test_* functions and tests/ directory)
+        #[arg(short, long)]
+        include_tests: bool,
+    },
+    /// Compute CF for the functions changed by a unified diff
+    ChangedCf {
+        /// Unified diff file (e.g. `git diff` output)
+        #[arg(long)]
+        diff: PathBuf,
+        /// Fail when a changed function's CF exceeds this many tokens
+        #[arg(long)]
+        budget: Option<u32>,
+    },
+    /// List nodes with highest CF
+    Top {
+        /// Number of nodes to display (default 10; unbounded with `--min-cf`)
+        #[arg(short, long)]
+        limit: Option<usize>,
+        /// Filter by node type (function, variable, or all; comma-separated for several)
         #[arg(short = 't', long, default_value = "function")]
         node_type: String,
+        /// Only rank nodes whose full symbol matches this regex
+        #[arg(long)]
+        filter: Option<String>,
+        /// List every node with at least this many tokens of CF
+        #[arg(long, value_name = "TOKENS")]
+        min_cf: Option<u32>,
+        /// Include test code (test_* functions and tests/ directory)
+        #[arg(short, long)]
+        include_tests: bool,
+        /// Also rank variables (globals and fields) alongside the node-type filter
+        #[arg(long)]
+        include_variables: bool,
+        /// Print only aggregate numbers (no per-node listing)
+        #[arg(long)]
+        summary_only: bool,
+        /// Print `rank,node_type,symbol,cf_tokens` CSV rows
+        #[arg(long)]
+        csv: bool,
+        /// Omit the CSV header row
+        #[arg(long, requires = "csv")]
+        no_header: bool,
+    },
+    /// List the functions above a CF percentile and their share of the total CF
+    Pareto {
+        /// Percentile cutoff (multiple of 5); functions strictly above it are listed
+        #[arg(long, default_value = "95")]
+        percentile: u32,
+        /// Include test code (test_* functions and tests/ directory)
+        #[arg(short, long)]
+        include_tests: bool,
+    },
+    /// List nodes that appear in the most other nodes' reachable sets
+    Ubiquity {
+        /// Number of nodes to display
+        #[arg(long, default_value = "10")]
+        top: usize,
+        /// Include test code (test_* functions and tests/ directory)
+        #[arg(short, long)]
+        include_tests: bool,
+    },
+    /// Report the share of nodes reachable from entry points, flagging likely-dead modules
+    Coverage {
+        /// Regex selecting entry point symbols (e.g. `main\(\)|views/`)
+        #[arg(long)]
+        entrypoints: String,
+        /// Count test code in the denominator
+        #[arg(short, long)]
+        include_tests: bool,
+        /// Flag modules whose unreachable ratio is at least this (0.0-1.0)
+        #[arg(long, default_value = "0.8")]
+        dead_threshold: f64,
+    },
+    /// Emit an N×N reachability matrix (CSV) for a list of symbols
+    Matrix {
+        /// File with one symbol per line
+        #[arg(long)]
+        symbols: PathBuf,
+        /// Use the target's context size instead of 1 for reachable entries
+        #[arg(long)]
+        weighted: bool,
+    },
+    /// Split the context of two symbol sets into A-only, B-only and shared nodes
+    SetDiff {
+        /// File with the symbols of set A, one per line
+        #[arg(long)]
+        set_a: PathBuf,
+        /// File with the symbols of set B, one per line
+        #[arg(long)]
+        set_b: PathBuf,
+    },
+    /// Report groups of functions with identical normalized bodies
+    Duplicates {
+        /// Ignore functions smaller than this many tokens
+        #[arg(long, default_value = "20")]
+        min_size: u32,
         /// Include test code (test_* functions and tests/ directory)
         #[arg(short, long)]
         include_tests: bool,
     },
+    /// List call cycles (mutually recursive functions) and self-recursive functions
+    Cycles {},
     /// Search for symbols by keyword
     Search {
         /// Keyword to search for in symbol names
         pattern: String,
+        /// Treat the pattern as a regex matched against the full symbol
+        #[arg(long)]
+        regex: bool,
         /// Show CF for each result
         #[arg(short, long)]
         with_cf: bool,
@@ -94,9 +497,44 @@ enum Commands {
         /// Print traversal node list with edge kind and boundary/transparent decision
         #[arg(long)]
         show_traversal: bool,
+        /// Render the reachable set as an indented dependency tree (edge kind, symbol, size)
+        #[arg(long)]
+        tree: bool,
+        /// Print traversal steps with all pruning inputs as JSON (for external tooling)
+        #[arg(long)]
+        explain_json: bool,
         /// Max tokens to include in output
         #[arg(short, long)]
         max_tokens: Option<u32>,
+        /// Follow dependencies at most this many hops from the symbol
+        #[arg(long)]
+        max_depth: Option<u32>,
+        /// Stop reaching into new files once this many distinct files are covered
+        #[arg(long)]
+        max_files: Option<usize>,
+        /// Also list callers up to this many Call hops upstream (not counted in CF)
+        #[arg(long, default_value = "0")]
+        with_callers: u32,
+        /// Print only aggregate numbers (no per-node listing)
+        #[arg(long)]
+        summary_only: bool,
+        /// Print only the files the context spans, with node counts and summed size
+        #[arg(long)]
+        files_only: bool,
+    },
+    /// Export the reachable set of a symbol as a diagram (e.g. Mermaid for Markdown docs)
+    Export {
+        /// Symbol to analyze (GEXF exports the whole graph when omitted)
+        symbol: Option<String>,
+        /// Diagram format
+        #[arg(long, value_enum, default_value_t = ExportFormat::Mermaid)]
+        diagram: ExportFormat,
+        /// Max tokens of reachable context to include
+        #[arg(short, long)]
+        max_tokens: Option<u32>,
+        /// Only include nodes up to this many BFS layers from the symbol (DOT)
+        #[arg(long)]
+        depth: Option<u32>,
     },
     /// Start an HTTP server for repeated queries
     Serve {
@@ -113,27 +551,73 @@ enum Commands {
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    // Logs go to stderr so they never mix with JSON/CSV on stdout
+    let default_filter = if cli.verbose {
+        "info,context_footprint::domain::solver=trace"
+    } else {
+        "info"
+    };
     tracing_subscriber::fmt()
         .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()),
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| default_filter.into()),
         )
+        .with_writer(std::io::stderr)
         .init();
 
-    let cli = Cli::parse();
     let json_path = &cli.semantic_data_path;
 
-    if let Commands::DebugGraphData {} = &cli.command {
-        return cli::debug_graph_data(json_path);
+    match &cli.command {
+        Commands::DebugGraphData {} => return cli::debug_graph_data(json_path),
+        Commands::Dump { out } => return cli::dump_semantic_data(json_path, out),
+        _ => {}
     }
 
-    let engine = ContextEngine::load_from_json(json_path)?;
-    let quiet_output = matches!(
-        &cli.command,
-        Commands::Reachable {
-            format: ReachableFormat::Json,
-            ..
-        }
-    );
+    let engine = load_engine(&cli, json_path)?;
+    if cli.report_unresolved {
+        cli::report_unresolved(&engine, 20);
+    }
+    let json_output = cli.format == Some(OutputFormat::Json);
+    let reachable_json = cli.format != Some(OutputFormat::Text);
+    let quiet_output = json_output
+        || matches!(&cli.command, Commands::Reachable { .. }) && reachable_json
+        || matches!(
+            &cli.command,
+            Commands::Matrix { .. }
+                | Commands::Stats { csv: true, .. }
+                | Commands::Top { csv: true, .. }
+                | Commands::Export { .. }
+                | Commands::Compute {
+                    summary_only: true,
+                    ..
+                }
+                | Commands::ComputeAt {
+                    summary_only: true,
+                    ..
+                }
+                | Commands::File {
+                    summary_only: true,
+                    ..
+                }
+                | Commands::Top {
+                    summary_only: true,
+                    ..
+                }
+                | Commands::Context {
+                    summary_only: true,
+                    ..
+                }
+                | Commands::Context {
+                    explain_json: true,
+                    ..
+                }
+                | Commands::Context {
+                    files_only: true,
+                    ..
+                }
+        );
 
     if !quiet_output {
         println!("Loading SemanticData from {}...", json_path.display());
@@ -145,14 +629,44 @@ async fn main() -> Result<()> {
     }
 
     match &cli.command {
-        Commands::DebugGraphData {} => unreachable!(),
-        Commands::Compute { symbols } => {
-            cli::compute_cf_for_symbols(&engine, symbols)?;
+        Commands::DebugGraphData {} | Commands::Dump { .. } => unreachable!(),
+        Commands::Compute {
+            symbols,
+            already_read,
+            max_depth,
+            weighted,
+            summary_only,
+        } => {
+            let already_read = match already_read {
+                Some(path) => cli::read_symbols_file(path)?,
+                None => Vec::new(),
+            };
+            cli::compute_cf_for_symbols(
+                &engine,
+                symbols,
+                &already_read,
+                *max_depth,
+                *weighted,
+                *summary_only,
+                json_output,
+            )?;
+        }
+        Commands::ComputeAt {
+            file,
+            line,
+            summary_only,
+        } => {
+            cli::compute_cf_at(&engine, file, *line, *summary_only, json_output)?;
+        }
+        Commands::File {
+            relative_path,
+            summary_only,
+        } => {
+            cli::compute_cf_for_file(&engine, relative_path, *summary_only, json_output)?;
         }
         Commands::Reachable {
             from,
             to,
-            format,
             witness_paths,
             max_paths,
         } => {
@@ -160,43 +674,260 @@ async fn main() -> Result<()> {
                 &engine,
                 from,
                 to,
-                matches!(format, ReachableFormat::Json),
+                reachable_json,
                 *witness_paths,
                 *max_paths,
             )?;
         }
-        Commands::Stats { include_tests } => {
-            cli::compute_and_display_cf_stats(&engine, *include_tests)?;
+        Commands::Stats {
+            include_tests,
+            include_variables,
+            csv: true,
+            no_header,
+            percentiles,
+        } => {
+            cli::display_stats_csv(
+                &engine,
+                *include_tests,
+                *include_variables,
+                percentiles.as_deref(),
+                !*no_header,
+            )?;
+        }
+        Commands::Stats {
+            include_tests,
+            include_variables,
+            percentiles,
+            ..
+        } => {
+            cli::compute_and_display_cf_stats(
+                &engine,
+                *include_tests,
+                *include_variables,
+                percentiles.as_deref(),
+                json_output,
+            )?;
+        }
+        Commands::FileStats {
+            limit,
+            include_tests,
+        } => {
+            cli::display_file_stats(&engine, *limit, *include_tests, json_output)?;
+        }
+        Commands::Fitness {
+            include_tests,
+            config,
+        } => {
+            cli::display_fitness(&engine, *include_tests, config.as_deref(), json_output)?;
+        }
+        Commands::Snapshot {
+            output,
+            include_tests,
+        } => {
+            cli::write_snapshot(&engine, output, *include_tests)?;
+        }
+        Commands::Check {
+            baseline,
+            tolerance,
+            include_tests,
+        } => {
+            cli::check_against_baseline(
+                &engine,
+                baseline,
+                *tolerance,
+                *include_tests,
+                json_output,
+            )?;
+        }
+        Commands::Diff {
+            old,
+            threshold,
+            include_tests,
+        } => {
+            let old_engine = load_engine(&cli, old)?;
+            cli::display_cf_diff(
+                &old_engine,
+                &engine,
+                *threshold,
+                *include_tests,
+                json_output,
+            )?;
+        }
+        Commands::ChangedCf { diff, budget } => {
+            cli::display_changed_cf(&engine, diff, *budget, json_output)?;
         }
         Commands::Top {
             limit,
             node_type,
+            filter,
+            min_cf,
+            include_tests,
+            include_variables,
+            csv: true,
+            no_header,
+            ..
+        } => {
+            cli::display_top_csv(
+                &engine,
+                *limit,
+                node_type,
+                filter.as_deref(),
+                *min_cf,
+                *include_tests,
+                *include_variables,
+                !*no_header,
+            )?;
+        }
+        Commands::Top {
+            limit,
+            node_type,
+            filter,
+            min_cf,
+            include_tests,
+            include_variables,
+            summary_only,
+            ..
+        } => {
+            cli::display_top_cf_nodes(
+                &engine,
+                *limit,
+                node_type,
+                filter.as_deref(),
+                *min_cf,
+                *include_tests,
+                *include_variables,
+                *summary_only,
+                json_output,
+            )?;
+        }
+        Commands::Pareto {
+            percentile,
+            include_tests,
+        } => {
+            cli::display_pareto(&engine, *percentile, *include_tests, json_output)?;
+        }
+        Commands::Ubiquity { top, include_tests } => {
+            cli::display_ubiquity(&engine, *top, *include_tests, json_output)?;
+        }
+        Commands::Coverage {
+            entrypoints,
             include_tests,
+            dead_threshold,
         } => {
-            cli::display_top_cf_nodes(&engine, *limit, node_type, *include_tests)?;
+            cli::display_coverage(
+                &engine,
+                entrypoints,
+                *include_tests,
+                *dead_threshold,
+                json_output,
+            )?;
+        }
+        Commands::Matrix { symbols, weighted } => {
+            cli::display_matrix_csv(&engine, symbols, *weighted)?;
+        }
+        Commands::Path { from, to } => {
+            cli::display_path(&engine, from, to, json_output)?;
+        }
+        Commands::Neighbors { symbol, direction } => {
+            cli::display_neighbors(&engine, symbol, (*direction).into(), json_output)?;
+        }
+        Commands::SetDiff { set_a, set_b } => {
+            cli::display_set_diff(&engine, set_a, set_b, json_output)?;
+        }
+        Commands::Duplicates {
+            min_size,
+            include_tests,
+        } => {
+            cli::display_duplicates(&engine, *min_size, *include_tests, json_output)?;
+        }
+        Commands::Cycles {} => {
+            cli::display_cycles(&engine, json_output)?;
         }
         Commands::Search {
             pattern,
+            regex,
             with_cf,
             limit,
             include_tests,
         } => {
-            cli::search_symbols(&engine, pattern, *with_cf, *limit, *include_tests)?;
+            cli::search_symbols(
+                &engine,
+                pattern,
+                *regex,
+                *with_cf,
+                *limit,
+                *include_tests,
+                json_output,
+            )?;
         }
         Commands::Context {
             symbol,
-            show_boundaries,
+            show_boundaries: _,
             show_traversal,
+            tree,
+            explain_json,
             max_tokens,
+            max_depth,
+            max_files,
+            with_callers,
+            summary_only,
+            files_only,
         } => {
+            if *files_only {
+                return cli::display_context_files(
+                    &engine,
+                    symbol,
+                    *max_tokens,
+                    *max_depth,
+                    *max_files,
+                    json_output,
+                );
+            }
+            if *summary_only {
+                return cli::display_context_summary(
+                    &engine,
+                    symbol,
+                    *max_tokens,
+                    *max_depth,
+                    *max_files,
+                    json_output,
+                );
+            }
+            if *explain_json {
+                return cli::display_context_explain_json(&engine, symbol, *max_tokens, *max_depth);
+            }
             cli::display_context_code(
                 &engine,
                 symbol,
-                *show_boundaries,
                 *show_traversal,
+                *tree,
                 *max_tokens,
+                *max_depth,
+                *max_files,
+                *with_callers,
+                json_output,
             )?;
         }
+        Commands::Export {
+            symbol,
+            diagram,
+            max_tokens,
+            depth,
+        } => match diagram {
+            ExportFormat::Mermaid => {
+                let symbol = symbol
+                    .as_deref()
+                    .ok_or_else(|| anyhow::anyhow!("Mermaid export requires a symbol"))?;
+                cli::display_context_mermaid(&engine, symbol, *max_tokens)?
+            }
+            ExportFormat::Gexf => cli::display_graph_gexf(&engine, symbol.as_deref())?,
+            ExportFormat::Dot => {
+                let symbol = symbol
+                    .as_deref()
+                    .ok_or_else(|| anyhow::anyhow!("DOT export requires a symbol"))?;
+                cli::display_context_dot(&engine, symbol, *max_tokens, *depth)?
+            }
+        },
         Commands::Serve { host, port } => {
             let addr: SocketAddr = format!("{host}:{port}")
                 .parse()
@@ -212,3 +943,102 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// `--chars-per-token`: a finite ratio above zero.
+fn parse_chars_per_token(value: &str) -> Result<f32, String> {
+    let ratio: f32 = value.parse().map_err(|e| format!("{e}"))?;
+    if ratio.is_finite() && ratio > 0.0 {
+        Ok(ratio)
+    } else {
+        Err(format!("must be a positive number, got {value}"))
+    }
+}
+
+/// `check --tolerance`: a finite, non-negative percentage.
+fn parse_tolerance(value: &str) -> Result<f32, String> {
+    let percent: f32 = value.parse().map_err(|e| format!("{e}"))?;
+    if percent.is_finite() && percent >= 0.0 {
+        Ok(percent)
+    } else {
+        Err(format!("must be a non-negative number, got {value}"))
+    }
+}
+
+/// Load the SemanticData at `path` with the global loading and policy options applied.
+fn load_engine(cli: &Cli, path: &Path) -> Result<ContextEngine> {
+    let policy_file = cli
+        .policy_file
+        .as_deref()
+        .map(PolicyFile::load)
+        .transpose()?;
+    let mut exclude = policy_file
+        .as_ref()
+        .map(|policy| policy.exclude.clone())
+        .unwrap_or_default();
+    for pattern in &cli.exclude_glob {
+        exclude.push(
+            PathGlob::new(pattern).with_context(|| format!("Invalid --exclude-glob: {pattern}"))?,
+        );
+    }
+    let engine = ContextEngine::load_from_json_with_options(
+        path,
+        LoadOptions {
+            strip_comments: cli.strip_comments,
+            infer_config_constants: cli.infer_config_constants,
+            token_cache: cli.token_cache.clone(),
+            size_function: match cli.size_fn {
+                SizeFnArg::Tiktoken => SizeFunctionKind::Tiktoken,
+                SizeFnArg::Lines => SizeFunctionKind::Lines,
+                SizeFnArg::Chars => SizeFunctionKind::Chars {
+                    chars_per_token: cli.chars_per_token,
+                },
+            },
+            doc_scorer: cli.doc_scorer.into(),
+            exclude,
+            #[cfg(feature = "archive")]
+            source_archive: cli.source_archive.clone(),
+            allow_missing_sources: cli.allow_missing_sources,
+            no_source: cli.no_source,
+            external_max_tokens: cli.external_max_tokens,
+            external_signature_len: cli.external_signature_len,
+            include_param_types: cli.include_param_types,
+            normalize_symbol_versions: cli.normalize_symbol_versions,
+            signature_sizes: policy_file
+                .as_ref()
+                .is_some_and(|policy| policy.params.boundary_counts_signature_only),
+        },
+    )?;
+    let mut policy_override = match (policy_file, cli.policy) {
+        (Some(policy_file), _) => Some(policy_file.params),
+        (None, Some(policy)) => Some(ContextEngine::policy_preset(policy.into())),
+        (None, None) => None,
+    };
+    if cli.func_doc_threshold.is_some()
+        || cli.interface_doc_threshold.is_some()
+        || cli.frozen_fields_immutable
+    {
+        let params = policy_override
+            .get_or_insert_with(|| ContextEngine::policy_preset(PolicyKind::Academic));
+        if let Some(threshold) = cli.func_doc_threshold {
+            params.func_doc_threshold = threshold;
+        }
+        if let Some(threshold) = cli.interface_doc_threshold {
+            params.interface_doc_threshold = threshold;
+        }
+        if cli.frozen_fields_immutable {
+            params.treat_frozen_type_fields_as_immutable = true;
+        }
+    }
+    if policy_override.is_some() {
+        engine.set_policy_override(policy_override);
+    }
+    if !cli.ignore_calls.is_empty() {
+        let patterns = cli
+            .ignore_calls
+            .iter()
+            .map(|p| regex::Regex::new(p).with_context(|| format!("Invalid --ignore-calls: {p}")))
+            .collect::<Result<Vec<_>>>()?;
+        engine.set_excluded_edge_targets(patterns);
+    }
+    Ok(engine)
+}
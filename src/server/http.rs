@@ -11,6 +11,7 @@ use axum::{
 use serde::Deserialize;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::task::spawn_blocking;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 
@@ -24,15 +25,25 @@ struct StatsQuery {
     #[serde(default)]
     include_tests: bool,
     #[serde(default)]
+    include_variables: bool,
+    #[serde(default)]
     policy: Option<PolicyKind>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+struct DeepHealthQuery {
+    #[serde(default = "default_sample")]
+    sample: usize,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 struct TopQuery {
     #[serde(default = "default_limit")]
     limit: usize,
     #[serde(default = "default_node_type")]
     node_type: String,
+    filter: Option<String>,
+    min_cf: Option<u32>,
     #[serde(default)]
     include_tests: bool,
     #[serde(default)]
@@ -43,6 +54,8 @@ struct TopQuery {
 struct SearchQuery {
     pattern: String,
     #[serde(default)]
+    regex: bool,
+    #[serde(default)]
     with_cf: bool,
     limit: Option<usize>,
     #[serde(default)]
@@ -51,6 +64,37 @@ struct SearchQuery {
     policy: Option<PolicyKind>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+struct NeighborsQuery {
+    symbol: String,
+    #[serde(default)]
+    direction: NeighborDirection,
+}
+
+/// Sets the flag when dropped. Held by a handler across its blocking computation: if the
+/// client disconnects, axum drops the handler future and the computation is cancelled.
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl CancelOnDrop {
+    fn new() -> Self {
+        Self(Arc::new(AtomicBool::new(false)))
+    }
+
+    fn flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.0)
+    }
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+fn default_sample() -> usize {
+    5
+}
+
 fn default_limit() -> usize {
     10
 }
@@ -73,10 +117,12 @@ pub fn build_router(engine: ContextEngine) -> Router {
 
     Router::new()
         .route("/health", get(health))
+        .route("/health/deep", get(deep_health))
         .route("/compute", post(compute))
         .route("/stats", get(stats))
         .route("/top", get(top))
         .route("/search", get(search))
+        .route("/neighbors", get(neighbors))
         .route("/context", post(context))
         .route("/reload", post(reload))
         .with_state(state)
@@ -96,6 +142,21 @@ async fn health(State(state): State<Arc<HttpState>>) -> impl IntoResponse {
     Json(state.engine.health())
 }
 
+async fn deep_health(
+    State(state): State<Arc<HttpState>>,
+    Query(q): Query<DeepHealthQuery>,
+) -> impl IntoResponse {
+    let engine = state.engine.clone();
+    match spawn_blocking(move || engine.deep_health(q.sample)).await {
+        Ok(res) => Json(res).into_response(),
+        Err(e) => api_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("task join error: {e}"),
+        )
+        .into_response(),
+    }
+}
+
 async fn reload(State(state): State<Arc<HttpState>>) -> impl IntoResponse {
     let engine = state.engine.clone();
     match spawn_blocking(move || engine.reload()).await {
@@ -114,7 +175,9 @@ async fn compute(
     Json(req): Json<ComputeRequest>,
 ) -> impl IntoResponse {
     let engine = state.engine.clone();
-    match spawn_blocking(move || engine.compute(req)).await {
+    let cancel = CancelOnDrop::new();
+    let flag = cancel.flag();
+    match spawn_blocking(move || engine.compute_cancellable(req, flag)).await {
         Ok(Ok(res)) => Json(res).into_response(),
         Ok(Err(e)) => api_error(StatusCode::BAD_REQUEST, e.to_string()).into_response(),
         Err(e) => api_error(
@@ -147,7 +210,13 @@ async fn stats(
 ) -> impl IntoResponse {
     let engine = state.engine.clone();
     let policy = q.policy.unwrap_or_default();
-    match spawn_blocking(move || engine.stats(q.include_tests, policy)).await {
+    let cancel = CancelOnDrop::new();
+    let flag = cancel.flag();
+    match spawn_blocking(move || {
+        engine.stats_cancellable(q.include_tests, q.include_variables, policy, None, flag)
+    })
+    .await
+    {
         Ok(Ok(res)) => Json(res).into_response(),
         Ok(Err(e)) => api_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
         Err(e) => api_error(
@@ -163,7 +232,18 @@ async fn top(State(state): State<Arc<HttpState>>, Query(q): Query<TopQuery>) ->
     let node_type = q.node_type.clone();
     let policy = q.policy.unwrap_or_default();
 
-    match spawn_blocking(move || engine.top(q.limit, &node_type, q.include_tests, policy)).await {
+    match spawn_blocking(move || {
+        engine.top(
+            q.limit,
+            &node_type,
+            q.filter.as_deref(),
+            q.min_cf,
+            q.include_tests,
+            policy,
+        )
+    })
+    .await
+    {
         Ok(Ok(res)) => Json(res).into_response(),
         Ok(Err(e)) => api_error(StatusCode::BAD_REQUEST, e.to_string()).into_response(),
         Err(e) => api_error(
@@ -182,7 +262,14 @@ async fn search(
     let policy = q.policy.unwrap_or_default();
 
     match spawn_blocking(move || {
-        engine.search(&q.pattern, q.with_cf, q.limit, q.include_tests, policy)
+        engine.search(
+            &q.pattern,
+            q.regex,
+            q.with_cf,
+            q.limit,
+            q.include_tests,
+            policy,
+        )
     })
     .await
     {
@@ -196,6 +283,23 @@ async fn search(
     }
 }
 
+async fn neighbors(
+    State(state): State<Arc<HttpState>>,
+    Query(q): Query<NeighborsQuery>,
+) -> impl IntoResponse {
+    let engine = state.engine.clone();
+
+    match spawn_blocking(move || engine.neighbors(&q.symbol, q.direction)).await {
+        Ok(Ok(res)) => Json(res).into_response(),
+        Ok(Err(e)) => api_error(StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => api_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("task join error: {e}"),
+        )
+        .into_response(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -260,6 +364,93 @@ mod tests {
         g
     }
 
+    async fn get_json(app: &Router, uri: &str) -> serde_json::Value {
+        let res = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(res.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_http_deep_health_reports_unreadable_sources() {
+        use crate::adapters::fs::reader::FileSourceReader;
+
+        let engine = ContextEngine::from_prebuilt(
+            "semantic_data.json".into(),
+            "/nonexistent/project/root".into(),
+            make_graph(),
+            Arc::new(FileSourceReader::new()),
+        );
+        let app = build_router(engine);
+
+        let health = get_json(&app, "/health").await;
+        assert_eq!(health["node_count"], 1);
+
+        let deep = get_json(&app, "/health/deep").await;
+        assert_eq!(deep["node_count"], 1);
+        assert_eq!(deep["sampled_nodes"], 1);
+        assert_eq!(deep["readable_nodes"], 0);
+        assert_eq!(deep["failures"][0]["file_path"], "app.py");
+
+        // Same graph with a working reader
+        let engine = ContextEngine::from_prebuilt(
+            "semantic_data.json".into(),
+            "/repo".into(),
+            make_graph(),
+            Arc::new(MockReader),
+        );
+        let deep = get_json(&build_router(engine), "/health/deep?sample=3").await;
+        assert_eq!(deep["readable_nodes"], 1);
+        assert_eq!(deep["failures"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_http_neighbors_lists_edges_with_direction() {
+        let mut graph = make_graph();
+        let f = graph.get_node_by_symbol("sym/f().").unwrap();
+        let Node::Function(mut g) = graph.node(f).clone() else {
+            unreachable!()
+        };
+        g.core.id = 1;
+        let g = graph.add_node("sym/g().".into(), Node::Function(g));
+        graph.add_edge(f, g, EdgeKind::Call);
+        let engine = ContextEngine::from_prebuilt(
+            "semantic_data.json".into(),
+            "/repo".into(),
+            graph,
+            Arc::new(MockReader),
+        );
+        let app = build_router(engine);
+
+        let out = get_json(&app, "/neighbors?symbol=sym/g().&direction=out").await;
+        assert_eq!(out["node"]["symbol"], "sym/g().");
+        assert_eq!(out["neighbors"], serde_json::json!([]));
+
+        let both = get_json(&app, "/neighbors?symbol=sym/g().").await;
+        let neighbors = both["neighbors"].as_array().unwrap();
+        assert_eq!(neighbors.len(), 1);
+        assert_eq!(neighbors[0]["node"]["symbol"], "sym/f().");
+        assert_eq!(neighbors[0]["edge_kind"], "Call");
+        assert_eq!(neighbors[0]["direction"], "in");
+
+        let res = app
+            .oneshot(
+                Request::builder()
+                    .uri("/neighbors?symbol=sym/missing")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn test_http_health_and_compute() {
         let engine = ContextEngine::from_prebuilt(
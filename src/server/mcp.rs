@@ -51,11 +51,17 @@ impl CfMcpServer {
     ) -> Result<Json<StatsResponse>, String> {
         let engine = self.engine.clone();
         let p = params.0;
-        spawn_blocking(move || engine.stats(p.include_tests, p.policy.unwrap_or_default()))
-            .await
-            .map_err(|e| format!("task join error: {e}"))?
-            .map(Json)
-            .map_err(|e| e.to_string())
+        spawn_blocking(move || {
+            engine.stats(
+                p.include_tests,
+                p.include_variables,
+                p.policy.unwrap_or_default(),
+            )
+        })
+        .await
+        .map_err(|e| format!("task join error: {e}"))?
+        .map(Json)
+        .map_err(|e| e.to_string())
     }
 
     #[tool(description = "List nodes with highest CF.")]
@@ -64,9 +70,12 @@ impl CfMcpServer {
         let p = params.0;
         let node_type = p.node_type.unwrap_or_else(|| "all".to_string());
         spawn_blocking(move || {
+            let default_limit = if p.min_cf.is_some() { usize::MAX } else { 10 };
             engine.top(
-                p.limit.unwrap_or(10),
+                p.limit.unwrap_or(default_limit),
                 &node_type,
+                p.filter.as_deref(),
+                p.min_cf,
                 p.include_tests,
                 p.policy.unwrap_or_default(),
             )
@@ -87,6 +96,7 @@ impl CfMcpServer {
         spawn_blocking(move || {
             engine.search(
                 &p.pattern,
+                p.regex,
                 p.with_cf,
                 p.limit,
                 p.include_tests,
@@ -120,6 +130,9 @@ impl CfMcpServer {
 pub struct CfStatsParams {
     #[serde(default)]
     pub include_tests: bool,
+    /// Also report the CF distribution of variables (globals and fields).
+    #[serde(default)]
+    pub include_variables: bool,
     pub policy: Option<PolicyKind>,
 }
 
@@ -127,6 +140,10 @@ pub struct CfStatsParams {
 pub struct TopParams {
     pub limit: Option<usize>,
     pub node_type: Option<String>, // all|function|type|variable
+    /// Regex on the full symbol restricting which nodes are ranked
+    pub filter: Option<String>,
+    /// List every node at or above this CF; `limit` then defaults to unbounded
+    pub min_cf: Option<u32>,
     #[serde(default)]
     pub include_tests: bool,
     pub policy: Option<PolicyKind>,
@@ -135,6 +152,9 @@ pub struct TopParams {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, rmcp::schemars::JsonSchema)]
 pub struct SearchParams {
     pub pattern: String,
+    /// Match `pattern` as a regex against the full symbol instead of as a substring
+    #[serde(default)]
+    pub regex: bool,
     #[serde(default)]
     pub with_cf: bool,
     pub limit: Option<usize>,
@@ -230,6 +250,10 @@ mod tests {
                 symbols: vec!["sym/f().".into()],
                 policy: PolicyKind::Academic,
                 max_tokens: None,
+                max_depth: None,
+                func_doc_threshold: None,
+                interface_doc_threshold: None,
+                already_read: Vec::new(),
             }))
             .await
             .unwrap()
@@ -240,6 +264,7 @@ mod tests {
         let _stats = server
             .cf_stats(Parameters(CfStatsParams {
                 include_tests: true,
+                include_variables: false,
                 policy: Some(PolicyKind::Academic),
             }))
             .await
@@ -250,6 +275,8 @@ mod tests {
             .top_cf(Parameters(TopParams {
                 limit: Some(10),
                 node_type: Some("all".into()),
+                filter: None,
+                min_cf: None,
                 include_tests: true,
                 policy: Some(PolicyKind::Academic),
             }))
@@ -260,6 +287,7 @@ mod tests {
         let _search = server
             .search_symbols(Parameters(SearchParams {
                 pattern: "sym".into(),
+                regex: false,
                 with_cf: true,
                 limit: None,
                 include_tests: true,
@@ -274,8 +302,11 @@ mod tests {
                 symbol: "sym/f().".into(),
                 policy: PolicyKind::Academic,
                 max_tokens: None,
+                max_depth: None,
                 include_code: false,
                 show_traversal: false,
+                with_callers: 0,
+                max_files: None,
             }))
             .await
             .unwrap()
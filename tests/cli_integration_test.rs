@@ -61,6 +61,7 @@ fn write_reachable_fixture() -> (TempDir, std::path::PathBuf) {
                     },
                     enclosing_symbol: None,
                     is_external: false,
+                    is_generated: false,
                     documentation: vec!["Doc for A".to_string()],
                     details: SymbolDetails::Function(FunctionDetails {
                         parameters: vec![Parameter {
@@ -72,6 +73,7 @@ fn write_reachable_fixture() -> (TempDir, std::path::PathBuf) {
                         }],
                         return_types: vec!["int".to_string()],
                         type_params: vec![],
+                        throws: vec![],
                         modifiers: FunctionModifiers {
                             visibility: Visibility::Public,
                             ..Default::default()
@@ -96,11 +98,13 @@ fn write_reachable_fixture() -> (TempDir, std::path::PathBuf) {
                     },
                     enclosing_symbol: None,
                     is_external: false,
+                    is_generated: false,
                     documentation: vec![],
                     details: SymbolDetails::Function(FunctionDetails {
                         parameters: vec![],
                         return_types: vec!["int".to_string()],
                         type_params: vec![],
+                        throws: vec![],
                         modifiers: FunctionModifiers {
                             visibility: Visibility::Public,
                             ..Default::default()
@@ -155,6 +159,48 @@ fn test_cli_help_succeeds() {
     assert!(stdout.contains("Compute") || stdout.contains("compute"));
 }
 
+#[test]
+fn test_cli_rejects_non_positive_chars_per_token() {
+    let Some(bin) = bin() else {
+        eprintln!("Skipping CLI test: CARGO_BIN_EXE not set");
+        return;
+    };
+    for ratio in ["0", "-2", "NaN", "inf"] {
+        let out = Command::new(&bin)
+            .args([
+                "data.json",
+                "--size-fn",
+                "chars",
+                "--chars-per-token",
+                ratio,
+            ])
+            .args(["stats"])
+            .output()
+            .expect("run stats");
+        assert!(!out.status.success());
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        assert!(stderr.contains("--chars-per-token"), "stderr: {stderr}");
+    }
+}
+
+#[test]
+fn test_cli_rejects_negative_tolerance() {
+    let Some(bin) = bin() else {
+        eprintln!("Skipping CLI test: CARGO_BIN_EXE not set");
+        return;
+    };
+    for tolerance in ["-5", "NaN", "inf"] {
+        let out = Command::new(&bin)
+            .args(["data.json", "check", "--baseline", "base.json"])
+            .args(["--tolerance", tolerance])
+            .output()
+            .expect("run check");
+        assert!(!out.status.success());
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        assert!(stderr.contains("--tolerance"), "stderr: {stderr}");
+    }
+}
+
 #[test]
 fn test_cli_load_error_when_data_missing() {
     let Some(bin) = bin() else {
@@ -359,3 +405,196 @@ fn test_cli_reachable_json_reports_hits_and_unresolved_symbols() {
         serde_json::json!([["sym::func_a", "sym::func_b"]])
     );
 }
+
+#[test]
+fn test_cli_no_source_builds_without_checkout() {
+    let Some(bin) = bin() else {
+        eprintln!("Skipping CLI test: CARGO_BIN_EXE not set");
+        return;
+    };
+
+    let (tempdir, json_path) = write_reachable_fixture();
+    std::fs::remove_file(tempdir.path().join("repo").join("main.py")).expect("remove source");
+    let json_path_str = json_path.to_string_lossy().to_string();
+    let reachable = |extra: &[&str]| {
+        Command::new(&bin)
+            .args([json_path_str.as_str(), "reachable", "--from", "sym::func_a"])
+            .args(["--to", "sym::func_b"])
+            .args(extra)
+            .output()
+            .expect("run reachable")
+    };
+
+    assert!(!reachable(&[]).status.success());
+    let out = reachable(&["--no-source"]);
+    assert!(
+        out.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let json: serde_json::Value =
+        serde_json::from_slice(&out.stdout).expect("reachable JSON output");
+    assert_eq!(json["reachable"], true);
+}
+
+#[test]
+fn test_cli_summary_only_prints_totals_without_symbol_lines() {
+    let Some(bin) = bin() else {
+        eprintln!("Skipping CLI test: CARGO_BIN_EXE not set");
+        return;
+    };
+
+    let (_tempdir, json_path) = write_reachable_fixture();
+    let json_path_str = json_path.to_string_lossy().to_string();
+    let run = |args: &[&str]| {
+        let out = Command::new(&bin)
+            .arg(json_path_str.as_str())
+            .args(args)
+            .output()
+            .expect("run cli");
+        assert!(
+            out.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        String::from_utf8_lossy(&out.stdout).to_string()
+    };
+
+    let full = run(&["context", "sym::func_a"]);
+    assert!(full.contains("Symbol: "));
+
+    let summary = run(&["context", "sym::func_a", "--summary-only"]);
+    assert!(summary.contains("Total size: "));
+    assert!(summary.contains("Boundaries: "));
+    assert!(!summary.contains("Symbol: "));
+    assert!(!summary.contains("sym::func_b"));
+
+    let summary = run(&["compute", "sym::func_a", "--summary-only"]);
+    assert!(summary.contains("Total context size: "));
+    assert!(!summary.contains("sym::func_a"));
+
+    let summary = run(&["top", "--summary-only"]);
+    assert!(summary.contains("Highest CF: "));
+    assert!(!summary.contains("sym::func_"));
+}
+
+#[test]
+fn test_cli_matrix_streams_one_row_per_symbol() {
+    let Some(bin) = bin() else {
+        eprintln!("Skipping CLI test: CARGO_BIN_EXE not set");
+        return;
+    };
+
+    let (tempdir, json_path) = write_reachable_fixture();
+    let symbols_path = tempdir.path().join("symbols.txt");
+    std::fs::write(&symbols_path, "sym::func_a\n# comment\nsym::func_b\n").expect("write symbols");
+    let out = Command::new(&bin)
+        .arg(json_path.as_os_str())
+        .args(["matrix", "--symbols"])
+        .arg(symbols_path.as_os_str())
+        .output()
+        .expect("run matrix");
+    assert!(
+        out.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], ",sym::func_a,sym::func_b");
+    let rows = &lines[1..];
+    assert_eq!(rows.len(), 2);
+    for (i, row) in rows.iter().enumerate() {
+        let cells: Vec<&str> = row.split(',').collect();
+        assert_eq!(cells.len(), 3, "{row}");
+        assert_eq!(cells[i + 1], "1", "diagonal of {row}");
+    }
+}
+
+#[test]
+fn test_cli_format_json_round_trips_response_dtos() {
+    use context_footprint::app::dto::{ContextResponse, SearchResponse, StatsResponse};
+
+    let Some(bin) = bin() else {
+        eprintln!("Skipping CLI test: CARGO_BIN_EXE not set");
+        return;
+    };
+
+    let (_tempdir, json_path) = write_reachable_fixture();
+    let run = |args: &[&str]| {
+        let out = Command::new(&bin)
+            .arg(json_path.as_os_str())
+            .args(["--format", "json"])
+            .args(args)
+            .output()
+            .expect("run cli");
+        assert!(
+            out.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        String::from_utf8_lossy(&out.stdout).to_string()
+    };
+
+    let stats: StatsResponse = serde_json::from_str(&run(&["stats"])).expect("stats JSON");
+    assert_eq!(stats.functions.count, 2);
+    assert!(!stats.functions.percentiles.is_empty());
+    let value: serde_json::Value = serde_json::from_str(&run(&["stats"])).unwrap();
+    assert!(value["functions"]["percentiles"][0]["percentile"].is_number());
+    assert!(value["functions"]["percentiles"][0]["tokens"].is_number());
+
+    let context: ContextResponse =
+        serde_json::from_str(&run(&["context", "sym::func_a"])).expect("context JSON");
+    let files: Vec<_> = context
+        .layers
+        .iter()
+        .flat_map(|layer| layer.files.iter().map(|f| f.file_path.as_str()))
+        .collect();
+    assert!(files.contains(&"main.py"));
+
+    let search: SearchResponse =
+        serde_json::from_str(&run(&["search", "func"])).expect("search JSON");
+    assert_eq!(search.total_matches, 2);
+
+    // --format is global, so it may also follow the subcommand
+    let out = Command::new(&bin)
+        .arg(json_path.as_os_str())
+        .args(["stats", "--format", "json"])
+        .output()
+        .expect("run stats");
+    assert!(out.status.success());
+    let _: StatsResponse = serde_json::from_slice(&out.stdout).expect("stats JSON");
+
+    let out = Command::new(&bin)
+        .arg(json_path.as_os_str())
+        .args(["reachable", "--from", "sym::func_a", "--to", "sym::func_b"])
+        .args(["--format", "text"])
+        .output()
+        .expect("run reachable");
+    assert!(out.status.success());
+    assert!(serde_json::from_slice::<serde_json::Value>(&out.stdout).is_err());
+}
+
+#[test]
+fn test_cli_file_lists_reachable_files() {
+    let Some(bin) = bin() else {
+        eprintln!("Skipping CLI test: CARGO_BIN_EXE not set");
+        return;
+    };
+
+    let (_tempdir, json_path) = write_reachable_fixture();
+    let out = Command::new(&bin)
+        .arg(&json_path)
+        .args(["file", "main.py"])
+        .output()
+        .expect("run file");
+    assert!(
+        out.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("Files touched: 1"), "stdout: {stdout}");
+    assert!(stdout.contains("    main.py\n"), "stdout: {stdout}");
+}
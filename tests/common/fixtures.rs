@@ -46,11 +46,13 @@ pub fn function_def(
         },
         enclosing_symbol: None,
         is_external: false,
+        is_generated: false,
         documentation,
         details: SymbolDetails::Function(FunctionDetails {
             parameters,
             return_types,
             type_params: vec![],
+            throws: vec![],
             modifiers: FunctionModifiers {
                 is_async: false,
                 is_generator: false,
@@ -120,6 +122,7 @@ pub fn variable_def(
         span: default_span(),
         enclosing_symbol: None,
         is_external: false,
+        is_generated: false,
         documentation,
         details: SymbolDetails::Variable(VariableDetails {
             var_type,
@@ -146,11 +149,14 @@ pub fn type_def(
         span: default_span(),
         enclosing_symbol: None,
         is_external: false,
+        is_generated: false,
         documentation,
         details: SymbolDetails::Type(TypeDetails {
             kind: type_kind,
             is_abstract,
             is_final: false,
+            is_data_class: false,
+            is_frozen: false,
             visibility: Visibility::Public,
             type_params: vec![],
             fields: vec![],
@@ -52,6 +52,18 @@ fn compute_cf(
 ) -> (
     Arc<context_footprint::domain::graph::ContextGraph>,
     context_footprint::domain::solver::CfResult,
+) {
+    compute_cf_with(semantic_data, start_symbol, PruningParams::academic(0.5))
+}
+
+/// [`compute_cf`] under custom pruning params.
+fn compute_cf_with(
+    semantic_data: SemanticData,
+    start_symbol: &str,
+    params: PruningParams,
+) -> (
+    Arc<context_footprint::domain::graph::ContextGraph>,
+    context_footprint::domain::solver::CfResult,
 ) {
     let source_reader = source_reader_for_semantic_data(&semantic_data, "content");
     let builder = GraphBuilder::new(Box::new(MockSizeFunction), Box::new(MockDocScorer));
@@ -64,7 +76,7 @@ fn compute_cf(
         .get_node_by_symbol(start_symbol)
         .expect("Start symbol not found");
 
-    let solver = CfSolver::new(Arc::clone(&graph_arc), PruningParams::academic(0.5));
+    let solver = CfSolver::new(Arc::clone(&graph_arc), params);
     let result = solver.compute_cf(&[start_idx], None);
 
     (graph_arc, result)
@@ -864,3 +876,127 @@ fn test_mediator_topology_decoupling() {
         "Mediator topology should reduce local footprint compared to mesh/spaghetti topology"
     );
 }
+
+/// Data Class Pattern:
+/// build_point -> Point(...) where Point is a @dataclass.
+/// The generated __init__ only assigns fields; it is a boundary, so its field
+/// initializations (Write to Point.x, default factory call) are not traversed.
+#[test]
+fn test_data_class_constructor_boundary() {
+    use context_footprint::domain::semantic::SymbolDetails;
+
+    let sym_caller = "build_point";
+    let sym_point = "Point";
+    let sym_init = "Point.__init__";
+    let sym_field = "Point.x";
+    let sym_default = "make_default_x";
+
+    let semantic_data = |is_data_class: bool| {
+        let mut point = type_def(sym_point, "Point", vec![], TypeKind::Class, false);
+        if let SymbolDetails::Type(details) = &mut point.details {
+            details.is_data_class = is_data_class;
+        }
+        let mut init = function_def(sym_init, "__init__", vec![], vec![], None);
+        init.enclosing_symbol = Some(sym_point.into());
+        if let SymbolDetails::Function(details) = &mut init.details {
+            details.modifiers.is_constructor = true;
+        }
+        let mut field = variable_def(sym_field, "x", vec![], None, Mutability::Mutable);
+        field.enclosing_symbol = Some(sym_point.into());
+
+        SemanticData {
+            project_root: "/test".into(),
+            documents: vec![DocumentSemantics {
+                relative_path: "point.py".into(),
+                language: "python".into(),
+                definitions: vec![
+                    function_def(sym_caller, "build_point", vec![], vec![], None),
+                    point,
+                    init,
+                    field,
+                    function_def(sym_default, "make_default_x", vec![], vec![], None),
+                ],
+                references: vec![
+                    call_reference(sym_point, sym_caller),
+                    write_reference(sym_field, sym_init),
+                    call_reference(sym_default, sym_init),
+                ],
+            }],
+            external_symbols: vec![],
+        }
+    };
+
+    let (graph, result) = compute_cf(semantic_data(true), sym_caller);
+    assert_reachable(&graph, &result, sym_init);
+    assert_not_reachable(&graph, &result, sym_field);
+    assert_not_reachable(&graph, &result, sym_default);
+
+    // Same shape without the data-class marker: the constructor is traversed.
+    let (graph, result) = compute_cf(semantic_data(false), sym_caller);
+    assert_reachable(&graph, &result, sym_field);
+    assert_reachable(&graph, &result, sym_default);
+}
+
+/// Frozen Data Class Pattern:
+/// describe -> reads Config.name, where Config is `@dataclass(frozen=True)`.
+/// The field defaulted to Mutable, but the frozen type fixes its value at construction,
+/// so with `treat_frozen_type_fields_as_immutable` the Read is a boundary and the writer
+/// (the generated __init__) is not expanded.
+#[test]
+fn test_frozen_type_field_read_boundary() {
+    use context_footprint::domain::semantic::{SymbolDetails, VariableScope};
+
+    let sym_reader = "describe";
+    let sym_config = "Config";
+    let sym_field = "Config.name";
+    let sym_builder = "load_config";
+
+    let semantic_data = |is_frozen: bool| {
+        let mut config = type_def(sym_config, "Config", vec![], TypeKind::Class, false);
+        if let SymbolDetails::Type(details) = &mut config.details {
+            details.is_data_class = true;
+            details.is_frozen = is_frozen;
+        }
+        let mut field = variable_def(sym_field, "name", vec![], None, Mutability::Mutable);
+        field.enclosing_symbol = Some(sym_config.into());
+        if let SymbolDetails::Variable(details) = &mut field.details {
+            details.scope = VariableScope::Field;
+        }
+
+        SemanticData {
+            project_root: "/test".into(),
+            documents: vec![DocumentSemantics {
+                relative_path: "config.py".into(),
+                language: "python".into(),
+                definitions: vec![
+                    function_def(sym_reader, "describe", vec![], vec![], None),
+                    config,
+                    field,
+                    function_def(sym_builder, "load_config", vec![], vec![], None),
+                ],
+                references: vec![
+                    read_reference(sym_field, sym_reader),
+                    write_reference(sym_field, sym_builder),
+                ],
+            }],
+            external_symbols: vec![],
+        }
+    };
+
+    let frozen_immutable = PruningParams {
+        treat_frozen_type_fields_as_immutable: true,
+        ..PruningParams::academic(0.5)
+    };
+    let (graph, result) =
+        compute_cf_with(semantic_data(true), sym_reader, frozen_immutable.clone());
+    assert_reachable(&graph, &result, sym_field);
+    assert_not_reachable(&graph, &result, sym_builder);
+
+    // Without the frozen marker the mutable field pulls in its writers.
+    let (graph, result) = compute_cf_with(semantic_data(false), sym_reader, frozen_immutable);
+    assert_reachable(&graph, &result, sym_builder);
+
+    // The presets leave frozen type fields mutable.
+    let (graph, result) = compute_cf(semantic_data(true), sym_reader);
+    assert_reachable(&graph, &result, sym_builder);
+}
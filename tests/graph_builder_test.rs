@@ -6,7 +6,8 @@ use context_footprint::domain::builder::GraphBuilder;
 use context_footprint::domain::edge::EdgeKind;
 use petgraph::visit::EdgeRef;
 
-use context_footprint::domain::policy::{SizeFunction, SourceSpan};
+use context_footprint::domain::policy::{DocumentationScorer, NodeInfo, SizeFunction, SourceSpan};
+use context_footprint::domain::semantic::{SymbolDefinition, SymbolDetails};
 
 use common::fixtures::{
     create_semantic_data_annotated_style_factory, create_semantic_data_empty_document,
@@ -15,7 +16,7 @@ use common::fixtures::{
     create_semantic_data_with_cycle, create_semantic_data_with_shared_state,
     source_reader_for_semantic_data,
 };
-use common::mock::{MockDocScorer, MockSizeFunction};
+use common::mock::{MockDocScorer, MockSizeFunction, MockSourceReader};
 
 const DUMMY_SOURCE: &str = "def foo(): pass\n";
 
@@ -49,6 +50,174 @@ fn test_build_graph_two_files() {
     assert!(graph.graph.edge_count() >= 1);
 }
 
+#[test]
+fn test_excluded_files_never_enter_the_graph() {
+    use context_footprint::domain::path_glob::PathGlob;
+
+    let semantic_data = create_semantic_data_two_files();
+    let reader = source_reader_for_semantic_data(&semantic_data, DUMMY_SOURCE);
+
+    let builder = GraphBuilder::new(
+        Box::new(MockSizeFunction::new()),
+        Box::new(MockDocScorer::new()),
+    )
+    .with_exclude(vec![PathGlob::new("utils.py").unwrap()]);
+    let graph = builder.build(semantic_data, &reader).unwrap();
+
+    assert_eq!(graph.graph.node_count(), 1);
+    assert!(graph.get_node_by_symbol("sym::utils::func_util").is_none());
+    // The call from main.py into the excluded file is left unresolved, not dangling
+    assert_eq!(graph.graph.edge_count(), 0);
+}
+
+#[test]
+fn test_missing_source_degrades_to_zero_size_when_not_fatal() {
+    let semantic_data = create_semantic_data_two_files();
+    let new_builder = || {
+        GraphBuilder::new(
+            Box::new(MockSizeFunction::with_size(5)),
+            Box::new(MockDocScorer::new()),
+        )
+    };
+
+    assert!(
+        new_builder()
+            .build(semantic_data.clone(), &MockSourceReader::new())
+            .is_err(),
+        "missing sources are fatal by default"
+    );
+
+    let graph = new_builder()
+        .with_missing_source_fatal(false)
+        .build(semantic_data, &MockSourceReader::new())
+        .unwrap();
+    assert_eq!(graph.graph.node_count(), 2);
+    assert!(graph.graph.edge_count() >= 1);
+    for node in graph.graph.node_weights() {
+        assert_eq!(node.core().context_size, 0);
+    }
+    assert!(
+        graph
+            .warnings
+            .iter()
+            .any(|w| w.contains("2 source file(s) could not be read"))
+    );
+}
+
+#[test]
+fn test_unresolved_calls_are_kept_for_diagnostics() {
+    let mut semantic_data = create_semantic_data_two_files();
+    let mut dynamic_call = common::fixtures::call_reference("sym::missing", "sym::main::func_main");
+    dynamic_call.target_symbol = None;
+    dynamic_call.receiver = Some("sym::main::client".into());
+    dynamic_call.method_name = Some("fetch".into());
+    semantic_data.documents[0].references.push(dynamic_call);
+    let reader = source_reader_for_semantic_data(&semantic_data, DUMMY_SOURCE);
+
+    let builder = GraphBuilder::new(
+        Box::new(MockSizeFunction::new()),
+        Box::new(MockDocScorer::new()),
+    );
+    let graph = builder.build(semantic_data, &reader).unwrap();
+
+    assert_eq!(
+        graph.unresolved.len(),
+        1,
+        "the resolved call is not reported"
+    );
+    let unresolved = &graph.unresolved[0];
+    assert_eq!(unresolved.source, "sym::main::func_main");
+    assert_eq!(unresolved.callee(), "fetch");
+    assert_eq!(unresolved.receiver.as_deref(), Some("sym::main::client"));
+}
+
+#[test]
+fn test_param_type_edges_reach_dto_constructor_or_fields() {
+    use common::fixtures::{function_def, method_def, type_def, variable_def};
+    use context_footprint::domain::semantic::{
+        DocumentSemantics, Mutability, Parameter, SemanticData, TypeKind, VariableScope,
+    };
+
+    let param = |name: &str, type_id: &str| Parameter {
+        name: name.into(),
+        param_type: Some(type_id.into()),
+        is_high_freedom_type: false,
+        has_default: false,
+        is_variadic: false,
+    };
+    let mut field = variable_def(
+        "api/Point#x.",
+        "x",
+        vec![],
+        Some("int".into()),
+        Mutability::Mutable,
+    );
+    field.enclosing_symbol = Some("api/Point#".into());
+    if let SymbolDetails::Variable(v) = &mut field.details {
+        v.scope = VariableScope::Field;
+    }
+    let mut field_y = field.clone();
+    field_y.symbol_id = "api/Point#y.".into();
+    field_y.name = "y".into();
+    let semantic_data = SemanticData {
+        project_root: "/test".into(),
+        documents: vec![DocumentSemantics {
+            relative_path: "api.py".into(),
+            language: "python".into(),
+            definitions: vec![
+                type_def("api/User#", "User", vec![], TypeKind::Class, false),
+                method_def(
+                    "api/User#__init__().",
+                    "__init__",
+                    "api/User#",
+                    vec![],
+                    vec![],
+                    None,
+                ),
+                type_def("api/Point#", "Point", vec![], TypeKind::Class, false),
+                field,
+                field_y,
+                function_def(
+                    "api/create_user().",
+                    "create_user",
+                    vec![],
+                    vec![param("user", "api/User#"), param("origin", "api/Point#")],
+                    None,
+                ),
+            ],
+            references: vec![],
+        }],
+        external_symbols: vec![],
+    };
+    let reader = source_reader_for_semantic_data(&semantic_data, DUMMY_SOURCE);
+    let new_builder = || {
+        GraphBuilder::new(
+            Box::new(MockSizeFunction::new()),
+            Box::new(MockDocScorer::new()),
+        )
+    };
+
+    let default = new_builder().build(semantic_data.clone(), &reader).unwrap();
+    assert_eq!(default.graph.edge_count(), 0, "opt-in");
+
+    let graph = new_builder()
+        .with_param_type_edges(true)
+        .build(semantic_data, &reader)
+        .unwrap();
+    let create = graph.get_node_by_symbol("api/create_user().").unwrap();
+    // Parameter order, then each type's fields in definition order (edges are listed most
+    // recent first)
+    let uses: Vec<&str> = graph
+        .outgoing_edges(create)
+        .filter(|(_, kind)| **kind == EdgeKind::Uses)
+        .map(|(target, _)| graph.symbol_of(target).unwrap())
+        .collect();
+    assert_eq!(
+        uses,
+        vec!["api/Point#y.", "api/Point#x.", "api/User#__init__()."]
+    );
+}
+
 #[test]
 fn test_three_pass_creates_nodes_then_edges() {
     let semantic_data = create_semantic_data_simple();
@@ -226,3 +395,749 @@ fn test_use_signature_only_for_size_limits_context_size_to_signature() {
         "annotated-style factory should use signature-only size (1 line = 10), not full body (260)"
     );
 }
+
+#[test]
+fn test_signature_sizes_are_measured_only_when_enabled() {
+    use common::fixtures::function_def;
+    use context_footprint::domain::semantic::{DocumentSemantics, SemanticData};
+
+    let semantic_data = SemanticData {
+        project_root: "/test".into(),
+        documents: vec![DocumentSemantics {
+            relative_path: "main.py".into(),
+            language: "python".into(),
+            definitions: vec![function_def("main.run", "run", vec![], vec![], None)],
+            references: vec![],
+        }],
+        external_symbols: vec![],
+    };
+    let source = "def run():\n    a()\n    b()\n    c()\n    d()\n    e()\n";
+    let reader = source_reader_for_semantic_data(&semantic_data, source);
+    let signature_size = |builder: GraphBuilder| {
+        let graph = builder.build(semantic_data.clone(), &reader).unwrap();
+        let run = graph.get_node_by_symbol("main.run").unwrap();
+        graph.node(run).core().signature_size
+    };
+    let new_builder = || {
+        GraphBuilder::new(
+            Box::new(LineCountSizeFunction),
+            Box::new(MockDocScorer::new()),
+        )
+    };
+
+    assert_eq!(signature_size(new_builder()), None, "opt-in");
+    assert_eq!(
+        signature_size(new_builder().with_signature_sizes(true)),
+        Some(10)
+    );
+}
+
+#[test]
+fn test_declaration_file_definitions_are_external_boundaries() {
+    use common::fixtures::{call_reference, function_def};
+    use context_footprint::domain::policy::PruningParams;
+    use context_footprint::domain::semantic::{DocumentSemantics, SemanticData};
+    use context_footprint::domain::solver::CfSolver;
+    use std::sync::Arc;
+
+    let semantic_data = SemanticData {
+        project_root: "/test".into(),
+        documents: vec![
+            DocumentSemantics {
+                relative_path: "src/main.ts".into(),
+                language: "typescript".into(),
+                definitions: vec![function_def("main.run", "run", vec![], vec![], None)],
+                references: vec![call_reference("lib.greet", "main.run")],
+            },
+            DocumentSemantics {
+                relative_path: "types/lib.d.ts".into(),
+                language: "typescript".into(),
+                definitions: vec![function_def("lib.greet", "greet", vec![], vec![], None)],
+                references: vec![],
+            },
+        ],
+        external_symbols: vec![],
+    };
+    let reader = source_reader_for_semantic_data(&semantic_data, DUMMY_SOURCE);
+    let builder = GraphBuilder::new(
+        Box::new(MockSizeFunction::with_size(500)),
+        Box::new(MockDocScorer::new()),
+    );
+    let graph = builder.build(semantic_data, &reader).unwrap();
+
+    let greet = graph.node(graph.get_node_by_symbol("lib.greet").unwrap());
+    assert!(greet.core().is_external, ".d.ts definitions are external");
+    assert_eq!(greet.core().context_size, 50, "size capped like externals");
+
+    let run = graph.get_node_by_symbol("main.run").unwrap();
+    let solver = CfSolver::new(Arc::new(graph), PruningParams::strict(0.5));
+    let result = solver.compute_cf(&[run], None);
+    assert_eq!(result.total_context_size, 500 + 50);
+    assert_eq!(result.reachable_set.len(), 2);
+}
+
+#[test]
+fn test_generated_definitions_are_external_boundaries() {
+    use common::fixtures::{call_reference, function_def};
+    use context_footprint::domain::policy::PruningParams;
+    use context_footprint::domain::semantic::{DocumentSemantics, SemanticData};
+    use context_footprint::domain::solver::CfSolver;
+    use std::sync::Arc;
+
+    // message_pb2.py: indexer marked the definition with the Generated role
+    let mut serialize = function_def("pb.serialize", "serialize", vec![], vec![], None);
+    serialize.is_generated = true;
+    let semantic_data = SemanticData {
+        project_root: "/test".into(),
+        documents: vec![
+            DocumentSemantics {
+                relative_path: "app/main.py".into(),
+                language: "python".into(),
+                definitions: vec![function_def("main.run", "run", vec![], vec![], None)],
+                references: vec![call_reference("pb.serialize", "main.run")],
+            },
+            DocumentSemantics {
+                relative_path: "app/message_pb2.py".into(),
+                language: "python".into(),
+                definitions: vec![serialize],
+                references: vec![],
+            },
+        ],
+        external_symbols: vec![],
+    };
+    let reader = source_reader_for_semantic_data(&semantic_data, DUMMY_SOURCE);
+    let builder = GraphBuilder::new(
+        Box::new(MockSizeFunction::with_size(500)),
+        Box::new(MockDocScorer::new()),
+    );
+    let graph = builder.build(semantic_data, &reader).unwrap();
+
+    let serialize = graph.node(graph.get_node_by_symbol("pb.serialize").unwrap());
+    assert!(serialize.core().is_external, "generated code is a boundary");
+    assert_eq!(
+        serialize.core().context_size,
+        50,
+        "size capped like externals"
+    );
+
+    let run = graph.get_node_by_symbol("main.run").unwrap();
+    let solver = CfSolver::new(Arc::new(graph), PruningParams::strict(0.5));
+    assert_eq!(solver.compute_cf(&[run], None).total_context_size, 500 + 50);
+}
+
+#[test]
+fn test_conflicting_external_symbols_keep_richer_metadata() {
+    use common::fixtures::function_def;
+    use context_footprint::domain::node::Node;
+
+    let mut semantic_data = create_semantic_data_simple();
+    let mut bare = function_def("ext::dumps", "dumps", vec![], vec![], None);
+    bare.is_external = true;
+    let mut rich = function_def(
+        "ext::dumps",
+        "dumps",
+        vec!["Serialize obj to a JSON formatted str.".into()],
+        vec![],
+        Some("str".into()),
+    );
+    rich.is_external = true;
+    semantic_data.external_symbols = vec![bare, rich];
+
+    let reader = source_reader_for_semantic_data(&semantic_data, DUMMY_SOURCE);
+    let builder = GraphBuilder::new(
+        Box::new(MockSizeFunction::new()),
+        Box::new(MockDocScorer::new()),
+    );
+    let graph = builder.build(semantic_data, &reader).unwrap();
+
+    // One node per symbol: the duplicate does not leave an orphan behind.
+    assert_eq!(graph.graph.node_count(), 3);
+    let idx = graph.get_node_by_symbol("ext::dumps").unwrap();
+    let Node::Function(f) = graph.node(idx) else {
+        panic!("expected function node");
+    };
+    assert_eq!(f.return_types, vec!["str".to_string()]);
+    assert_eq!(graph.warnings.len(), 1);
+    assert!(graph.warnings[0].contains("ext::dumps"));
+}
+
+#[test]
+fn test_external_size_cap_and_signature_truncation_are_configurable() {
+    use common::fixtures::function_def;
+
+    /// Roughly one token per four characters of the measured source.
+    struct CharSize;
+    impl SizeFunction for CharSize {
+        fn compute(&self, source: &str, _span: &SourceSpan, _doc_texts: &[String]) -> u32 {
+            source.len() as u32 / 4
+        }
+    }
+
+    let external_size = |builder: GraphBuilder| {
+        let mut semantic_data = create_semantic_data_simple();
+        let mut route = function_def(
+            "ext::route",
+            "route",
+            vec![],
+            vec![],
+            Some(format!("Annotated[str, Form({})]", "x".repeat(400))),
+        );
+        route.is_external = true;
+        semantic_data.external_symbols = vec![route];
+        let reader = source_reader_for_semantic_data(&semantic_data, DUMMY_SOURCE);
+        let graph = builder.build(semantic_data, &reader).unwrap();
+        let idx = graph.get_node_by_symbol("ext::route").unwrap();
+        graph.node(idx).core().context_size
+    };
+    let new_builder = || GraphBuilder::new(Box::new(CharSize), Box::new(MockDocScorer::new()));
+
+    assert_eq!(external_size(new_builder()), 50, "default cap");
+    let truncated = external_size(new_builder().with_external_max_tokens(1000));
+    assert!(truncated > 50, "a higher cap keeps more of the signature");
+    let full = external_size(
+        new_builder()
+            .with_external_max_tokens(1000)
+            .with_external_signature_len(1000),
+    );
+    assert!(full > truncated, "a longer signature budget keeps more");
+}
+
+#[test]
+fn test_external_symbols_differing_only_in_version_merge_when_normalized() {
+    use common::fixtures::{call_reference, function_def};
+    use context_footprint::domain::semantic::normalize_symbol_version;
+
+    let old = "scip-python python requests 2.31.0 requests/get().";
+    let new = "scip-python python requests 2.32.0 requests/get().";
+    assert_eq!(normalize_symbol_version(old), normalize_symbol_version(new));
+    assert_eq!(normalize_symbol_version("local 3"), "local 3");
+
+    let build = |normalize: bool| {
+        let mut semantic_data = create_semantic_data_simple();
+        let caller = semantic_data.documents[0].definitions[0].symbol_id.clone();
+        let mut bare = function_def(old, "get", vec![], vec![], None);
+        bare.is_external = true;
+        let mut rich = function_def(new, "get", vec![], vec![], Some("Response".into()));
+        rich.is_external = true;
+        semantic_data.external_symbols = vec![bare, rich];
+        // The caller was indexed against the older version
+        semantic_data.documents[0]
+            .references
+            .push(call_reference(old, &caller));
+
+        let reader = source_reader_for_semantic_data(&semantic_data, DUMMY_SOURCE);
+        let builder = GraphBuilder::new(
+            Box::new(MockSizeFunction::new()),
+            Box::new(MockDocScorer::new()),
+        )
+        .with_symbol_version_normalization(normalize);
+        (builder.build(semantic_data, &reader).unwrap(), caller)
+    };
+
+    let (graph, _) = build(false);
+    assert!(graph.get_node_by_symbol(old).is_some());
+    assert!(graph.get_node_by_symbol(new).is_some());
+    assert!(graph.warnings.is_empty());
+
+    let (graph, caller) = build(true);
+    assert!(graph.get_node_by_symbol(old).is_none());
+    let kept = graph.get_node_by_symbol(new).unwrap();
+    assert_eq!(graph.warnings.len(), 1);
+    // References to the dropped version resolve to the surviving node
+    let caller_idx = graph.get_node_by_symbol(&caller).unwrap();
+    assert!(
+        graph
+            .neighbors(caller_idx)
+            .any(|(idx, kind)| idx == kept && *kind == EdgeKind::Call)
+    );
+}
+
+#[test]
+fn test_single_line_variable_span_expands_to_full_initializer() {
+    use common::fixtures::variable_def;
+    use context_footprint::domain::semantic::{Mutability, SourceSpan as SemanticSpan};
+
+    /// Size = number of lines covered by the span.
+    struct LineCountSize;
+    impl SizeFunction for LineCountSize {
+        fn compute(&self, _source: &str, span: &SourceSpan, _doc_texts: &[String]) -> u32 {
+            span.end_line - span.start_line + 1
+        }
+    }
+
+    // Neither the `}` in the string nor the one in the comment closes the dict
+    let source =
+        "import os\n\nCONFIG = {\n    \"a\": \"}\",  # not }\n    \"b\": 2,\n}\nOTHER = 1\n";
+    let mut semantic_data = create_semantic_data_simple();
+    let mut config = variable_def("sym::CONFIG", "CONFIG", vec![], None, Mutability::Mutable);
+    // Name-only range, as some indexers report it
+    config.span = SemanticSpan {
+        start_line: 2,
+        start_column: 0,
+        end_line: 2,
+        end_column: 6,
+    };
+    let mut other = variable_def("sym::OTHER", "OTHER", vec![], None, Mutability::Mutable);
+    other.span = SemanticSpan {
+        start_line: 6,
+        start_column: 0,
+        end_line: 6,
+        end_column: 5,
+    };
+    semantic_data.documents[0]
+        .definitions
+        .extend([config, other]);
+
+    let reader = source_reader_for_semantic_data(&semantic_data, source);
+    let builder = GraphBuilder::new(Box::new(LineCountSize), Box::new(MockDocScorer::new()));
+    let graph = builder.build(semantic_data, &reader).unwrap();
+
+    let config = graph.node(graph.get_node_by_symbol("sym::CONFIG").unwrap());
+    assert_eq!(config.core().span.end_line, 5);
+    assert_eq!(config.core().context_size, 4);
+    let other = graph.node(graph.get_node_by_symbol("sym::OTHER").unwrap());
+    assert_eq!(other.core().context_size, 1);
+}
+
+#[test]
+fn test_return_types_inferred_from_rust_and_typescript_signatures() {
+    use common::fixtures::{function_def, type_def};
+    use common::mock::MockSourceReader;
+    use context_footprint::domain::node::Node;
+    use context_footprint::domain::semantic::{
+        DocumentSemantics, SemanticData, SourceSpan as SemanticSpan, TypeKind,
+    };
+
+    let document = |path: &str, language: &str, make_id: &str, foo_id: &str, end_line| {
+        let mut make = function_def(make_id, "make", vec![], vec![], None);
+        make.span = SemanticSpan {
+            start_line: 0,
+            start_column: 0,
+            end_line,
+            end_column: 1,
+        };
+        DocumentSemantics {
+            relative_path: path.into(),
+            language: language.into(),
+            definitions: vec![
+                make,
+                type_def(foo_id, "Foo", vec![], TypeKind::Class, false),
+            ],
+            references: vec![],
+        }
+    };
+    let semantic_data = SemanticData {
+        project_root: "/test".into(),
+        documents: vec![
+            document("lib.rs", "rust", "rs::make", "rs::Foo", 2),
+            document("app.ts", "typescript", "ts::make", "ts::Foo", 2),
+        ],
+        external_symbols: vec![],
+    };
+    let reader = MockSourceReader::new()
+        .with_file(
+            "/test/lib.rs",
+            "fn make(x: i32) -> Foo {\n    Foo { x }\n}\n",
+        )
+        .with_file(
+            "/test/app.ts",
+            "const make = (x: number): Foo => {\n  return new Foo(x);\n};\n",
+        );
+
+    let builder = GraphBuilder::new(
+        Box::new(MockSizeFunction::new()),
+        Box::new(MockDocScorer::new()),
+    );
+    let graph = builder.build(semantic_data, &reader).unwrap();
+
+    for (make_id, foo_id) in [("rs::make", "rs::Foo"), ("ts::make", "ts::Foo")] {
+        let Node::Function(f) = graph.node(graph.get_node_by_symbol(make_id).unwrap()) else {
+            panic!("expected function node");
+        };
+        assert_eq!(f.return_types, vec![foo_id.to_string()], "{make_id}");
+    }
+
+    // The adapter left TypeScript parameters empty: they are read from the signature
+    let Node::Function(f) = graph.node(graph.get_node_by_symbol("ts::make").unwrap()) else {
+        panic!("expected function node");
+    };
+    assert_eq!(f.parameters.len(), 1);
+    assert_eq!(f.parameters[0].name, "x");
+    assert_eq!(f.parameters[0].param_type.as_deref(), Some("number"));
+    assert!(f.is_signature_complete());
+}
+
+#[test]
+fn test_references_without_enclosing_definition_are_reported() {
+    use common::fixtures::call_reference;
+
+    let mut semantic_data = create_semantic_data_simple();
+    semantic_data.documents[0]
+        .references
+        .push(call_reference("sym::func_b", ""));
+
+    let reader = source_reader_for_semantic_data(&semantic_data, DUMMY_SOURCE);
+    let builder = GraphBuilder::new(
+        Box::new(MockSizeFunction::new()),
+        Box::new(MockDocScorer::new()),
+    );
+    let graph = builder.build(semantic_data, &reader).unwrap();
+
+    assert_eq!(graph.warnings.len(), 1);
+    assert!(graph.warnings[0].starts_with("1 reference(s) in "));
+    assert!(graph.warnings[0].contains("no enclosing definition"));
+}
+
+#[test]
+fn test_method_call_on_locally_constructed_object_resolves_to_concrete_method() {
+    use common::fixtures::{call_reference, function_def, method_def, type_def};
+    use context_footprint::domain::semantic::{DocumentSemantics, SemanticData, TypeKind};
+
+    // def main():
+    //     x = Concrete()
+    //     x.run()
+    let mut construct = call_reference("Concrete#", "main().");
+    construct.assigned_to = Some("local 1".into());
+    let mut method_call = call_reference("unresolved", "main().");
+    method_call.target_symbol = None;
+    method_call.receiver = Some("local 1".into());
+    method_call.method_name = Some("run".into());
+
+    let semantic_data = SemanticData {
+        project_root: "/test".into(),
+        documents: vec![DocumentSemantics {
+            relative_path: "main.py".into(),
+            language: "python".into(),
+            definitions: vec![
+                function_def("main().", "main", vec![], vec![], None),
+                type_def("Concrete#", "Concrete", vec![], TypeKind::Class, false),
+                method_def("Concrete#run().", "run", "Concrete#", vec![], vec![], None),
+            ],
+            references: vec![construct, method_call],
+        }],
+        external_symbols: vec![],
+    };
+
+    let reader = source_reader_for_semantic_data(&semantic_data, DUMMY_SOURCE);
+    let builder = GraphBuilder::new(
+        Box::new(MockSizeFunction::new()),
+        Box::new(MockDocScorer::new()),
+    );
+    let graph = builder.build(semantic_data, &reader).unwrap();
+
+    let main = graph.get_node_by_symbol("main().").unwrap();
+    let run = graph.get_node_by_symbol("Concrete#run().").unwrap();
+    assert!(
+        graph
+            .graph
+            .edges(main)
+            .any(|e| e.target() == run && *e.weight() == EdgeKind::Call)
+    );
+}
+
+#[test]
+fn test_typescript_namespace_member_resolves_through_namespace_value() {
+    use common::fixtures::{call_reference, function_def, method_def};
+    use context_footprint::domain::node::Node;
+    use context_footprint::domain::semantic::{DocumentSemantics, SemanticData};
+
+    // a.ts: namespace Foo { export function bar() {} }
+    // b.ts: function run() { Foo.bar() }  -- indexed against the namespace's value binding
+    let bar = "scip-typescript npm app 1.0 src/`a.ts`/Foo/bar().";
+    let bar_via_value = "scip-typescript npm app 1.0 src/`a.ts`/Foo.bar().";
+    let run = "scip-typescript npm app 1.0 src/`b.ts`/run().";
+    let semantic_data = SemanticData {
+        project_root: "/test".into(),
+        documents: vec![
+            DocumentSemantics {
+                relative_path: "src/a.ts".into(),
+                language: "typescript".into(),
+                definitions: vec![method_def(
+                    bar,
+                    "bar",
+                    "scip-typescript npm app 1.0 src/`a.ts`/Foo/",
+                    vec![],
+                    vec![],
+                    None,
+                )],
+                references: vec![],
+            },
+            DocumentSemantics {
+                relative_path: "src/b.ts".into(),
+                language: "typescript".into(),
+                definitions: vec![function_def(run, "run", vec![], vec![], None)],
+                references: vec![call_reference(bar_via_value, run)],
+            },
+        ],
+        external_symbols: vec![],
+    };
+
+    let reader = source_reader_for_semantic_data(&semantic_data, DUMMY_SOURCE);
+    let builder = GraphBuilder::new(
+        Box::new(MockSizeFunction::new()),
+        Box::new(MockDocScorer::new()),
+    );
+    let graph = builder.build(semantic_data, &reader).unwrap();
+
+    let bar_idx = graph
+        .get_node_by_symbol(bar)
+        .expect("namespace member node");
+    assert!(matches!(graph.node(bar_idx), Node::Function(_)));
+    let run_idx = graph.get_node_by_symbol(run).unwrap();
+    assert!(graph.graph.contains_edge(run_idx, bar_idx));
+}
+
+#[test]
+fn test_unwritten_all_caps_global_becomes_const_read_boundary() {
+    use common::fixtures::{function_def, read_reference, variable_def, write_reference};
+    use context_footprint::domain::node::{Mutability, Node};
+    use context_footprint::domain::policy::{
+        BoundaryReason, PruningDecision, PruningParams, evaluate_forward,
+    };
+    use context_footprint::domain::semantic::{
+        DocumentSemantics, Mutability as SemanticMutability, SemanticData,
+    };
+
+    let semantic_data = SemanticData {
+        project_root: "/test".into(),
+        documents: vec![DocumentSemantics {
+            relative_path: "config.py".into(),
+            language: "python".into(),
+            definitions: vec![
+                function_def("sym::handler", "handler", vec![], vec![], None),
+                function_def("sym::bump", "bump", vec![], vec![], None),
+                variable_def(
+                    "sym::CONFIG",
+                    "CONFIG",
+                    vec![],
+                    None,
+                    SemanticMutability::Mutable,
+                ),
+                variable_def(
+                    "sym::COUNTER",
+                    "COUNTER",
+                    vec![],
+                    None,
+                    SemanticMutability::Mutable,
+                ),
+            ],
+            references: vec![
+                read_reference("sym::CONFIG", "sym::handler"),
+                read_reference("sym::COUNTER", "sym::handler"),
+                write_reference("sym::COUNTER", "sym::bump"),
+            ],
+        }],
+        external_symbols: vec![],
+    };
+    let reader = source_reader_for_semantic_data(&semantic_data, DUMMY_SOURCE);
+    let build = |enabled| {
+        GraphBuilder::new(
+            Box::new(MockSizeFunction::new()),
+            Box::new(MockDocScorer::new()),
+        )
+        .with_config_constant_inference(enabled)
+        .build(semantic_data.clone(), &reader)
+        .unwrap()
+    };
+    let mutability = |graph: &context_footprint::domain::graph::ContextGraph, sym| match graph
+        .node(graph.get_node_by_symbol(sym).unwrap())
+    {
+        Node::Variable(v) => v.mutability.clone(),
+        Node::Function(_) => panic!("expected variable node"),
+    };
+
+    let off = build(false);
+    assert_eq!(mutability(&off, "sym::CONFIG"), Mutability::Mutable);
+
+    let graph = build(true);
+    assert_eq!(mutability(&graph, "sym::CONFIG"), Mutability::Const);
+    // Written somewhere: stays mutable
+    assert_eq!(mutability(&graph, "sym::COUNTER"), Mutability::Mutable);
+
+    let handler = graph.node(graph.get_node_by_symbol("sym::handler").unwrap());
+    let config = graph.node(graph.get_node_by_symbol("sym::CONFIG").unwrap());
+    assert_eq!(
+        evaluate_forward(
+            &PruningParams::strict(0.5),
+            handler,
+            config,
+            &EdgeKind::Read,
+            &graph
+        ),
+        PruningDecision::Boundary(BoundaryReason::ImmutableVariable)
+    );
+}
+
+#[test]
+fn test_unresolved_method_call_recovers_with_name_from_target_symbol() {
+    use common::fixtures::{call_reference, function_def, method_def, type_def};
+    use context_footprint::domain::semantic::{DocumentSemantics, SemanticData, TypeKind};
+
+    // def main():
+    //     repo = SqlRepo()
+    //     repo.save()   -- indexed against a symbol outside the project, no method_name
+    let mut construct = call_reference("SqlRepo#", "main().");
+    construct.assigned_to = Some("local 1".into());
+    let mut method_call = call_reference("vendor/BaseRepo#save().", "main().");
+    method_call.receiver = Some("local 1".into());
+    assert!(method_call.method_name.is_none());
+
+    let semantic_data = SemanticData {
+        project_root: "/test".into(),
+        documents: vec![DocumentSemantics {
+            relative_path: "main.py".into(),
+            language: "python".into(),
+            definitions: vec![
+                function_def("main().", "main", vec![], vec![], None),
+                type_def("SqlRepo#", "SqlRepo", vec![], TypeKind::Class, false),
+                method_def("SqlRepo#save().", "save", "SqlRepo#", vec![], vec![], None),
+            ],
+            references: vec![construct, method_call],
+        }],
+        external_symbols: vec![],
+    };
+
+    let reader = source_reader_for_semantic_data(&semantic_data, DUMMY_SOURCE);
+    let builder = GraphBuilder::new(
+        Box::new(MockSizeFunction::new()),
+        Box::new(MockDocScorer::new()),
+    );
+    let graph = builder.build(semantic_data, &reader).unwrap();
+
+    let main = graph.get_node_by_symbol("main().").unwrap();
+    let save = graph.get_node_by_symbol("SqlRepo#save().").unwrap();
+    assert!(
+        graph
+            .graph
+            .edges(main)
+            .any(|e| e.target() == save && *e.weight() == EdgeKind::Call)
+    );
+}
+
+/// Scores by the fraction of parameter names mentioned in the documentation.
+struct ParamCoverageDocScorer;
+
+impl DocumentationScorer for ParamCoverageDocScorer {
+    fn score(&self, _node_info: &NodeInfo, _doc_text: Option<&str>) -> f32 {
+        0.0
+    }
+
+    fn score_node(&self, def: &SymbolDefinition, _node_info: &NodeInfo, doc: Option<&str>) -> f32 {
+        let SymbolDetails::Function(details) = &def.details else {
+            return 0.0;
+        };
+        let (Some(doc), false) = (doc, details.parameters.is_empty()) else {
+            return 0.0;
+        };
+        let documented = details
+            .parameters
+            .iter()
+            .filter(|p| doc.contains(p.name.as_str()))
+            .count();
+        documented as f32 / details.parameters.len() as f32
+    }
+}
+
+#[test]
+fn test_doc_scorer_score_node_sees_parameter_names() {
+    use common::fixtures::function_def;
+    use context_footprint::domain::semantic::{DocumentSemantics, Parameter, SemanticData};
+
+    let param = |name: &str| Parameter {
+        name: name.into(),
+        ..Default::default()
+    };
+    let semantic_data = SemanticData {
+        project_root: "/test".into(),
+        documents: vec![DocumentSemantics {
+            relative_path: "main.py".into(),
+            language: "python".into(),
+            definitions: vec![function_def(
+                "send().",
+                "send",
+                vec!["Send a message.\n\nArgs:\n    to: recipient address".into()],
+                vec![param("to"), param("body")],
+                None,
+            )],
+            references: vec![],
+        }],
+        external_symbols: vec![],
+    };
+
+    let reader = source_reader_for_semantic_data(&semantic_data, DUMMY_SOURCE);
+    let builder = GraphBuilder::new(
+        Box::new(MockSizeFunction::new()),
+        Box::new(ParamCoverageDocScorer),
+    );
+    let graph = builder.build(semantic_data, &reader).unwrap();
+
+    let send = graph.node(graph.get_node_by_symbol("send().").unwrap());
+    assert_eq!(send.core().doc_score, 0.5);
+}
+
+#[test]
+fn test_declared_throws_become_raises_edges_to_exception_constructors() {
+    use common::fixtures::{function_def, method_def, type_def};
+    use context_footprint::domain::semantic::{DocumentSemantics, SemanticData, TypeKind};
+
+    let mut load = function_def("load().", "load", vec![], vec![], None);
+    let mut init = method_def(
+        "NotFound#__init__().",
+        "__init__",
+        "NotFound#",
+        vec![],
+        vec![],
+        None,
+    );
+    if let SymbolDetails::Function(f) = &mut load.details {
+        f.throws = vec!["NotFound#".into(), "Unindexed#".into()];
+    }
+    let mut from_code = method_def(
+        "NotFound#from_code().",
+        "from_code",
+        "NotFound#",
+        vec![],
+        vec![],
+        None,
+    );
+    for ctor in [&mut init, &mut from_code] {
+        if let SymbolDetails::Function(f) = &mut ctor.details {
+            f.modifiers.is_constructor = true;
+        }
+    }
+    let semantic_data = SemanticData {
+        project_root: "/test".into(),
+        documents: vec![DocumentSemantics {
+            relative_path: "main.py".into(),
+            language: "python".into(),
+            definitions: vec![
+                load,
+                type_def("NotFound#", "NotFound", vec![], TypeKind::Class, false),
+                init,
+                from_code,
+            ],
+            references: vec![],
+        }],
+        external_symbols: vec![],
+    };
+
+    let reader = source_reader_for_semantic_data(&semantic_data, DUMMY_SOURCE);
+    let builder = GraphBuilder::new(
+        Box::new(MockSizeFunction::new()),
+        Box::new(MockDocScorer::new()),
+    );
+    let graph = builder.build(semantic_data, &reader).unwrap();
+
+    let load = graph.get_node_by_symbol("load().").unwrap();
+    let init = graph.get_node_by_symbol("NotFound#__init__().").unwrap();
+    let from_code = graph.get_node_by_symbol("NotFound#from_code().").unwrap();
+    // `Unindexed#` has no constructor node, so only NotFound is linked, through both of its
+    // constructors in definition order (edges are listed most recent first)
+    let raises: Vec<_> = graph
+        .graph
+        .edges(load)
+        .filter(|e| matches!(e.weight(), EdgeKind::Raises))
+        .map(|e| e.target())
+        .collect();
+    assert_eq!(raises, vec![from_code, init]);
+}
@@ -54,11 +54,14 @@ fn build_payment_gateway_fixture(interface_doc_score: f32) -> SemanticData {
                     },
                     enclosing_symbol: None,
                     is_external: false,
+                    is_generated: false,
                     documentation: vec![],
                     details: SymbolDetails::Type(TypeDetails {
                         kind: context_footprint::domain::semantic::TypeKind::Interface,
                         is_abstract: true,
                         is_final: false,
+                        is_data_class: false,
+                        is_frozen: false,
                         visibility: Visibility::Public,
                         type_params: vec![],
                         implements: vec![],
@@ -85,6 +88,7 @@ fn build_payment_gateway_fixture(interface_doc_score: f32) -> SemanticData {
                     },
                     enclosing_symbol: Some(interface_id.to_string()),
                     is_external: false,
+                    is_generated: false,
                     documentation: charge_docs,
                     details: SymbolDetails::Function(FunctionDetails {
                         parameters: vec![Parameter {
@@ -96,6 +100,7 @@ fn build_payment_gateway_fixture(interface_doc_score: f32) -> SemanticData {
                         }],
                         return_types: vec!["bool#".to_string()],
                         type_params: vec![],
+                        throws: vec![],
                         modifiers: FunctionModifiers {
                             is_async: false,
                             is_generator: false,
@@ -127,11 +132,14 @@ fn build_payment_gateway_fixture(interface_doc_score: f32) -> SemanticData {
                     },
                     enclosing_symbol: None,
                     is_external: false,
+                    is_generated: false,
                     documentation: vec![],
                     details: SymbolDetails::Type(TypeDetails {
                         kind: context_footprint::domain::semantic::TypeKind::Class,
                         is_abstract: false,
                         is_final: false,
+                        is_data_class: false,
+                        is_frozen: false,
                         visibility: Visibility::Public,
                         type_params: vec![],
                         implements: vec![interface_id.to_string()],
@@ -158,6 +166,7 @@ fn build_payment_gateway_fixture(interface_doc_score: f32) -> SemanticData {
                     },
                     enclosing_symbol: Some(stripe_type_id.to_string()),
                     is_external: false,
+                    is_generated: false,
                     documentation: vec![
                         "Charges via Stripe API. Returns True on success.".to_string(),
                     ],
@@ -171,6 +180,7 @@ fn build_payment_gateway_fixture(interface_doc_score: f32) -> SemanticData {
                         }],
                         return_types: vec!["bool#".to_string()],
                         type_params: vec![],
+                        throws: vec![],
                         modifiers: FunctionModifiers {
                             is_async: false,
                             is_generator: false,
@@ -202,11 +212,14 @@ fn build_payment_gateway_fixture(interface_doc_score: f32) -> SemanticData {
                     },
                     enclosing_symbol: None,
                     is_external: false,
+                    is_generated: false,
                     documentation: vec![],
                     details: SymbolDetails::Type(TypeDetails {
                         kind: context_footprint::domain::semantic::TypeKind::Class,
                         is_abstract: false,
                         is_final: false,
+                        is_data_class: false,
+                        is_frozen: false,
                         visibility: Visibility::Public,
                         type_params: vec![],
                         implements: vec![interface_id.to_string()],
@@ -233,6 +246,7 @@ fn build_payment_gateway_fixture(interface_doc_score: f32) -> SemanticData {
                     },
                     enclosing_symbol: Some(paypal_type_id.to_string()),
                     is_external: false,
+                    is_generated: false,
                     documentation: vec![],
                     details: SymbolDetails::Function(FunctionDetails {
                         parameters: vec![Parameter {
@@ -244,6 +258,7 @@ fn build_payment_gateway_fixture(interface_doc_score: f32) -> SemanticData {
                         }],
                         return_types: vec!["bool#".to_string()],
                         type_params: vec![],
+                        throws: vec![],
                         modifiers: FunctionModifiers {
                             is_async: false,
                             is_generator: false,
@@ -275,6 +290,7 @@ fn build_payment_gateway_fixture(interface_doc_score: f32) -> SemanticData {
                     },
                     enclosing_symbol: Some(paypal_type_id.to_string()),
                     is_external: false,
+                    is_generated: false,
                     documentation: vec![],
                     details: SymbolDetails::Function(FunctionDetails {
                         parameters: vec![Parameter {
@@ -286,6 +302,7 @@ fn build_payment_gateway_fixture(interface_doc_score: f32) -> SemanticData {
                         }],
                         return_types: vec!["bool#".to_string()],
                         type_params: vec![],
+                        throws: vec![],
                         modifiers: FunctionModifiers {
                             is_async: false,
                             is_generator: false,
@@ -317,11 +334,13 @@ fn build_payment_gateway_fixture(interface_doc_score: f32) -> SemanticData {
                     },
                     enclosing_symbol: None,
                     is_external: false,
+                    is_generated: false,
                     documentation: vec![],
                     details: SymbolDetails::Function(FunctionDetails {
                         parameters: vec![],
                         return_types: vec!["bool#".to_string()],
                         type_params: vec![],
+                        throws: vec![],
                         modifiers: FunctionModifiers::default(),
                     }),
                 },
@@ -458,6 +477,34 @@ fn test_documented_interface_stops_at_boundary() {
     assert_eq!(result.total_context_size, 20);
 }
 
+#[test]
+fn test_follow_overrides_controls_implementation_expansion() {
+    let graph = Arc::new(build_graph_with_score(0.0));
+    let interface_method_idx = graph
+        .get_node_by_symbol("test#IPaymentGateway#charge().")
+        .expect("Interface method should exist");
+    let call_api_idx = graph
+        .get_node_by_symbol("test#PayPalGateway#_call_paypal_api().")
+        .expect("_call_paypal_api should exist");
+    let call_api_id = graph.node(call_api_idx).core().id;
+
+    // Followed: both (undocumented) implementations are Transparent, so PayPal's helper is pulled in
+    let solver = CfSolver::new(Arc::clone(&graph), PruningParams::academic(0.5));
+    let result = solver.compute_cf(&[interface_method_idx], None);
+    assert_eq!(result.reachable_set.len(), 4);
+    assert!(result.reachable_set.contains(&call_api_id));
+
+    // Not followed: implementations are Boundaries (counted, not expanded)
+    let params = PruningParams {
+        follow_overrides: false,
+        ..PruningParams::academic(0.5)
+    };
+    let solver = CfSolver::new(Arc::clone(&graph), params);
+    let result = solver.compute_cf(&[interface_method_idx], None);
+    assert_eq!(result.reachable_set.len(), 3);
+    assert!(!result.reachable_set.contains(&call_api_id));
+}
+
 #[test]
 fn test_no_implemented_by_for_non_interface_methods() {
     let graph = build_graph_with_score(0.0);
@@ -477,3 +524,156 @@ fn test_no_implemented_by_for_non_interface_methods() {
         "Non-interface methods should not have OverriddenBy edges"
     );
 }
+
+/// Go: `Square` satisfies `Shape` structurally (no `implements` declaration).
+#[test]
+fn test_go_structural_interface_satisfaction_adds_overridden_by() {
+    use common::fixtures::{method_def, type_def};
+    use context_footprint::domain::semantic::TypeKind;
+    use petgraph::visit::EdgeRef;
+
+    let mut area = method_def(
+        "geo/Shape#Area().",
+        "Area",
+        "geo/Shape#",
+        vec![],
+        vec![],
+        None,
+    );
+    if let SymbolDetails::Function(f) = &mut area.details {
+        f.modifiers.is_abstract = true;
+    }
+    let data = SemanticData {
+        project_root: "/test".to_string(),
+        documents: vec![DocumentSemantics {
+            relative_path: "geo/shape.go".to_string(),
+            language: "go".to_string(),
+            definitions: vec![
+                type_def("geo/Shape#", "Shape", vec![], TypeKind::Interface, true),
+                area,
+                type_def("geo/Square#", "Square", vec![], TypeKind::Struct, false),
+                method_def(
+                    "geo/Square#Area().",
+                    "Area",
+                    "geo/Square#",
+                    vec![],
+                    vec![],
+                    None,
+                ),
+                method_def(
+                    "geo/Square#Perimeter().",
+                    "Perimeter",
+                    "geo/Square#",
+                    vec![],
+                    vec![],
+                    None,
+                ),
+                type_def("geo/Point#", "Point", vec![], TypeKind::Struct, false),
+                method_def(
+                    "geo/Point#String().",
+                    "String",
+                    "geo/Point#",
+                    vec![],
+                    vec![],
+                    None,
+                ),
+            ],
+            references: vec![],
+        }],
+        external_symbols: vec![],
+    };
+
+    let reader = MockSourceReader::new().with_file("/test/geo/shape.go", "package geo\n");
+    let builder = GraphBuilder::new(
+        Box::new(MockSizeFunction::new()),
+        Box::new(MockDocScorer::new()),
+    );
+    let graph = builder.build(data, &reader).unwrap();
+
+    let iface_area = graph.get_node_by_symbol("geo/Shape#Area().").unwrap();
+    let square_area = graph.get_node_by_symbol("geo/Square#Area().").unwrap();
+    let overridden_by: Vec<_> = graph
+        .graph
+        .edges(iface_area)
+        .filter(|e| matches!(e.weight(), EdgeKind::OverriddenBy))
+        .map(|e| e.target())
+        .collect();
+    assert_eq!(overridden_by, vec![square_area]);
+    assert_eq!(
+        graph.type_registry.get_implementors("geo/Shape#"),
+        Some(&vec!["geo/Square#".to_string()])
+    );
+}
+
+/// `Leaf(Mid)`, `Mid(Base)`: `Leaf.run` links to `Base.run` because `Mid` does not define it.
+#[test]
+fn test_inherits_edge_skips_bases_without_the_method() {
+    use common::fixtures::{method_def, type_def};
+    use context_footprint::domain::semantic::TypeKind;
+    use petgraph::visit::EdgeRef;
+
+    let class = |id: &str, name: &str, bases: &[&str]| {
+        let mut def = type_def(id, name, vec![], TypeKind::Class, false);
+        if let SymbolDetails::Type(t) = &mut def.details {
+            t.inherits = bases.iter().map(|b| b.to_string()).collect();
+        }
+        def
+    };
+    let data = SemanticData {
+        project_root: "/test".to_string(),
+        documents: vec![DocumentSemantics {
+            relative_path: "jobs.py".to_string(),
+            language: "python".to_string(),
+            definitions: vec![
+                class("jobs/Base#", "Base", &[]),
+                method_def(
+                    "jobs/Base#run().",
+                    "run",
+                    "jobs/Base#",
+                    vec![],
+                    vec![],
+                    None,
+                ),
+                class("jobs/Mid#", "Mid", &["jobs/Base#"]),
+                method_def(
+                    "jobs/Mid#stop().",
+                    "stop",
+                    "jobs/Mid#",
+                    vec![],
+                    vec![],
+                    None,
+                ),
+                class("jobs/Leaf#", "Leaf", &["jobs/Mid#"]),
+                method_def(
+                    "jobs/Leaf#run().",
+                    "run",
+                    "jobs/Leaf#",
+                    vec![],
+                    vec![],
+                    None,
+                ),
+            ],
+            references: vec![],
+        }],
+        external_symbols: vec![],
+    };
+
+    let reader = MockSourceReader::new().with_file("/test/jobs.py", "class Base:\n    pass\n");
+    let builder = GraphBuilder::new(
+        Box::new(MockSizeFunction::new()),
+        Box::new(MockDocScorer::new()),
+    );
+    let graph = builder.build(data, &reader).unwrap();
+
+    let base_run = graph.get_node_by_symbol("jobs/Base#run().").unwrap();
+    let leaf_run = graph.get_node_by_symbol("jobs/Leaf#run().").unwrap();
+    let inherits: Vec<_> = graph
+        .graph
+        .edges(leaf_run)
+        .filter(|e| matches!(e.weight(), EdgeKind::Inherits))
+        .map(|e| e.target())
+        .collect();
+    assert_eq!(inherits, vec![base_run]);
+    let mid_stop = graph.get_node_by_symbol("jobs/Mid#stop().").unwrap();
+    assert_eq!(graph.graph.edges(mid_stop).count(), 0);
+}
@@ -58,11 +58,14 @@ fn test_interface_method_becomes_node_with_flag() {
                     },
                     enclosing_symbol: None,
                     is_external: false,
+                    is_generated: false,
                     documentation: vec!["Repository interface".to_string()],
                     details: SymbolDetails::Type(TypeDetails {
                         kind: context_footprint::domain::semantic::TypeKind::Interface,
                         is_abstract: true,
                         is_final: false,
+                        is_data_class: false,
+                        is_frozen: false,
                         visibility: Visibility::Public,
                         type_params: vec![],
                         implements: vec![],
@@ -89,6 +92,7 @@ fn test_interface_method_becomes_node_with_flag() {
                     },
                     enclosing_symbol: Some(interface_id.to_string()),
                     is_external: false,
+                    is_generated: false,
                     documentation: vec!["Load data".to_string()],
                     details: SymbolDetails::Function(FunctionDetails {
                         parameters: vec![Parameter {
@@ -100,6 +104,7 @@ fn test_interface_method_becomes_node_with_flag() {
                         }],
                         return_types: vec!["dict#".to_string()],
                         type_params: vec![],
+                        throws: vec![],
                         modifiers: FunctionModifiers {
                             is_async: false,
                             is_generator: false,
@@ -184,11 +189,14 @@ fn test_interface_method_with_good_doc_is_boundary() {
                     },
                     enclosing_symbol: None,
                     is_external: false,
+                    is_generated: false,
                     documentation: vec!["Service interface".to_string()],
                     details: SymbolDetails::Type(TypeDetails {
                         kind: context_footprint::domain::semantic::TypeKind::Interface,
                         is_abstract: true,
                         is_final: false,
+                        is_data_class: false,
+                        is_frozen: false,
                         visibility: Visibility::Public,
                         type_params: vec![],
                         implements: vec![],
@@ -214,6 +222,7 @@ fn test_interface_method_with_good_doc_is_boundary() {
                     },
                     enclosing_symbol: Some(interface_id.to_string()),
                     is_external: false,
+                    is_generated: false,
                     documentation: vec![
                         "Process data with detailed documentation explaining the contract"
                             .to_string(),
@@ -228,6 +237,7 @@ fn test_interface_method_with_good_doc_is_boundary() {
                         }],
                         return_types: vec!["bool#".to_string()],
                         type_params: vec![],
+                        throws: vec![],
                         modifiers: FunctionModifiers {
                             is_async: false,
                             is_generator: false,
@@ -265,7 +275,9 @@ fn test_interface_method_with_good_doc_is_boundary() {
 
     assert_eq!(
         decision,
-        context_footprint::domain::policy::PruningDecision::Boundary,
+        context_footprint::domain::policy::PruningDecision::Boundary(
+            context_footprint::domain::policy::BoundaryReason::InterfaceMethod
+        ),
         "Well-documented interface method should be a boundary"
     );
 }
@@ -301,11 +313,14 @@ fn test_call_to_interface_method_creates_edge() {
                     },
                     enclosing_symbol: None,
                     is_external: false,
+                    is_generated: false,
                     documentation: vec!["Data repository".to_string()],
                     details: SymbolDetails::Type(TypeDetails {
                         kind: context_footprint::domain::semantic::TypeKind::Interface,
                         is_abstract: true,
                         is_final: false,
+                        is_data_class: false,
+                        is_frozen: false,
                         visibility: Visibility::Public,
                         type_params: vec![],
                         implements: vec![],
@@ -332,6 +347,7 @@ fn test_call_to_interface_method_creates_edge() {
                     },
                     enclosing_symbol: Some(interface_id.to_string()),
                     is_external: false,
+                    is_generated: false,
                     documentation: vec!["Save data".to_string()],
                     details: SymbolDetails::Function(FunctionDetails {
                         parameters: vec![Parameter {
@@ -343,6 +359,7 @@ fn test_call_to_interface_method_creates_edge() {
                         }],
                         return_types: vec!["bool#".to_string()],
                         type_params: vec![],
+                        throws: vec![],
                         modifiers: FunctionModifiers {
                             is_async: false,
                             is_generator: false,
@@ -374,6 +391,7 @@ fn test_call_to_interface_method_creates_edge() {
                     },
                     enclosing_symbol: None,
                     is_external: false,
+                    is_generated: false,
                     documentation: vec![],
                     details: SymbolDetails::Function(FunctionDetails::default()),
                 },
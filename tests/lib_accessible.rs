@@ -37,6 +37,9 @@ fn test_mock_doc_scorer() {
         name: "foo".into(),
         signature: None,
         language: None,
+        param_names: Vec::new(),
+        has_return_type: false,
+        is_abstract: false,
     };
     assert_eq!(s.score(&info, Some("doc")), 0.8);
     assert_eq!(s.score(&info, None), 0.0);